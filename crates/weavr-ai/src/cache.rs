@@ -0,0 +1,579 @@
+//! On-disk cache of AI suggestions and explanations.
+//!
+//! Asking again for the same hunk (reopening a file, pressing `s` twice)
+//! is answered from the cache instead of the provider. Answers are keyed
+//! by provider, model, [`PROMPT_VERSION`], request kind and the full
+//! [`AiRequest`], so any change to the conflict, its context or the
+//! configured prompt template is a miss.
+//!
+//! Entries older than the TTL are ignored and removed. After each write the
+//! oldest entries are removed until the cache fits in its size limit.
+//! Failures to read or write the cache are never errors: the provider is
+//! simply asked.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use weavr_core::{Fnv1a, Resolution};
+
+use crate::error::AiError;
+use crate::request::AiRequest;
+use crate::AiProvider;
+
+/// Version of the built-in prompts sent to providers. Bump it when they
+/// change, so answers to the old prompts are no longer used.
+pub const PROMPT_VERSION: u32 = 1;
+
+/// Cache configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CacheConfig {
+    /// Whether answers are cached.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// How long an answer stays valid.
+    #[serde(default = "default_ttl", with = "humantime_serde")]
+    pub ttl: Duration,
+
+    /// Maximum size of the cache directory in bytes.
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: u64,
+
+    /// Cache directory; defaults to `ai/` in the user cache directory
+    /// (e.g. `~/.cache/weavr/ai`).
+    pub dir: Option<PathBuf>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            ttl: default_ttl(),
+            max_bytes: default_max_bytes(),
+            dir: None,
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_ttl() -> Duration {
+    Duration::from_secs(7 * 24 * 60 * 60)
+}
+
+fn default_max_bytes() -> u64 {
+    50 * 1024 * 1024
+}
+
+/// A cached answer, stored as one JSON file per key.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    /// When the answer was received, in seconds since the Unix epoch.
+    created: u64,
+    /// The suggested resolution, for suggestion requests.
+    #[serde(default)]
+    suggestion: Option<Resolution>,
+    /// The explanation, for explanation requests.
+    #[serde(default)]
+    explanation: Option<String>,
+}
+
+/// On-disk store of provider answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AiCache {
+    dir: PathBuf,
+    ttl: Duration,
+    max_bytes: u64,
+}
+
+impl AiCache {
+    /// Creates a cache in `dir`.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration, max_bytes: u64) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+            max_bytes,
+        }
+    }
+
+    /// Creates the cache described by `config`, or `None` if caching is
+    /// disabled or there is no user cache directory.
+    #[must_use]
+    pub fn from_config(config: &CacheConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let dir = config.dir.clone().or_else(Self::default_dir)?;
+        Some(Self::new(dir, config.ttl, config.max_bytes))
+    }
+
+    /// Returns the default cache directory, `ai/` in the user cache directory.
+    #[must_use]
+    pub fn default_dir() -> Option<PathBuf> {
+        directories::ProjectDirs::from("", "", "weavr").map(|dirs| dirs.cache_dir().join("ai"))
+    }
+
+    /// Returns the cache directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the key of a request: 16 hex digits, or `None` if the
+    /// request cannot be serialized and so must not be cached.
+    #[must_use]
+    pub fn key(provider: &str, model: &str, kind: &str, request: &AiRequest) -> Option<String> {
+        let request = serde_json::to_string(request).ok()?;
+        let version = PROMPT_VERSION.to_string();
+
+        let mut hash = Fnv1a::new();
+        for part in [provider, model, &version, kind, &request] {
+            hash.feed(part.as_bytes());
+            hash.feed(&[0xff]);
+        }
+        Some(hash.finish_hex())
+    }
+
+    /// Returns the suggestion cached under `key`, if any.
+    #[must_use]
+    pub fn suggestion(&self, key: &str) -> Option<Resolution> {
+        self.read(key)?.suggestion
+    }
+
+    /// Returns the explanation cached under `key`, if any.
+    #[must_use]
+    pub fn explanation(&self, key: &str) -> Option<String> {
+        self.read(key)?.explanation
+    }
+
+    /// Caches a suggestion under `key`.
+    pub fn put_suggestion(&self, key: &str, suggestion: &Resolution) {
+        self.write(
+            key,
+            &Entry {
+                created: now_secs(),
+                suggestion: Some(suggestion.clone()),
+                explanation: None,
+            },
+        );
+    }
+
+    /// Caches an explanation under `key`.
+    pub fn put_explanation(&self, key: &str, explanation: &str) {
+        self.write(
+            key,
+            &Entry {
+                created: now_secs(),
+                suggestion: None,
+                explanation: Some(explanation.to_string()),
+            },
+        );
+    }
+
+    /// Removes the entry cached under `key`, if any.
+    pub fn remove(&self, key: &str) {
+        let _ = std::fs::remove_file(self.file_for(key));
+    }
+
+    /// Reads a fresh entry, removing it if it has expired.
+    fn read(&self, key: &str) -> Option<Entry> {
+        let path = self.file_for(key);
+        let content = std::fs::read_to_string(&path).ok()?;
+        let entry: Entry = serde_json::from_str(&content).ok()?;
+        if now_secs().saturating_sub(entry.created) >= self.ttl.as_secs() {
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+        Some(entry)
+    }
+
+    /// Writes an entry, then trims the cache to its size limit.
+    fn write(&self, key: &str, entry: &Entry) {
+        let Ok(json) = serde_json::to_string(entry) else {
+            return;
+        };
+        if std::fs::create_dir_all(&self.dir).is_ok()
+            && std::fs::write(self.file_for(key), json).is_ok()
+        {
+            self.prune();
+        }
+    }
+
+    /// Removes the oldest entries until the cache fits in `max_bytes`.
+    fn prune(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+            })
+            .collect();
+
+        let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+        files.sort();
+        for (_, size, path) in files {
+            if total <= self.max_bytes {
+                break;
+            }
+            if std::fs::remove_file(path).is_ok() {
+                total -= size;
+            }
+        }
+    }
+
+    /// Returns the file for `key`, keeping only hex digits so a key cannot
+    /// name a file outside the cache.
+    fn file_for(&self, key: &str) -> PathBuf {
+        let name: String = key.chars().filter(char::is_ascii_hexdigit).collect();
+        self.dir.join(format!("{name}.json"))
+    }
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// An [`AiProvider`] that answers repeated requests from an [`AiCache`].
+///
+/// Only answers are cached; declined requests, errors and requests that
+/// cannot be serialized into a key go to the provider every time. A
+/// suggestion the strategy rejects is removed again.
+pub struct CachedProvider {
+    inner: Box<dyn AiProvider>,
+    cache: AiCache,
+}
+
+impl CachedProvider {
+    /// Wraps `inner` with `cache`.
+    #[must_use]
+    pub fn new(inner: Box<dyn AiProvider>, cache: AiCache) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Returns the key of a request to the wrapped provider.
+    fn key(&self, kind: &str, request: &AiRequest) -> Option<String> {
+        AiCache::key(self.inner.name(), self.inner.model(), kind, request)
+    }
+}
+
+#[async_trait]
+impl AiProvider for CachedProvider {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    async fn suggest(&self, request: &AiRequest) -> Result<Option<Resolution>, AiError> {
+        let Some(key) = self.key("suggest", request) else {
+            return self.inner.suggest(request).await;
+        };
+        if let Some(resolution) = self.cache.suggestion(&key) {
+            return Ok(Some(resolution));
+        }
+        let resolution = self.inner.suggest(request).await?;
+        if let Some(resolution) = &resolution {
+            self.cache.put_suggestion(&key, resolution);
+        }
+        Ok(resolution)
+    }
+
+    async fn explain(&self, request: &AiRequest) -> Result<Option<String>, AiError> {
+        let Some(key) = self.key("explain", request) else {
+            return self.inner.explain(request).await;
+        };
+        if let Some(explanation) = self.cache.explanation(&key) {
+            return Ok(Some(explanation));
+        }
+        let explanation = self.inner.explain(request).await?;
+        if let Some(explanation) = &explanation {
+            self.cache.put_explanation(&key, explanation);
+        }
+        Ok(explanation)
+    }
+
+    fn reject_suggestion(&self, request: &AiRequest) {
+        if let Some(key) = self.key("suggest", request) {
+            self.cache.remove(&key);
+        }
+        self.inner.reject_suggestion(request);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::providers::test_request::request;
+
+    /// Counts calls and answers with `answer`, or the request's left side.
+    struct CountingProvider {
+        model: &'static str,
+        calls: Arc<AtomicUsize>,
+        answer: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl AiProvider for CountingProvider {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn model(&self) -> &str {
+            self.model
+        }
+
+        async fn suggest(&self, request: &AiRequest) -> Result<Option<Resolution>, AiError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let answer = self
+                .answer
+                .map_or_else(|| request.left.clone(), String::from);
+            Ok(Some(Resolution::manual(answer)))
+        }
+
+        async fn explain(&self, _request: &AiRequest) -> Result<Option<String>, AiError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Some("explained".into()))
+        }
+    }
+
+    fn cached(cache: AiCache, model: &'static str) -> (CachedProvider, Arc<AtomicUsize>) {
+        answering(cache, model, None)
+    }
+
+    fn answering(
+        cache: AiCache,
+        model: &'static str,
+        answer: Option<&'static str>,
+    ) -> (CachedProvider, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = CountingProvider {
+            model,
+            calls: Arc::clone(&calls),
+            answer,
+        };
+        (CachedProvider::new(Box::new(provider), cache), calls)
+    }
+
+    #[tokio::test]
+    async fn rejected_suggestions_are_not_answered_from_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = AiCache::new(dir.path(), default_ttl(), default_max_bytes());
+        let config = crate::AiConfig {
+            enabled: true,
+            min_confidence: 0,
+            ..crate::AiConfig::default()
+        };
+
+        // Below min_confidence: the answer has no confidence at all
+        let (provider, calls) = cached(cache.clone(), "m1");
+        let strategy = crate::AiStrategy::new(
+            Box::new(provider),
+            crate::AiConfig {
+                min_confidence: 50,
+                ..config.clone()
+            },
+        );
+        assert!(strategy
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(strategy
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Failing verification
+        let (provider, calls) = answering(cache, "m2", Some("<<<<<<< HEAD\nleft"));
+        let strategy = crate::AiStrategy::new(Box::new(provider), config);
+        for _ in 0..2 {
+            let err = strategy
+                .suggest(&request("left", "right", None, "file.txt"))
+                .await
+                .unwrap_err();
+            assert!(matches!(err, AiError::InvalidSuggestion(_)));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn repeated_requests_are_answered_from_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = AiCache::new(dir.path(), default_ttl(), default_max_bytes());
+        let (provider, calls) = cached(cache.clone(), "m1");
+
+        let first = provider
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        let second = provider
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        provider
+            .suggest(&request("other", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Explanations are cached separately from suggestions
+        let explanation = provider
+            .explain(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(explanation.as_deref(), Some("explained"));
+        provider
+            .explain(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Another model does not see these answers
+        let (other_model, other_calls) = cached(cache, "m2");
+        other_model
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(other_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = AiCache::new(dir.path(), Duration::ZERO, default_max_bytes());
+        let (provider, calls) = cached(cache, "m1");
+
+        provider
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        provider
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn prune_keeps_the_cache_under_its_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = AiCache::new(dir.path(), default_ttl(), 300);
+
+        for i in 0..10 {
+            cache.put_explanation(&format!("{i:016x}"), &"x".repeat(50));
+        }
+        let size: u64 = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().metadata().unwrap().len())
+            .sum();
+        assert!(size <= 300);
+        assert!(cache.explanation(&format!("{:016x}", 9)).is_some());
+    }
+
+    #[test]
+    fn keys_depend_on_provider_model_and_request() {
+        let key = AiCache::key(
+            "claude",
+            "m1",
+            "suggest",
+            &request("left", "right", None, "file.txt"),
+        );
+        assert_eq!(key.as_ref().map(String::len), Some(16));
+        assert_eq!(
+            key,
+            AiCache::key(
+                "claude",
+                "m1",
+                "suggest",
+                &request("left", "right", None, "file.txt")
+            )
+        );
+        assert_ne!(
+            key,
+            AiCache::key(
+                "openai",
+                "m1",
+                "suggest",
+                &request("left", "right", None, "file.txt")
+            )
+        );
+        assert_ne!(
+            key,
+            AiCache::key(
+                "claude",
+                "m2",
+                "suggest",
+                &request("left", "right", None, "file.txt")
+            )
+        );
+        assert_ne!(
+            key,
+            AiCache::key(
+                "claude",
+                "m1",
+                "explain",
+                &request("left", "right", None, "file.txt")
+            )
+        );
+        assert_ne!(
+            key,
+            AiCache::key(
+                "claude",
+                "m1",
+                "suggest",
+                &request("other", "right", None, "file.txt")
+            )
+        );
+
+        let template = crate::PromptTemplate::parse("House rules.\n{conflict}").unwrap();
+        assert_ne!(
+            key,
+            AiCache::key(
+                "claude",
+                "m1",
+                "suggest",
+                &request("left", "right", None, "file.txt").with_template(Some(template))
+            )
+        );
+    }
+
+    #[test]
+    fn disabled_cache_is_none() {
+        let config = CacheConfig {
+            enabled: false,
+            ..CacheConfig::default()
+        };
+        assert!(AiCache::from_config(&config).is_none());
+
+        let config = CacheConfig {
+            dir: Some("/tmp/weavr-ai-cache".into()),
+            ..CacheConfig::default()
+        };
+        assert_eq!(
+            AiCache::from_config(&config).unwrap().dir(),
+            Path::new("/tmp/weavr-ai-cache")
+        );
+    }
+}
@@ -3,12 +3,14 @@
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+use crate::cache::CacheConfig;
 #[cfg(feature = "ai-claude")]
 use crate::providers::claude::ClaudeConfig;
 #[cfg(feature = "ai-local")]
 use crate::providers::local::LocalConfig;
 #[cfg(feature = "ai-openai")]
 use crate::providers::openai::OpenAiConfig;
+use crate::template::PromptConfig;
 
 /// Top-level AI configuration.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -32,6 +34,31 @@ pub struct AiConfig {
     #[serde(default)]
     pub auto_suggest: bool,
 
+    /// Lines of surrounding file content sent on either side of a conflict.
+    #[serde(default = "default_context_lines")]
+    pub context_lines: usize,
+
+    /// Maximum requests in flight when suggesting for several hunks.
+    #[serde(default = "default_max_in_flight")]
+    pub max_in_flight: usize,
+
+    /// Retries of a request after a rate limit, timeout or server error.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled for each further retry. A
+    /// delay asked for by the provider (`Retry-After`) takes precedence.
+    #[serde(default = "default_retry_backoff", with = "humantime_serde")]
+    pub retry_backoff: Duration,
+
+    /// On-disk cache of suggestions and explanations.
+    #[serde(default)]
+    pub cache: CacheConfig,
+
+    /// Prompt templates replacing the built-in prompts, for all providers.
+    #[serde(default)]
+    pub prompts: PromptConfig,
+
     /// Claude-specific configuration.
     #[cfg(feature = "ai-claude")]
     #[serde(default)]
@@ -56,6 +83,12 @@ impl Default for AiConfig {
             timeout: default_timeout(),
             min_confidence: 70,
             auto_suggest: false,
+            context_lines: default_context_lines(),
+            max_in_flight: default_max_in_flight(),
+            max_retries: default_max_retries(),
+            retry_backoff: default_retry_backoff(),
+            cache: CacheConfig::default(),
+            prompts: PromptConfig::default(),
             #[cfg(feature = "ai-claude")]
             claude: ClaudeConfig::default(),
             #[cfg(feature = "ai-openai")]
@@ -74,9 +107,26 @@ fn default_min_confidence() -> u8 {
     70
 }
 
+fn default_context_lines() -> usize {
+    20
+}
+
+fn default_max_in_flight() -> usize {
+    4
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_backoff() -> Duration {
+    Duration::from_secs(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::template::TemplateSource;
 
     #[test]
     fn default_config() {
@@ -85,5 +135,71 @@ mod tests {
         assert!(config.provider.is_none());
         assert_eq!(config.timeout, Duration::from_secs(30));
         assert_eq!(config.min_confidence, 70);
+        assert_eq!(config.context_lines, 20);
+        assert_eq!(config.max_in_flight, 4);
+        assert_eq!(config.max_retries, 3);
+        assert_eq!(config.retry_backoff, Duration::from_secs(1));
+        assert!(config.cache.enabled);
+        assert_eq!(config.cache.ttl, Duration::from_secs(7 * 24 * 60 * 60));
+    }
+
+    #[test]
+    fn deserialize_cache_settings() {
+        let toml = r#"
+            [cache]
+            ttl = "1day"
+            max_bytes = 1048576
+            dir = "/tmp/weavr"
+        "#;
+
+        let config: AiConfig = toml::from_str(toml).unwrap();
+        assert!(config.cache.enabled);
+        assert_eq!(config.cache.ttl, Duration::from_secs(24 * 60 * 60));
+        assert_eq!(config.cache.max_bytes, 1_048_576);
+        assert_eq!(config.cache.dir, Some("/tmp/weavr".into()));
+    }
+
+    #[test]
+    fn deserialize_prompt_templates() {
+        let toml = r#"
+            [prompts]
+            merge = "Prefer theirs for lockfiles.\n{conflict}"
+
+            [[prompts.overrides]]
+            path = "migrations/*.sql"
+            merge = { file = "prompts/migrations.txt" }
+        "#;
+
+        let config: AiConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.prompts.merge,
+            Some(TemplateSource::Inline(
+                "Prefer theirs for lockfiles.\n{conflict}".into()
+            ))
+        );
+        assert!(config.prompts.explain.is_none());
+        let over = &config.prompts.overrides[0];
+        assert_eq!(over.path.as_deref(), Some("migrations/*.sql"));
+        assert_eq!(
+            over.merge,
+            Some(TemplateSource::File {
+                file: "prompts/migrations.txt".into()
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_batch_settings() {
+        let toml = r#"
+            max_in_flight = 8
+            max_retries = 0
+            retry_backoff = "250ms"
+        "#;
+
+        let config: AiConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.max_in_flight, 8);
+        assert_eq!(config.max_retries, 0);
+        assert_eq!(config.retry_backoff, Duration::from_millis(250));
+        assert_eq!(config.timeout, Duration::from_secs(30));
     }
 }
@@ -47,6 +47,14 @@ pub enum AiError {
     #[error("provider '{0}' is not available")]
     ProviderNotAvailable(String),
 
+    /// A configured prompt template cannot be read or is invalid.
+    #[error("invalid prompt template: {0}")]
+    InvalidPrompt(String),
+
+    /// The suggestion failed verification and must not be offered.
+    #[error("suggestion rejected: {0}")]
+    InvalidSuggestion(String),
+
     /// Context too large for provider.
     #[error("conflict too large: {size} bytes exceeds {max} byte limit")]
     ContextTooLarge {
@@ -56,3 +64,69 @@ pub enum AiError {
         max: usize,
     },
 }
+
+impl AiError {
+    /// Returns true if the same request may succeed when retried: rate
+    /// limits, timeouts, network failures and server errors (5xx).
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } | Self::Timeout(_) => true,
+            #[cfg(any(feature = "ai-claude", feature = "ai-openai", feature = "ai-local"))]
+            Self::NetworkError(_) => true,
+            Self::ProviderError { status, .. } => *status >= 500,
+            Self::ApiKeyError(_)
+            | Self::ParseError(_)
+            | Self::ProviderNotAvailable(_)
+            | Self::InvalidPrompt(_)
+            | Self::InvalidSuggestion(_)
+            | Self::ContextTooLarge { .. } => false,
+        }
+    }
+
+    /// Returns how long the provider asked to wait before retrying, if it did.
+    #[must_use]
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited {
+                retry_after_secs: Some(secs),
+                ..
+            } => Some(Duration::from_secs(*secs)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transient_errors_are_retryable() {
+        let rate_limited = AiError::RateLimited {
+            provider: "claude".into(),
+            retry_after_secs: Some(5),
+        };
+        assert!(rate_limited.is_retryable());
+        assert_eq!(rate_limited.retry_after(), Some(Duration::from_secs(5)));
+        assert!(AiError::Timeout(Duration::from_secs(30)).is_retryable());
+
+        let server_error = |status| AiError::ProviderError {
+            provider: "openai".into(),
+            status,
+            message: String::new(),
+        };
+        assert!(server_error(503).is_retryable());
+        assert!(!server_error(400).is_retryable());
+        assert!(server_error(503).retry_after().is_none());
+    }
+
+    #[test]
+    fn permanent_errors_are_not_retryable() {
+        assert!(!AiError::ApiKeyError("missing".into()).is_retryable());
+        assert!(!AiError::ParseError("bad json".into()).is_retryable());
+        assert!(!AiError::ProviderNotAvailable("local".into()).is_retryable());
+        assert!(!AiError::InvalidSuggestion("markers".into()).is_retryable());
+        assert!(!AiError::InvalidPrompt("no placeholders".into()).is_retryable());
+    }
+}
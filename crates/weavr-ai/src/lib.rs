@@ -1,7 +1,9 @@
 //! AI provider integrations for weavr merge conflict resolver.
 //!
 //! This crate provides AI-assisted conflict resolution through various providers.
-//! All AI features are opt-in and suggestions **never auto-apply**.
+//! All AI features are opt-in. Suggestions are only applied automatically in
+//! headless mode, and only when they reach `min_confidence` and pass
+//! verification (see [`verify`]); the TUI never applies one unasked.
 //!
 //! # Feature Flags
 //!
@@ -10,10 +12,14 @@
 //! - `ai-local` - Enables local LLM support (Ollama, etc.)
 //! - `all-providers` - Enables all providers
 //!
+//! Answers can be cached on disk by wrapping a provider in a
+//! [`CachedProvider`]. The prompts sent to every provider can be replaced
+//! with templates (see [`template`]).
+//!
 //! # Example
 //!
 //! ```ignore
-//! use weavr_ai::{AiProvider, AiStrategy, AiConfig};
+//! use weavr_ai::{AiConfig, AiProvider, AiRequest, AiStrategy};
 //! use weavr_ai::providers::ClaudeProvider;
 //!
 //! let config = AiConfig {
@@ -25,23 +31,31 @@
 //! let provider = ClaudeProvider::new(&config.claude)?;
 //! let strategy = AiStrategy::new(Box::new(provider), config);
 //!
-//! // Request a suggestion (async)
-//! let suggestion = strategy.suggest(&hunk).await?;
+//! // Request a suggestion (async), with 20 lines of file context
+//! let mut hunk = session.hunk(hunk_id).unwrap().clone();
+//! hunk.context = session.context_window(hunk_id, 20).unwrap();
+//! let request = AiRequest::from_hunk(&hunk, Some("src/lib.rs"));
+//! let suggestion = strategy.suggest(&request).await?;
 //! ```
 
+pub mod cache;
 pub mod config;
 pub mod error;
 pub mod providers;
 pub mod request;
 pub mod strategy;
+pub mod template;
+pub mod verify;
 
+pub use cache::{AiCache, CacheConfig, CachedProvider};
 pub use config::AiConfig;
 pub use error::AiError;
 pub use request::{AiRequest, AiResponse, ConflictContext};
-pub use strategy::AiStrategy;
+pub use strategy::{AiStrategy, BatchProgress, Suggestion};
+pub use template::{PromptConfig, PromptOverride, PromptTemplate, Prompts, TemplateSource};
+pub use verify::{LineOrigin, Provenance, Verification};
 
 use async_trait::async_trait;
-use weavr_core::ConflictHunk;
 use weavr_core::Resolution;
 
 /// Trait for AI providers that can suggest conflict resolutions.
@@ -53,23 +67,35 @@ use weavr_core::Resolution;
 ///
 /// - `suggest` should return a `Resolution` with `ResolutionStrategyKind::AiSuggested`
 /// - `explain` provides natural language explanation without suggesting a resolution
+/// - The request carries the file path, language, marker labels, commit
+///   subjects and surrounding file content; providers should pass them on
+/// - The request may carry a [`PromptTemplate`] to build the prompt from
+///   instead of the built-in one
 /// - Both methods may return `Ok(None)` if the provider declines to respond
 #[async_trait]
 pub trait AiProvider: Send + Sync {
     /// Returns the provider name (e.g., "claude", "openai", "local").
     fn name(&self) -> &'static str;
 
-    /// Suggests a resolution for the given conflict hunk.
+    /// Returns the model answering requests, as configured.
+    fn model(&self) -> &str;
+
+    /// Suggests a resolution for the conflict described by `request`.
     ///
     /// Returns `Ok(Some(resolution))` if a suggestion was generated,
     /// `Ok(None)` if the provider declined to suggest (e.g., low confidence),
     /// or `Err(AiError)` if an error occurred.
-    async fn suggest(&self, hunk: &ConflictHunk) -> Result<Option<Resolution>, AiError>;
+    async fn suggest(&self, request: &AiRequest) -> Result<Option<Resolution>, AiError>;
 
     /// Generates a natural-language explanation of the conflict.
     ///
     /// Returns `Ok(Some(explanation))` if generated successfully,
     /// `Ok(None)` if the provider declined to explain,
     /// or `Err(AiError)` if an error occurred.
-    async fn explain(&self, hunk: &ConflictHunk) -> Result<Option<String>, AiError>;
+    async fn explain(&self, request: &AiRequest) -> Result<Option<String>, AiError>;
+
+    /// Called when the suggestion returned for `request` was rejected
+    /// (below `min_confidence` or failing verification), so it is not
+    /// given again. Does nothing by default.
+    fn reject_suggestion(&self, _request: &AiRequest) {}
 }
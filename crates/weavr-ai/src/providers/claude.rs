@@ -2,14 +2,14 @@
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use weavr_core::{
-    ConflictHunk, Resolution, ResolutionMetadata, ResolutionSource, ResolutionStrategyKind,
-};
+use weavr_core::{Resolution, ResolutionMetadata, ResolutionSource, ResolutionStrategyKind};
 
 use crate::error::AiError;
 use crate::request::{AiRequest, AiResponse};
 use crate::AiProvider;
 
+use super::prompt;
+
 /// Claude API response structure.
 #[derive(Deserialize)]
 struct ClaudeApiResponse {
@@ -22,14 +22,6 @@ struct ContentBlock {
     text: Option<String>,
 }
 
-/// Raw AI response with f32 confidence (as returned by the model).
-#[derive(Deserialize)]
-struct RawAiResponse {
-    suggestion: String,
-    confidence: f32,
-    explanation: Option<String>,
-}
-
 /// Claude provider configuration.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ClaudeConfig {
@@ -117,86 +109,6 @@ impl ClaudeProvider {
         })
     }
 
-    /// Builds a prompt for merge conflict resolution.
-    fn build_merge_prompt(request: &AiRequest) -> String {
-        let base_section = request
-            .base
-            .as_ref()
-            .map(|b| format!("\nBase (common ancestor):\n```\n{b}\n```\n"))
-            .unwrap_or_default();
-
-        let language_hint = request
-            .context
-            .language
-            .as_ref()
-            .map(|l| format!("\nLanguage: {l}"))
-            .unwrap_or_default();
-
-        format!(
-            r#"You are a merge conflict resolver. Given two versions of code that conflict, suggest a merged resolution.
-
-Left (ours/HEAD):
-```
-{}
-```
-
-Right (theirs/incoming):
-```
-{}
-```
-{base_section}
-Context before conflict: {:?}
-Context after conflict: {:?}
-{language_hint}
-
-Respond with ONLY valid JSON (no markdown, no explanation outside JSON):
-{{
-  "suggestion": "the merged content exactly as it should appear",
-  "confidence": 0.85,
-  "explanation": "brief explanation of how you merged the changes"
-}}
-
-Important:
-- The "suggestion" field must contain the exact merged content
-- Confidence should be 0.0-1.0 based on how certain you are
-- Preserve original formatting, indentation, and line endings"#,
-            request.left, request.right, request.context.before, request.context.after
-        )
-    }
-
-    /// Builds a prompt for explaining a conflict.
-    fn build_explain_prompt(request: &AiRequest) -> String {
-        let base_section = request
-            .base
-            .as_ref()
-            .map(|b| format!("\nBase (common ancestor):\n```\n{b}\n```\n"))
-            .unwrap_or_default();
-
-        format!(
-            r"You are a merge conflict analyzer. Explain the differences between these two versions of code.
-
-Left (ours/HEAD):
-```
-{}
-```
-
-Right (theirs/incoming):
-```
-{}
-```
-{base_section}
-
-Provide a clear, concise explanation of:
-1. What changed on the left side
-2. What changed on the right side
-3. Why they conflict
-4. Suggestions for resolution
-
-Keep the explanation brief and technical.",
-            request.left, request.right
-        )
-    }
-
     /// Parses the Claude API response into an `AiResponse`.
     fn parse_response(response_body: &str) -> Result<AiResponse, AiError> {
         let claude_response: ClaudeApiResponse = serde_json::from_str(response_body)
@@ -208,42 +120,7 @@ Keep the explanation brief and technical.",
             .find_map(|c| c.text)
             .ok_or_else(|| AiError::ParseError("no text in Claude response".into()))?;
 
-        // Clean up the response text - models sometimes wrap JSON in code fences
-        let cleaned = Self::extract_json(&text);
-
-        // Parse the raw JSON (with f32 confidence)
-        let raw: RawAiResponse = serde_json::from_str(cleaned).map_err(|e| {
-            AiError::ParseError(format!(
-                "failed to parse AI response JSON: {e}\nRaw text: {text}"
-            ))
-        })?;
-
-        // Convert f32 confidence (0.0-1.0) to u8 percentage (0-100)
-        // The clamp ensures value is in [0.0, 100.0], so truncation and sign loss are safe.
-        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-        let confidence = (raw.confidence * 100.0).round().clamp(0.0, 100.0) as u8;
-
-        Ok(AiResponse {
-            suggestion: raw.suggestion,
-            confidence,
-            explanation: raw.explanation,
-        })
-    }
-
-    /// Extracts JSON from text that may be wrapped in code fences.
-    fn extract_json(text: &str) -> &str {
-        let trimmed = text.trim();
-
-        // Strip ```json or ``` prefix
-        let without_prefix = trimmed
-            .strip_prefix("```json")
-            .or_else(|| trimmed.strip_prefix("```"))
-            .map_or(trimmed, str::trim_start);
-
-        // Strip trailing ```
-        without_prefix
-            .strip_suffix("```")
-            .map_or(without_prefix, str::trim_end)
+        prompt::parse_suggestion(&text)
     }
 }
 
@@ -253,9 +130,12 @@ impl AiProvider for ClaudeProvider {
         "claude"
     }
 
-    async fn suggest(&self, hunk: &ConflictHunk) -> Result<Option<Resolution>, AiError> {
-        let request = AiRequest::from_hunk(hunk, None);
-        let prompt = Self::build_merge_prompt(&request);
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn suggest(&self, request: &AiRequest) -> Result<Option<Resolution>, AiError> {
+        let prompt = prompt::build_merge_prompt(request);
 
         let response = self
             .client
@@ -277,16 +157,17 @@ impl AiProvider for ClaudeProvider {
         let status = response.status();
         if !status.is_success() {
             let status_code = status.as_u16();
-            let message = response.text().await.unwrap_or_default();
 
             // Check for rate limiting
             if status_code == 429 {
                 return Err(AiError::RateLimited {
                     provider: "claude".into(),
-                    retry_after_secs: None,
+                    retry_after_secs: prompt::retry_after_secs(&response),
                 });
             }
 
+            let message = response.text().await.unwrap_or_default();
+
             return Err(AiError::ProviderError {
                 provider: "claude".into(),
                 status: status_code,
@@ -310,9 +191,8 @@ impl AiProvider for ClaudeProvider {
         }))
     }
 
-    async fn explain(&self, hunk: &ConflictHunk) -> Result<Option<String>, AiError> {
-        let request = AiRequest::from_hunk(hunk, None);
-        let prompt = Self::build_explain_prompt(&request);
+    async fn explain(&self, request: &AiRequest) -> Result<Option<String>, AiError> {
+        let prompt = prompt::build_explain_prompt(request);
 
         let response = self
             .client
@@ -377,42 +257,4 @@ mod tests {
         assert_eq!(config.model, "claude-opus-4-20250514");
         assert_eq!(config.max_tokens, 8192);
     }
-
-    #[test]
-    fn parse_raw_ai_response() {
-        let json =
-            r#"{"suggestion": "merged code", "confidence": 0.9, "explanation": "Combined both"}"#;
-        let raw: RawAiResponse = serde_json::from_str(json).unwrap();
-        assert_eq!(raw.suggestion, "merged code");
-        assert!((raw.confidence - 0.9).abs() < f32::EPSILON);
-        assert_eq!(raw.explanation, Some("Combined both".into()));
-
-        // Test conversion to u8 percentage
-        let confidence = (raw.confidence * 100.0).round() as u8;
-        assert_eq!(confidence, 90);
-    }
-
-    #[test]
-    fn extract_json_plain() {
-        let text = r#"{"suggestion": "code", "confidence": 0.8}"#;
-        assert_eq!(ClaudeProvider::extract_json(text), text);
-    }
-
-    #[test]
-    fn extract_json_with_fences() {
-        let text = "```json\n{\"suggestion\": \"code\"}\n```";
-        assert_eq!(
-            ClaudeProvider::extract_json(text),
-            "{\"suggestion\": \"code\"}"
-        );
-    }
-
-    #[test]
-    fn extract_json_with_plain_fences() {
-        let text = "```\n{\"suggestion\": \"code\"}\n```";
-        assert_eq!(
-            ClaudeProvider::extract_json(text),
-            "{\"suggestion\": \"code\"}"
-        );
-    }
 }
@@ -1,29 +1,74 @@
 //! Local LLM provider implementation (e.g., Ollama).
+//!
+//! Talks to a model server on the user's machine or network, so no code
+//! is sent to a hosted API. Two request formats are supported: Ollama's
+//! `/api/generate` and the `OpenAI`-compatible `/v1/chat/completions`
+//! served by llama.cpp, vLLM, LM Studio and Ollama itself.
+
+use std::time::Duration;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use weavr_core::{ConflictHunk, Resolution};
+use weavr_core::{Resolution, ResolutionMetadata, ResolutionSource, ResolutionStrategyKind};
 
 use crate::error::AiError;
+use crate::request::AiRequest;
 use crate::AiProvider;
 
+use super::prompt;
+
+/// Ollama `/api/generate` response structure (non-streaming).
+#[derive(Deserialize)]
+struct GenerateResponse {
+    response: String,
+}
+
+/// Request format spoken by a local model server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LocalApi {
+    /// Ollama's `/api/generate`.
+    Ollama,
+    /// `OpenAI`-compatible `/v1/chat/completions`.
+    OpenAi,
+}
+
+impl LocalApi {
+    /// Guesses the format from the endpoint URL: chat completions for
+    /// URLs ending in `/chat/completions`, Ollama otherwise.
+    #[must_use]
+    pub fn detect(endpoint: &str) -> Self {
+        if endpoint
+            .trim_end_matches('/')
+            .ends_with("/chat/completions")
+        {
+            Self::OpenAi
+        } else {
+            Self::Ollama
+        }
+    }
+}
+
 /// Local LLM provider configuration.
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct LocalConfig {
-    /// Endpoint URL (e.g., `http://localhost:11434/api/generate` for Ollama).
+    /// Endpoint URL (e.g., `http://localhost:11434/api/generate` for Ollama,
+    /// or `http://localhost:8080/v1/chat/completions` for llama.cpp).
     pub endpoint: Option<String>,
 
     /// Model name.
     pub model: Option<String>,
+
+    /// Request format; detected from the endpoint when not set.
+    pub api: Option<LocalApi>,
 }
 
 /// Local LLM provider.
 pub struct LocalProvider {
-    #[allow(dead_code)]
     endpoint: String,
-    #[allow(dead_code)]
     model: String,
-    #[allow(dead_code)]
+    api: LocalApi,
+    timeout: Duration,
     client: reqwest::Client,
 }
 
@@ -34,6 +79,15 @@ impl LocalProvider {
     ///
     /// Returns an error if the endpoint or model is not configured.
     pub fn new(config: &LocalConfig) -> Result<Self, AiError> {
+        Self::with_timeout(config, Duration::from_secs(30))
+    }
+
+    /// Creates a new local LLM provider with a custom timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint or model is not configured.
+    pub fn with_timeout(config: &LocalConfig, timeout: Duration) -> Result<Self, AiError> {
         let endpoint = config.endpoint.clone().ok_or_else(|| {
             AiError::ProviderNotAvailable("local LLM endpoint not configured".into())
         })?;
@@ -42,12 +96,86 @@ impl LocalProvider {
             AiError::ProviderNotAvailable("local LLM model not configured".into())
         })?;
 
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| {
+                AiError::ProviderNotAvailable(format!("failed to build HTTP client: {e}"))
+            })?;
+
         Ok(Self {
+            api: config.api.unwrap_or_else(|| LocalApi::detect(&endpoint)),
             endpoint,
             model,
-            client: reqwest::Client::new(),
+            timeout,
+            client,
         })
     }
+
+    /// Sends `prompt` to the server and returns the reply text.
+    ///
+    /// With `json`, Ollama is asked to constrain its output to JSON.
+    async fn complete(&self, prompt: &str, json: bool) -> Result<Option<String>, AiError> {
+        let body = match self.api {
+            LocalApi::Ollama => {
+                let mut body = serde_json::json!({
+                    "model": self.model,
+                    "prompt": prompt,
+                    "stream": false
+                });
+                if json {
+                    body["format"] = "json".into();
+                }
+                body
+            }
+            LocalApi::OpenAi => serde_json::json!({
+                "model": self.model,
+                "stream": false,
+                "messages": [{
+                    "role": "user",
+                    "content": prompt
+                }]
+            }),
+        };
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("content-type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| self.request_error(e))?;
+        let response = prompt::check_status(response, "local").await?;
+
+        let body = response.text().await.map_err(|e| self.request_error(e))?;
+        self.parse_response(&body)
+    }
+
+    /// Maps a failed request, explaining connection failures.
+    fn request_error(&self, error: reqwest::Error) -> AiError {
+        if error.is_connect() {
+            return AiError::ProviderNotAvailable(format!(
+                "local LLM server at {} is not reachable ({error})",
+                self.endpoint
+            ));
+        }
+        prompt::request_error(error, self.timeout)
+    }
+
+    /// Extracts the reply text from the server's response.
+    fn parse_response(&self, response_body: &str) -> Result<Option<String>, AiError> {
+        match self.api {
+            LocalApi::Ollama => {
+                let response: GenerateResponse =
+                    serde_json::from_str(response_body).map_err(|e| {
+                        AiError::ParseError(format!("failed to parse Ollama response: {e}"))
+                    })?;
+                Ok(Some(response.response))
+            }
+            LocalApi::OpenAi => prompt::chat_completion_text(response_body, "local LLM"),
+        }
+    }
 }
 
 #[async_trait]
@@ -56,30 +184,60 @@ impl AiProvider for LocalProvider {
         "local"
     }
 
-    async fn suggest(&self, _hunk: &ConflictHunk) -> Result<Option<Resolution>, AiError> {
-        // TODO: Implement local LLM API integration (Ollama, llama.cpp, etc.)
-        Err(AiError::ProviderNotAvailable(
-            "Local LLM provider not yet implemented".into(),
-        ))
+    fn model(&self) -> &str {
+        &self.model
     }
 
-    async fn explain(&self, _hunk: &ConflictHunk) -> Result<Option<String>, AiError> {
-        // TODO: Implement local LLM API integration
-        Err(AiError::ProviderNotAvailable(
-            "Local LLM provider not yet implemented".into(),
-        ))
+    async fn suggest(&self, request: &AiRequest) -> Result<Option<Resolution>, AiError> {
+        let text = self
+            .complete(&prompt::build_merge_prompt(request), true)
+            .await?
+            .ok_or_else(|| AiError::ParseError("no text in local LLM response".into()))?;
+        let ai_response = prompt::parse_suggestion(&text)?;
+
+        Ok(Some(Resolution {
+            kind: ResolutionStrategyKind::AiSuggested {
+                provider: "local".into(),
+            },
+            content: ai_response.suggestion,
+            metadata: ResolutionMetadata {
+                source: ResolutionSource::Ai,
+                notes: ai_response.explanation,
+                confidence: Some(ai_response.confidence),
+            },
+        }))
+    }
+
+    async fn explain(&self, request: &AiRequest) -> Result<Option<String>, AiError> {
+        self.complete(&prompt::build_explain_prompt(request), false)
+            .await
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::test_request::request;
+    use super::super::test_server::{self, Reply};
     use super::*;
 
+    fn provider(endpoint: String, api: Option<LocalApi>) -> LocalProvider {
+        let config = LocalConfig {
+            endpoint: Some(endpoint),
+            model: Some("codellama".into()),
+            api,
+        };
+        LocalProvider::with_timeout(&config, Duration::from_secs(5)).unwrap()
+    }
+
+    const SUGGESTION: &str =
+        r#"{"suggestion": "right", "confidence": 0.6, "explanation": "Took theirs"}"#;
+
     #[test]
     fn default_config() {
         let config = LocalConfig::default();
         assert!(config.endpoint.is_none());
         assert!(config.model.is_none());
+        assert!(config.api.is_none());
     }
 
     #[test]
@@ -95,5 +253,128 @@ mod tests {
             Some("http://localhost:11434/api/generate".into())
         );
         assert_eq!(config.model, Some("codellama".into()));
+        assert!(config.api.is_none());
+
+        let config: LocalConfig = toml::from_str(r#"api = "openai""#).unwrap();
+        assert_eq!(config.api, Some(LocalApi::OpenAi));
+    }
+
+    #[test]
+    fn detects_api_from_endpoint() {
+        assert_eq!(
+            LocalApi::detect("http://localhost:11434/api/generate"),
+            LocalApi::Ollama
+        );
+        assert_eq!(
+            LocalApi::detect("http://localhost:8080/v1/chat/completions/"),
+            LocalApi::OpenAi
+        );
+    }
+
+    #[test]
+    fn requires_endpoint_and_model() {
+        let config = LocalConfig {
+            endpoint: Some("http://localhost:11434/api/generate".into()),
+            ..LocalConfig::default()
+        };
+        assert!(matches!(
+            LocalProvider::new(&config),
+            Err(AiError::ProviderNotAvailable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn suggest_with_ollama_generate() {
+        let (url, server) = test_server::serve(vec![Reply::json(&serde_json::json!({
+            "model": "codellama",
+            "response": SUGGESTION,
+            "done": true
+        }))])
+        .await;
+
+        let resolution = provider(format!("{url}/api/generate"), None)
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolution.content, "right");
+        assert_eq!(resolution.metadata.confidence, Some(60));
+        assert_eq!(
+            resolution.kind,
+            ResolutionStrategyKind::AiSuggested {
+                provider: "local".into()
+            }
+        );
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /api/generate "));
+        let body = test_server::body(&requests[0]);
+        assert_eq!(body["model"], "codellama");
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["format"], "json");
+        assert!(body["prompt"].as_str().unwrap().contains("left"));
+    }
+
+    #[tokio::test]
+    async fn explain_with_chat_completions() {
+        let (url, server) = test_server::serve(vec![Reply::json(&serde_json::json!({
+            "choices": [{ "message": { "role": "assistant", "content": "Both edited b." } }]
+        }))])
+        .await;
+
+        let explanation = provider(format!("{url}/v1/chat/completions"), None)
+            .explain(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(explanation.as_deref(), Some("Both edited b."));
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /v1/chat/completions "));
+        let body = test_server::body(&requests[0]);
+        assert_eq!(body["messages"][0]["role"], "user");
+        assert!(body.get("format").is_none());
+    }
+
+    #[tokio::test]
+    async fn explicit_api_overrides_detection() {
+        let (url, _server) = test_server::serve(vec![Reply::json(&serde_json::json!({
+            "choices": [{ "message": { "content": SUGGESTION } }]
+        }))])
+        .await;
+
+        let resolution = provider(format!("{url}/completions"), Some(LocalApi::OpenAi))
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolution.content, "right");
+    }
+
+    #[tokio::test]
+    async fn unreachable_server_is_not_available() {
+        // Bind and drop a listener to get a port nothing listens on
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let err = provider(format!("http://127.0.0.1:{port}/api/generate"), None)
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AiError::ProviderNotAvailable(_)));
+    }
+
+    #[tokio::test]
+    async fn error_status_is_a_provider_error() {
+        let (url, _server) =
+            test_server::serve(vec![Reply::status(404, r#"{"error":"model not found"}"#)]).await;
+
+        let err = provider(format!("{url}/api/generate"), None)
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AiError::ProviderError { status: 404, .. }));
     }
 }
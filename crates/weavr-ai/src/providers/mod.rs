@@ -12,6 +12,18 @@ pub mod openai;
 #[cfg(feature = "ai-local")]
 pub mod local;
 
+#[cfg(any(feature = "ai-claude", feature = "ai-openai", feature = "ai-local"))]
+mod prompt;
+
+#[cfg(all(
+    test,
+    any(feature = "ai-claude", feature = "ai-openai", feature = "ai-local")
+))]
+mod test_server;
+
+#[cfg(test)]
+pub(crate) mod test_request;
+
 // Re-export provider types for convenience
 #[cfg(feature = "ai-claude")]
 pub use claude::{ClaudeConfig, ClaudeProvider};
@@ -20,4 +32,4 @@ pub use claude::{ClaudeConfig, ClaudeProvider};
 pub use openai::{OpenAiConfig, OpenAiProvider};
 
 #[cfg(feature = "ai-local")]
-pub use local::{LocalConfig, LocalProvider};
+pub use local::{LocalApi, LocalConfig, LocalProvider};
@@ -1,12 +1,17 @@
 //! `OpenAI` provider implementation.
 
+use std::time::Duration;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use weavr_core::{ConflictHunk, Resolution};
+use weavr_core::{Resolution, ResolutionMetadata, ResolutionSource, ResolutionStrategyKind};
 
 use crate::error::AiError;
+use crate::request::AiRequest;
 use crate::AiProvider;
 
+use super::prompt;
+
 /// `OpenAI` provider configuration.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct OpenAiConfig {
@@ -21,6 +26,11 @@ pub struct OpenAiConfig {
     /// Maximum tokens in response.
     #[serde(default = "default_max_tokens")]
     pub max_tokens: u32,
+
+    /// API base URL; `/chat/completions` is appended. Point this at a proxy
+    /// or any OpenAI-compatible service.
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
 }
 
 impl Default for OpenAiConfig {
@@ -29,6 +39,7 @@ impl Default for OpenAiConfig {
             api_key_env: default_api_key_env(),
             model: default_model(),
             max_tokens: default_max_tokens(),
+            base_url: default_base_url(),
         }
     }
 }
@@ -45,15 +56,17 @@ fn default_max_tokens() -> u32 {
     4096
 }
 
+fn default_base_url() -> String {
+    "https://api.openai.com/v1".into()
+}
+
 /// `OpenAI` provider.
 pub struct OpenAiProvider {
-    #[allow(dead_code)]
     api_key: String,
-    #[allow(dead_code)]
     model: String,
-    #[allow(dead_code)]
     max_tokens: u32,
-    #[allow(dead_code)]
+    endpoint: String,
+    timeout: Duration,
     client: reqwest::Client,
 }
 
@@ -64,6 +77,15 @@ impl OpenAiProvider {
     ///
     /// Returns an error if the API key environment variable is not set.
     pub fn new(config: &OpenAiConfig) -> Result<Self, AiError> {
+        Self::with_timeout(config, Duration::from_secs(30))
+    }
+
+    /// Creates a new `OpenAI` provider with a custom timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API key environment variable is not set.
+    pub fn with_timeout(config: &OpenAiConfig, timeout: Duration) -> Result<Self, AiError> {
         let api_key = std::env::var(&config.api_key_env).map_err(|_| {
             AiError::ApiKeyError(format!(
                 "environment variable {} not set",
@@ -71,13 +93,54 @@ impl OpenAiProvider {
             ))
         })?;
 
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| {
+                AiError::ProviderNotAvailable(format!("failed to build HTTP client: {e}"))
+            })?;
+
         Ok(Self {
             api_key,
             model: config.model.clone(),
             max_tokens: config.max_tokens,
-            client: reqwest::Client::new(),
+            endpoint: format!("{}/chat/completions", config.base_url.trim_end_matches('/')),
+            timeout,
+            client,
         })
     }
+
+    /// Sends `prompt` as a single user message and returns the reply text.
+    async fn complete(&self, prompt: &str) -> Result<Option<String>, AiError> {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .bearer_auth(&self.api_key)
+            .header("content-type", "application/json")
+            .json(&serde_json::json!({
+                "model": self.model,
+                "max_tokens": self.max_tokens,
+                "messages": [{
+                    "role": "user",
+                    "content": prompt
+                }]
+            }))
+            .send()
+            .await
+            .map_err(|e| prompt::request_error(e, self.timeout))?;
+
+        let response = prompt::check_status(response, "openai").await?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| prompt::request_error(e, self.timeout))?;
+        Self::parse_response(&body)
+    }
+
+    /// Extracts the reply text from a chat completions response.
+    fn parse_response(response_body: &str) -> Result<Option<String>, AiError> {
+        prompt::chat_completion_text(response_body, "OpenAI")
+    }
 }
 
 #[async_trait]
@@ -86,30 +149,195 @@ impl AiProvider for OpenAiProvider {
         "openai"
     }
 
-    async fn suggest(&self, _hunk: &ConflictHunk) -> Result<Option<Resolution>, AiError> {
-        // TODO: Implement OpenAI API integration
-        Err(AiError::ProviderNotAvailable(
-            "OpenAI provider not yet implemented".into(),
-        ))
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn suggest(&self, request: &AiRequest) -> Result<Option<Resolution>, AiError> {
+        let text = self
+            .complete(&prompt::build_merge_prompt(request))
+            .await?
+            .ok_or_else(|| AiError::ParseError("no text in OpenAI response".into()))?;
+        let ai_response = prompt::parse_suggestion(&text)?;
+
+        Ok(Some(Resolution {
+            kind: ResolutionStrategyKind::AiSuggested {
+                provider: "openai".into(),
+            },
+            content: ai_response.suggestion,
+            metadata: ResolutionMetadata {
+                source: ResolutionSource::Ai,
+                notes: ai_response.explanation,
+                confidence: Some(ai_response.confidence),
+            },
+        }))
     }
 
-    async fn explain(&self, _hunk: &ConflictHunk) -> Result<Option<String>, AiError> {
-        // TODO: Implement OpenAI API integration
-        Err(AiError::ProviderNotAvailable(
-            "OpenAI provider not yet implemented".into(),
-        ))
+    async fn explain(&self, request: &AiRequest) -> Result<Option<String>, AiError> {
+        self.complete(&prompt::build_explain_prompt(request)).await
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::test_request::request;
+    use super::super::test_server::{self, Reply};
     use super::*;
 
+    const KEY_ENV: &str = "WEAVR_TEST_OPENAI_KEY";
+
+    fn provider(base_url: &str, timeout: Duration) -> OpenAiProvider {
+        std::env::set_var(KEY_ENV, "sk-test");
+        let config = OpenAiConfig {
+            api_key_env: KEY_ENV.into(),
+            base_url: format!("{base_url}/v1/"),
+            ..OpenAiConfig::default()
+        };
+        OpenAiProvider::with_timeout(&config, timeout).unwrap()
+    }
+
+    fn completion(content: &str) -> Reply {
+        Reply::json(&serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": content },
+                "finish_reason": "stop"
+            }]
+        }))
+    }
+
     #[test]
     fn default_config() {
         let config = OpenAiConfig::default();
         assert_eq!(config.api_key_env, "OPENAI_API_KEY");
         assert_eq!(config.model, "gpt-4");
         assert_eq!(config.max_tokens, 4096);
+        assert_eq!(config.base_url, "https://api.openai.com/v1");
+    }
+
+    #[test]
+    fn deserialize_config() {
+        let toml = r#"
+            model = "gpt-4o"
+            base_url = "https://llm.internal/openai/v1"
+        "#;
+
+        let config: OpenAiConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.api_key_env, "OPENAI_API_KEY");
+        assert_eq!(config.model, "gpt-4o");
+        assert_eq!(config.base_url, "https://llm.internal/openai/v1");
+    }
+
+    #[tokio::test]
+    async fn suggest_parses_chat_completion() {
+        let (url, server) = test_server::serve(vec![completion(
+            "```json\n{\"suggestion\": \"left\\nright\", \"confidence\": 0.87, \"explanation\": \"Kept both\"}\n```",
+        )])
+        .await;
+
+        let resolution = provider(&url, Duration::from_secs(5))
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolution.content, "left\nright");
+        assert_eq!(resolution.metadata.confidence, Some(87));
+        assert_eq!(resolution.metadata.source, ResolutionSource::Ai);
+        assert_eq!(resolution.metadata.notes.as_deref(), Some("Kept both"));
+        assert_eq!(
+            resolution.kind,
+            ResolutionStrategyKind::AiSuggested {
+                provider: "openai".into()
+            }
+        );
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /v1/chat/completions "));
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("authorization: bearer sk-test"));
+        let body = test_server::body(&requests[0]);
+        assert_eq!(body["model"], "gpt-4");
+        assert_eq!(body["max_tokens"], 4096);
+        let prompt = body["messages"][0]["content"].as_str().unwrap();
+        assert!(prompt.contains("Left (ours/HEAD) from HEAD:\n```\nleft\n```"));
+    }
+
+    #[tokio::test]
+    async fn explain_returns_reply_text() {
+        let (url, _server) =
+            test_server::serve(vec![completion("Both sides edited line 2.")]).await;
+
+        let explanation = provider(&url, Duration::from_secs(5))
+            .explain(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap();
+        assert_eq!(explanation.as_deref(), Some("Both sides edited line 2."));
+    }
+
+    #[tokio::test]
+    async fn unparseable_suggestion_is_a_parse_error() {
+        let (url, _server) = test_server::serve(vec![completion("Keep the left side.")]).await;
+
+        let err = provider(&url, Duration::from_secs(5))
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AiError::ParseError(_)));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_reports_retry_after() {
+        let mut reply = Reply::status(429, r#"{"error": {"message": "slow down"}}"#);
+        reply.headers.push(("retry-after", "20".into()));
+        let (url, _server) = test_server::serve(vec![reply]).await;
+
+        let err = provider(&url, Duration::from_secs(5))
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AiError::RateLimited {
+                retry_after_secs: Some(20),
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn error_status_is_a_provider_error() {
+        let (url, _server) = test_server::serve(vec![Reply::status(401, "invalid api key")]).await;
+
+        let err = provider(&url, Duration::from_secs(5))
+            .explain(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap_err();
+        match err {
+            AiError::ProviderError {
+                provider,
+                status,
+                message,
+            } => {
+                assert_eq!(provider, "openai");
+                assert_eq!(status, 401);
+                assert_eq!(message, "invalid api key");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_responses_time_out() {
+        let (url, _server) = test_server::serve(vec![Reply::hang()]).await;
+        let timeout = Duration::from_millis(200);
+
+        let err = provider(&url, timeout)
+            .suggest(&request("left", "right", None, "file.txt"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AiError::Timeout(t) if t == timeout));
     }
 }
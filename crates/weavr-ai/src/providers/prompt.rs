@@ -0,0 +1,297 @@
+//! Prompts, response parsing and HTTP error handling shared by the providers.
+//!
+//! Every provider asks the model for the same JSON object and reads it
+//! back the same way; only the HTTP API around it differs.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::error::AiError;
+use crate::request::{AiRequest, AiResponse};
+use crate::template::PromptTemplate;
+
+/// Chat completions response structure, as returned by `OpenAI` and
+/// compatible servers.
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<Choice>,
+}
+
+/// One choice in a chat completions response.
+#[derive(Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+/// The assistant message of a choice.
+#[derive(Deserialize)]
+struct ChoiceMessage {
+    content: Option<String>,
+}
+
+/// Raw AI response with f32 confidence (as returned by the model).
+#[derive(Deserialize)]
+pub(crate) struct RawAiResponse {
+    pub(crate) suggestion: String,
+    pub(crate) confidence: f32,
+    pub(crate) explanation: Option<String>,
+}
+
+/// Instructions for the answer to a merge prompt, appended to every merge
+/// template so the answer can be parsed.
+const RESPONSE_FORMAT: &str = r#"
+Respond with ONLY valid JSON (no markdown, no explanation outside JSON):
+{
+  "suggestion": "the merged content exactly as it should appear",
+  "confidence": 0.85,
+  "explanation": "brief explanation of how you merged the changes"
+}
+
+Important:
+- The "suggestion" field must contain the exact merged content
+- It replaces only the conflict; do not repeat the code before or after it
+- Confidence should be 0.0-1.0 based on how certain you are
+- Preserve original formatting, indentation, and line endings"#;
+
+/// Builds a prompt for merge conflict resolution, from the request's
+/// template or the built-in one.
+pub(crate) fn build_merge_prompt(request: &AiRequest) -> String {
+    let mut prompt = match &request.template {
+        Some(template) => template.render(request),
+        None => PromptTemplate::default_merge().render(request),
+    };
+    prompt.push_str(RESPONSE_FORMAT);
+    prompt
+}
+
+/// Builds a prompt for explaining a conflict, from the request's template
+/// or the built-in one.
+pub(crate) fn build_explain_prompt(request: &AiRequest) -> String {
+    match &request.template {
+        Some(template) => template.render(request),
+        None => PromptTemplate::default_explain().render(request),
+    }
+}
+
+/// Parses the model's answer to a merge prompt into an `AiResponse`.
+pub(crate) fn parse_suggestion(text: &str) -> Result<AiResponse, AiError> {
+    // Clean up the response text - models sometimes wrap JSON in code fences
+    let cleaned = extract_json(text);
+
+    // Parse the raw JSON (with f32 confidence)
+    let raw: RawAiResponse = serde_json::from_str(cleaned).map_err(|e| {
+        AiError::ParseError(format!(
+            "failed to parse AI response JSON: {e}\nRaw text: {text}"
+        ))
+    })?;
+
+    Ok(AiResponse {
+        suggestion: raw.suggestion,
+        confidence: confidence_percent(raw.confidence),
+        explanation: raw.explanation,
+    })
+}
+
+/// Converts f32 confidence (0.0-1.0) to u8 percentage (0-100).
+pub(crate) fn confidence_percent(confidence: f32) -> u8 {
+    // The clamp ensures value is in [0.0, 100.0], so truncation and sign loss are safe.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let percent = (confidence * 100.0).round().clamp(0.0, 100.0) as u8;
+    percent
+}
+
+/// Extracts JSON from text that may be wrapped in code fences.
+pub(crate) fn extract_json(text: &str) -> &str {
+    let trimmed = text.trim();
+
+    // Strip ```json or ``` prefix
+    let without_prefix = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .map_or(trimmed, str::trim_start);
+
+    // Strip trailing ```
+    without_prefix
+        .strip_suffix("```")
+        .map_or(without_prefix, str::trim_end)
+}
+
+/// Extracts the reply text from a chat completions response body.
+///
+/// `api` names the service in parse errors.
+pub(crate) fn chat_completion_text(body: &str, api: &str) -> Result<Option<String>, AiError> {
+    let response: ChatCompletionResponse = serde_json::from_str(body)
+        .map_err(|e| AiError::ParseError(format!("failed to parse {api} response: {e}")))?;
+
+    Ok(response.choices.into_iter().find_map(|c| c.message.content))
+}
+
+/// Returns the response if it succeeded, or the matching error:
+/// [`AiError::RateLimited`] for 429, [`AiError::ProviderError`] otherwise.
+pub(crate) async fn check_status(
+    response: reqwest::Response,
+    provider: &str,
+) -> Result<reqwest::Response, AiError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let status_code = status.as_u16();
+    if status_code == 429 {
+        return Err(AiError::RateLimited {
+            provider: provider.into(),
+            retry_after_secs: retry_after_secs(&response),
+        });
+    }
+
+    let message = response.text().await.unwrap_or_default();
+    Err(AiError::ProviderError {
+        provider: provider.into(),
+        status: status_code,
+        message,
+    })
+}
+
+/// Maps a failed request to [`AiError::Timeout`] when it ran out of time.
+pub(crate) fn request_error(error: reqwest::Error, timeout: Duration) -> AiError {
+    if error.is_timeout() {
+        AiError::Timeout(timeout)
+    } else {
+        error.into()
+    }
+}
+
+/// Returns the `Retry-After` delay of a rate-limited response, in seconds.
+pub(crate) fn retry_after_secs(response: &reqwest::Response) -> Option<u64> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_request::request;
+    use super::*;
+
+    #[test]
+    fn parse_raw_ai_response() {
+        let json =
+            r#"{"suggestion": "merged code", "confidence": 0.9, "explanation": "Combined both"}"#;
+        let raw: RawAiResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(raw.suggestion, "merged code");
+        assert!((raw.confidence - 0.9).abs() < f32::EPSILON);
+        assert_eq!(raw.explanation, Some("Combined both".into()));
+
+        // Test conversion to u8 percentage
+        assert_eq!(confidence_percent(raw.confidence), 90);
+    }
+
+    #[test]
+    fn merge_prompt_describes_file_and_sides() {
+        let prompt = build_merge_prompt(
+            &request("left", "right", None, "src/lib.rs")
+                .with_subjects(Some("Add left".into()), Some("Add right".into())),
+        );
+
+        assert!(prompt.contains("\nFile: src/lib.rs\nLanguage: rust\n"));
+        assert!(prompt.contains("Code before the conflict:\n```\nuse a;\n```"));
+        assert!(prompt.contains("Left (ours/HEAD) from HEAD, commit \"Add left\":\n```\nleft\n```"));
+        assert!(prompt.contains(
+            "Right (theirs/incoming) from feature, commit \"Add right\":\n```\nright\n```"
+        ));
+        assert!(prompt.contains("Code after the conflict:\n```\nfn main() {}\n```"));
+        assert!(!prompt.contains("Base (common ancestor)"));
+    }
+
+    #[test]
+    fn explain_prompt_omits_unknown_context() {
+        let mut request = request("left", "right", None, "src/lib.rs");
+        request.context = crate::request::ConflictContext {
+            before: Vec::new(),
+            after: Vec::new(),
+            file_path: None,
+            language: None,
+            labels: weavr_core::MarkerLabels::default(),
+            left_subject: None,
+            right_subject: None,
+        };
+        request.base = Some("base".into());
+        let prompt = build_explain_prompt(&request);
+
+        assert!(!prompt.contains("File:"));
+        assert!(!prompt.contains("Code before"));
+        assert!(prompt.contains("Left (ours/HEAD):\n```\nleft\n```"));
+        assert!(prompt.contains("Base (common ancestor):\n```\nbase\n```"));
+    }
+
+    #[test]
+    fn custom_template_keeps_response_format() {
+        let template = PromptTemplate::parse("Never reorder migrations.\n{left}{right}").unwrap();
+        let request = request("left", "right", None, "src/lib.rs").with_template(Some(template));
+
+        let prompt = build_merge_prompt(&request);
+        assert!(prompt.starts_with("Never reorder migrations.\n\nLeft (ours/HEAD)"));
+        assert!(prompt.contains("Respond with ONLY valid JSON"));
+        assert!(!prompt.contains("Code before the conflict"));
+
+        let prompt = build_explain_prompt(&request);
+        assert!(!prompt.contains("Respond with ONLY valid JSON"));
+    }
+
+    #[test]
+    fn confidence_is_clamped() {
+        assert_eq!(confidence_percent(1.7), 100);
+        assert_eq!(confidence_percent(-0.2), 0);
+    }
+
+    #[test]
+    fn parse_suggestion_in_fences() {
+        let text = "```json\n{\"suggestion\": \"code\", \"confidence\": 0.42}\n```";
+        let response = parse_suggestion(text).unwrap();
+        assert_eq!(response.suggestion, "code");
+        assert_eq!(response.confidence, 42);
+        assert!(response.explanation.is_none());
+    }
+
+    #[test]
+    fn parse_suggestion_rejects_prose() {
+        let err = parse_suggestion("I would keep both sides.").unwrap_err();
+        assert!(matches!(err, AiError::ParseError(_)));
+    }
+
+    #[test]
+    fn chat_completion_takes_first_content() {
+        let body =
+            r#"{"choices": [{"message": {"content": null}}, {"message": {"content": "hi"}}]}"#;
+        assert_eq!(
+            chat_completion_text(body, "test").unwrap().as_deref(),
+            Some("hi")
+        );
+        assert!(chat_completion_text("{}", "test").is_err());
+    }
+
+    #[test]
+    fn extract_json_plain() {
+        let text = r#"{"suggestion": "code", "confidence": 0.8}"#;
+        assert_eq!(extract_json(text), text);
+    }
+
+    #[test]
+    fn extract_json_with_fences() {
+        let text = "```json\n{\"suggestion\": \"code\"}\n```";
+        assert_eq!(extract_json(text), "{\"suggestion\": \"code\"}");
+    }
+
+    #[test]
+    fn extract_json_with_plain_fences() {
+        let text = "```\n{\"suggestion\": \"code\"}\n```";
+        assert_eq!(extract_json(text), "{\"suggestion\": \"code\"}");
+    }
+}
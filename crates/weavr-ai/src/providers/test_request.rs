@@ -0,0 +1,16 @@
+//! A conflict request for tests.
+
+use weavr_core::MergeSession;
+
+use crate::AiRequest;
+
+/// Returns the request for a conflict between `left` and `right`, with
+/// `base` if given, in the file `path` between `use a;` and `fn main() {}`.
+pub(crate) fn request(left: &str, right: &str, base: Option<&str>, path: &str) -> AiRequest {
+    let base = base.map_or(String::new(), |base| format!("||||||| base\n{base}\n"));
+    let content = format!(
+        "use a;\n<<<<<<< HEAD\n{left}\n{base}=======\n{right}\n>>>>>>> feature\nfn main() {{}}"
+    );
+    let session = MergeSession::from_conflicted(&content, path.into()).unwrap();
+    AiRequest::from_hunk(&session.hunks()[0], Some(path))
+}
@@ -0,0 +1,115 @@
+//! A stand-in HTTP server for provider tests.
+//!
+//! Serves one canned response per connection and hands back the raw
+//! requests it received, so tests can check what a provider sent.
+
+use std::fmt::Write;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// A canned HTTP response.
+pub(crate) struct Reply {
+    pub(crate) status: u16,
+    pub(crate) headers: Vec<(&'static str, String)>,
+    pub(crate) body: String,
+    /// Never answer, to exercise timeouts.
+    pub(crate) hang: bool,
+}
+
+impl Reply {
+    /// A `200 OK` with a JSON body.
+    pub(crate) fn json(body: &serde_json::Value) -> Self {
+        Self::status(200, body.to_string())
+    }
+
+    /// A response with the given status and body.
+    pub(crate) fn status(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+            hang: false,
+        }
+    }
+
+    /// A response that never arrives.
+    pub(crate) fn hang() -> Self {
+        Self {
+            hang: true,
+            ..Self::status(200, "")
+        }
+    }
+}
+
+/// Starts a server answering one connection per reply, in order.
+///
+/// Returns the base URL (`http://127.0.0.1:<port>`) and a handle
+/// resolving to the raw text of each request received.
+pub(crate) async fn serve(replies: Vec<Reply>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for reply in replies {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            requests.push(read_request(&mut stream).await);
+            if reply.hang {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                continue;
+            }
+            let mut head = format!(
+                "HTTP/1.1 {} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n",
+                reply.status,
+                reply.body.len()
+            );
+            for (name, value) in &reply.headers {
+                write!(head, "{name}: {value}\r\n").unwrap();
+            }
+            head.push_str("\r\n");
+            stream.write_all(head.as_bytes()).await.unwrap();
+            stream.write_all(reply.body.as_bytes()).await.unwrap();
+            stream.shutdown().await.unwrap();
+        }
+        requests
+    });
+
+    (url, handle)
+}
+
+/// Reads one request: headers, then `content-length` bytes of body.
+async fn read_request(stream: &mut tokio::net::TcpStream) -> String {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = stream.read(&mut buf).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        data.extend_from_slice(&buf[..n]);
+
+        let text = String::from_utf8_lossy(&data);
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length = text[..end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if data.len() >= end + 4 + length {
+                break;
+            }
+        }
+    }
+    String::from_utf8_lossy(&data).into_owned()
+}
+
+/// Returns the JSON body of a raw request.
+pub(crate) fn body(request: &str) -> serde_json::Value {
+    let (_, body) = request.split_once("\r\n\r\n").unwrap();
+    serde_json::from_str(body).unwrap()
+}
@@ -1,7 +1,9 @@
 //! Request and response types for AI providers.
 
 use serde::{Deserialize, Serialize};
-use weavr_core::ConflictHunk;
+use weavr_core::{ConflictHunk, MarkerLabels};
+
+use crate::template::PromptTemplate;
 
 /// Context provided to the AI provider about the conflict.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +16,15 @@ pub struct ConflictContext {
     pub file_path: Option<String>,
     /// Detected or specified language.
     pub language: Option<String>,
+    /// Labels on the conflict markers (e.g. `HEAD`, a branch name).
+    #[serde(default)]
+    pub labels: MarkerLabels,
+    /// Subject of the commit the left side comes from, if known.
+    #[serde(default)]
+    pub left_subject: Option<String>,
+    /// Subject of the commit the right side comes from, if known.
+    #[serde(default)]
+    pub right_subject: Option<String>,
 }
 
 /// Request payload sent to AI providers.
@@ -27,6 +38,9 @@ pub struct AiRequest {
     pub base: Option<String>,
     /// Surrounding context.
     pub context: ConflictContext,
+    /// Template to build the prompt from, instead of the built-in one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<PromptTemplate>,
 }
 
 /// Response from AI provider.
@@ -42,6 +56,10 @@ pub struct AiResponse {
 
 impl AiRequest {
     /// Creates a request from a `ConflictHunk`.
+    ///
+    /// The hunk's context lines are sent as they are; widen them first with
+    /// [`MergeSession::context_window`](weavr_core::MergeSession::context_window)
+    /// to show the model more of the file.
     #[must_use]
     pub fn from_hunk(hunk: &ConflictHunk, file_path: Option<&str>) -> Self {
         Self {
@@ -53,9 +71,28 @@ impl AiRequest {
                 after: hunk.context.after.clone(),
                 file_path: file_path.map(String::from),
                 language: file_path.and_then(detect_language),
+                labels: hunk.labels.clone(),
+                left_subject: None,
+                right_subject: None,
             },
+            template: None,
         }
     }
+
+    /// Sets the subjects of the commits each side comes from.
+    #[must_use]
+    pub fn with_subjects(mut self, left: Option<String>, right: Option<String>) -> Self {
+        self.context.left_subject = left;
+        self.context.right_subject = right;
+        self
+    }
+
+    /// Sets the template to build the prompt from.
+    #[must_use]
+    pub fn with_template(mut self, template: Option<PromptTemplate>) -> Self {
+        self.template = template;
+        self
+    }
 }
 
 /// Detects programming language from file extension.
@@ -69,6 +106,7 @@ fn detect_language(path: &str) -> Option<String> {
         "go" => Some("go".into()),
         "py" => Some("python".into()),
         "rb" => Some("ruby".into()),
+        "sql" => Some("sql".into()),
         "java" => Some("java".into()),
         "kt" | "kts" => Some("kotlin".into()),
         "swift" => Some("swift".into()),
@@ -86,6 +124,22 @@ fn detect_language(path: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn from_hunk_carries_file_and_labels() {
+        let content = "a\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\nb";
+        let session =
+            weavr_core::MergeSession::from_conflicted(content, "src/lib.rs".into()).unwrap();
+
+        let request = AiRequest::from_hunk(&session.hunks()[0], Some("src/lib.rs"))
+            .with_subjects(Some("Fix parser".into()), None);
+        assert_eq!(request.context.file_path.as_deref(), Some("src/lib.rs"));
+        assert_eq!(request.context.language.as_deref(), Some("rust"));
+        assert_eq!(request.context.labels.left.as_deref(), Some("HEAD"));
+        assert_eq!(request.context.labels.right.as_deref(), Some("feature"));
+        assert_eq!(request.context.left_subject.as_deref(), Some("Fix parser"));
+        assert!(request.context.right_subject.is_none());
+    }
+
     #[test]
     fn detect_rust() {
         assert_eq!(detect_language("src/main.rs"), Some("rust".into()));
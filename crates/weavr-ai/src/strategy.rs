@@ -1,26 +1,74 @@
 //! AI strategy wrapper for resolution workflows.
 
-use weavr_core::ConflictHunk;
-use weavr_core::Resolution;
+use std::future::Future;
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use futures_util::stream::{self, StreamExt};
+use weavr_core::{HunkId, Resolution};
 
 use crate::config::AiConfig;
 use crate::error::AiError;
+use crate::request::AiRequest;
+use crate::template::Prompts;
+use crate::verify::{verify, Verification};
 use crate::AiProvider;
 
+/// Longest wait between two retries.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A suggested resolution that passed the confidence threshold and
+/// verification.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    /// The suggested resolution.
+    pub resolution: Resolution,
+    /// What verification found, including warnings to show before the
+    /// suggestion is accepted.
+    pub verification: Verification,
+}
+
+/// Outcome of one hunk in a batch, reported as soon as it finishes.
+#[derive(Debug)]
+pub struct BatchProgress {
+    /// The hunk the result is for.
+    pub hunk_id: HunkId,
+    /// The suggestion, as returned by [`AiStrategy::suggest`].
+    pub result: Result<Option<Suggestion>, AiError>,
+    /// Number of hunks finished so far, including this one.
+    pub done: usize,
+    /// Number of hunks in the batch.
+    pub total: usize,
+}
+
 /// Wraps an `AiProvider` to produce `Resolution` objects.
 ///
-/// This struct handles configuration-based filtering (e.g., minimum confidence)
-/// and provides a consistent interface for the CLI/TUI to request AI suggestions.
+/// This struct handles configuration-based filtering (e.g., minimum confidence),
+/// retries of transient failures and batching, and provides a consistent
+/// interface for the CLI/TUI to request AI suggestions.
 pub struct AiStrategy {
     provider: Box<dyn AiProvider>,
     config: AiConfig,
+    prompts: Prompts,
 }
 
 impl AiStrategy {
     /// Creates a new `AiStrategy` with the given provider and configuration.
     #[must_use]
     pub fn new(provider: Box<dyn AiProvider>, config: AiConfig) -> Self {
-        Self { provider, config }
+        Self {
+            provider,
+            config,
+            prompts: Prompts::default(),
+        }
+    }
+
+    /// Builds prompts from `prompts` instead of the built-in templates,
+    /// e.g. those loaded from [`AiConfig::prompts`].
+    #[must_use]
+    pub fn with_prompts(mut self, prompts: Prompts) -> Self {
+        self.prompts = prompts;
+        self
     }
 
     /// Returns the provider name.
@@ -29,28 +77,45 @@ impl AiStrategy {
         self.provider.name()
     }
 
+    /// Returns how many lines of file context to send on either side of a
+    /// conflict.
+    #[must_use]
+    pub fn context_lines(&self) -> usize {
+        self.config.context_lines
+    }
+
     /// Returns whether AI is enabled in the configuration.
     #[must_use]
     pub fn is_enabled(&self) -> bool {
         self.config.enabled
     }
 
-    /// Asynchronously suggests a resolution for the hunk.
+    /// Asynchronously suggests a resolution for the conflict in `request`.
     ///
     /// Returns `Ok(None)` if:
     /// - AI is disabled in config
     /// - Provider returned no suggestion
     /// - Confidence is below the configured threshold
     ///
+    /// Retryable failures (see [`AiError::is_retryable`]) are retried up to
+    /// `max_retries` times with exponential backoff. Suggestions are then
+    /// checked against the conflict (see [`verify`]). Rejected suggestions
+    /// are reported to the provider (see [`AiProvider::reject_suggestion`]),
+    /// so a cache does not give them again.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the provider fails to generate a suggestion.
-    pub async fn suggest(&self, hunk: &ConflictHunk) -> Result<Option<Resolution>, AiError> {
+    /// Returns an error if the provider fails to generate a suggestion, or
+    /// [`AiError::InvalidSuggestion`] if the suggestion contains conflict
+    /// markers.
+    pub async fn suggest(&self, request: &AiRequest) -> Result<Option<Suggestion>, AiError> {
         if !self.config.enabled {
             return Ok(None);
         }
 
-        let response = self.provider.suggest(hunk).await?;
+        let template = self.prompts.merge_for(request).cloned();
+        let request = &request.clone().with_template(template);
+        let response = self.with_retry(|| self.provider.suggest(request)).await?;
 
         // Filter by confidence threshold
         match response {
@@ -58,17 +123,27 @@ impl AiStrategy {
                 if self.config.min_confidence > 0 {
                     match resolution.metadata.confidence {
                         Some(conf) if conf < self.config.min_confidence => {
+                            self.provider.reject_suggestion(request);
                             return Ok(None);
                         }
                         None => {
                             // When min_confidence is configured, treat missing confidence
                             // as below threshold for predictable behavior.
+                            self.provider.reject_suggestion(request);
                             return Ok(None);
                         }
                         _ => {}
                     }
                 }
-                Ok(Some(resolution))
+                let verification = verify(request, &resolution.content);
+                if let Err(e) = verification.check() {
+                    self.provider.reject_suggestion(request);
+                    return Err(e);
+                }
+                Ok(Some(Suggestion {
+                    resolution,
+                    verification,
+                }))
             }
             None => Ok(None),
         }
@@ -77,14 +152,284 @@ impl AiStrategy {
     /// Asynchronously explains the conflict.
     ///
     /// Returns `Ok(None)` if AI is disabled or the provider declines to explain.
+    /// Retryable failures are retried as for [`suggest`](Self::suggest).
     ///
     /// # Errors
     ///
     /// Returns an error if the provider fails to generate an explanation.
-    pub async fn explain(&self, hunk: &ConflictHunk) -> Result<Option<String>, AiError> {
+    pub async fn explain(&self, request: &AiRequest) -> Result<Option<String>, AiError> {
         if !self.config.enabled {
             return Ok(None);
         }
-        self.provider.explain(hunk).await
+        let template = self.prompts.explain_for(request).cloned();
+        let request = &request.clone().with_template(template);
+        self.with_retry(|| self.provider.explain(request)).await
+    }
+
+    /// Suggests resolutions for several hunks, with at most `max_in_flight`
+    /// requests at a time.
+    ///
+    /// `on_progress` is called as each hunk finishes, in completion order.
+    /// Returning [`ControlFlow::Break`] stops the batch: no further requests
+    /// are started and those in flight are dropped.
+    pub async fn suggest_batch<F>(&self, requests: Vec<(HunkId, AiRequest)>, mut on_progress: F)
+    where
+        F: FnMut(BatchProgress) -> ControlFlow<()>,
+    {
+        let total = requests.len();
+        let mut results = stream::iter(requests)
+            .map(|(hunk_id, request)| async move { (hunk_id, self.suggest(&request).await) })
+            .buffer_unordered(self.config.max_in_flight.max(1));
+
+        let mut done = 0;
+        while let Some((hunk_id, result)) = results.next().await {
+            done += 1;
+            let progress = BatchProgress {
+                hunk_id,
+                result,
+                done,
+                total,
+            };
+            if on_progress(progress).is_break() {
+                break;
+            }
+        }
+    }
+
+    /// Runs `call`, retrying retryable failures with exponential backoff.
+    async fn with_retry<T, Fut>(&self, mut call: impl FnMut() -> Fut) -> Result<T, AiError>
+    where
+        Fut: Future<Output = Result<T, AiError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match call().await {
+                Err(e) if e.is_retryable() && attempt < self.config.max_retries => {
+                    tokio::time::sleep(self.backoff(attempt, &e)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Returns the delay before retry number `attempt` (counting from 0).
+    fn backoff(&self, attempt: u32, error: &AiError) -> Duration {
+        error
+            .retry_after()
+            .unwrap_or_else(|| {
+                self.config
+                    .retry_backoff
+                    .saturating_mul(2u32.saturating_pow(attempt))
+            })
+            .min(MAX_BACKOFF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use weavr_core::{MergeSession, ResolutionMetadata, ResolutionSource, ResolutionStrategyKind};
+
+    use super::*;
+
+    /// Fails the first `failures` calls with `error`, then suggests the left side.
+    struct FlakyProvider {
+        calls: Arc<AtomicUsize>,
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+        failures: usize,
+        error: fn() -> AiError,
+    }
+
+    impl FlakyProvider {
+        fn new(failures: usize, error: fn() -> AiError) -> Self {
+            Self {
+                calls: Arc::default(),
+                in_flight: Arc::default(),
+                max_in_flight: Arc::default(),
+                failures,
+                error,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AiProvider for FlakyProvider {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn model(&self) -> &'static str {
+            "flaky-1"
+        }
+
+        async fn suggest(&self, request: &AiRequest) -> Result<Option<Resolution>, AiError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if call < self.failures {
+                return Err((self.error)());
+            }
+            Ok(Some(Resolution {
+                kind: ResolutionStrategyKind::AiSuggested {
+                    provider: "flaky".into(),
+                },
+                content: request.left.clone(),
+                metadata: ResolutionMetadata {
+                    source: ResolutionSource::Ai,
+                    notes: None,
+                    confidence: Some(90),
+                },
+            }))
+        }
+
+        async fn explain(&self, _request: &AiRequest) -> Result<Option<String>, AiError> {
+            Ok(None)
+        }
+    }
+
+    fn rate_limited() -> AiError {
+        AiError::RateLimited {
+            provider: "flaky".into(),
+            retry_after_secs: None,
+        }
+    }
+
+    fn strategy(provider: FlakyProvider, max_in_flight: usize) -> AiStrategy {
+        AiStrategy::new(
+            Box::new(provider),
+            AiConfig {
+                enabled: true,
+                max_in_flight,
+                retry_backoff: Duration::from_millis(1),
+                ..AiConfig::default()
+            },
+        )
+    }
+
+    fn requests(count: usize) -> Vec<(HunkId, AiRequest)> {
+        let content = "<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\n".repeat(count);
+        let session = MergeSession::from_conflicted(&content, "file.txt".into()).unwrap();
+        session
+            .hunks()
+            .iter()
+            .map(|hunk| (hunk.id, AiRequest::from_hunk(hunk, None)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors() {
+        let provider = FlakyProvider::new(2, rate_limited);
+        let calls = Arc::clone(&provider.calls);
+        let strategy = strategy(provider, 1);
+
+        let (_, request) = requests(1).remove(0);
+        let suggestion = strategy.suggest(&request).await.unwrap().unwrap();
+        assert_eq!(suggestion.resolution.content, "left");
+        assert_eq!(
+            suggestion.verification.warnings,
+            vec!["drops the right side's change"]
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn rejects_suggestions_with_markers() {
+        let strategy = strategy(FlakyProvider::new(0, rate_limited), 1);
+
+        let (_, mut request) = requests(1).remove(0);
+        request.left = "left\n>>>>>>> feature".into();
+        let err = strategy.suggest(&request).await.unwrap_err();
+        assert!(matches!(err, AiError::InvalidSuggestion(_)));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let provider = FlakyProvider::new(usize::MAX, rate_limited);
+        let calls = Arc::clone(&provider.calls);
+        let strategy = strategy(provider, 1);
+
+        let (_, request) = requests(1).remove(0);
+        let err = strategy.suggest(&request).await.unwrap_err();
+        assert!(matches!(err, AiError::RateLimited { .. }));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn permanent_errors_are_not_retried() {
+        let provider = FlakyProvider::new(1, || AiError::ParseError("bad".into()));
+        let calls = Arc::clone(&provider.calls);
+        let strategy = strategy(provider, 1);
+
+        let (_, request) = requests(1).remove(0);
+        assert!(strategy.suggest(&request).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn batch_bounds_concurrency_and_reports_progress() {
+        let provider = FlakyProvider::new(0, rate_limited);
+        let max_in_flight = Arc::clone(&provider.max_in_flight);
+        let strategy = strategy(provider, 3);
+
+        let mut progress = Vec::new();
+        strategy
+            .suggest_batch(requests(10), |p| {
+                assert!(p.result.unwrap().is_some());
+                progress.push((p.done, p.total));
+                ControlFlow::Continue(())
+            })
+            .await;
+
+        assert_eq!(
+            progress,
+            (1..=10).map(|done| (done, 10)).collect::<Vec<_>>()
+        );
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn batch_stops_on_break() {
+        let provider = FlakyProvider::new(0, rate_limited);
+        let calls = Arc::clone(&provider.calls);
+        let strategy = strategy(provider, 2);
+
+        let mut reported = 0;
+        strategy
+            .suggest_batch(requests(10), |_| {
+                reported += 1;
+                ControlFlow::Break(())
+            })
+            .await;
+
+        assert_eq!(reported, 1);
+        assert!(calls.load(Ordering::SeqCst) < 10);
+    }
+
+    #[test]
+    fn backoff_doubles_and_honours_retry_after() {
+        let strategy = strategy(FlakyProvider::new(0, rate_limited), 1);
+        assert_eq!(
+            strategy.backoff(0, &rate_limited()),
+            Duration::from_millis(1)
+        );
+        assert_eq!(
+            strategy.backoff(3, &rate_limited()),
+            Duration::from_millis(8)
+        );
+        assert_eq!(strategy.backoff(40, &rate_limited()), MAX_BACKOFF);
+
+        let asked = AiError::RateLimited {
+            provider: "flaky".into(),
+            retry_after_secs: Some(7),
+        };
+        assert_eq!(strategy.backoff(0, &asked), Duration::from_secs(7));
     }
 }
@@ -0,0 +1,710 @@
+//! Prompt templates: the instructions sent to the model around a conflict.
+//!
+//! Every provider builds its prompts from the same templates, so house rules
+//! ("never reorder SQL migrations", "prefer theirs for generated code") are
+//! written once in [`PromptConfig`] and apply whichever provider answers.
+//! A template is plain text with placeholders for the parts of the conflict:
+//!
+//! | Placeholder  | Replaced with                                            |
+//! |--------------|----------------------------------------------------------|
+//! | `{conflict}` | Everything below, as in the built-in prompts             |
+//! | `{file}`     | The file path, or nothing when unknown                   |
+//! | `{language}` | The file's language, or nothing when unknown             |
+//! | `{left}`     | The left side, as a titled code block                    |
+//! | `{right}`    | The right side, as a titled code block                   |
+//! | `{base}`     | The common ancestor as a titled code block, or nothing   |
+//! | `{context}`  | The code before and after the conflict, as code blocks   |
+//!
+//! Write `{{` and `}}` for literal braces. A template must contain
+//! `{conflict}`, or both `{left}` and `{right}`. Merge templates are always
+//! followed by the instructions for the JSON answer the providers parse.
+
+use std::fmt::{self, Write as _};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AiError;
+use crate::request::AiRequest;
+
+/// The built-in merge template.
+const DEFAULT_MERGE: &str = "You are a merge conflict resolver. Given two versions of code that conflict, suggest a merged resolution.\n{conflict}";
+
+/// The built-in explain template.
+const DEFAULT_EXPLAIN: &str =
+    "You are a merge conflict analyzer. Explain the differences between these two versions of code.
+{conflict}
+Provide a clear, concise explanation of:
+1. What changed on the left side
+2. What changed on the right side
+3. Why they conflict
+4. Suggestions for resolution
+
+Keep the explanation brief and technical.";
+
+/// A part of the conflict a template can refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Conflict,
+    File,
+    Language,
+    Left,
+    Right,
+    Base,
+    Context,
+}
+
+impl Placeholder {
+    /// Returns the placeholder written as `{name}`.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "conflict" => Some(Self::Conflict),
+            "file" => Some(Self::File),
+            "language" => Some(Self::Language),
+            "left" => Some(Self::Left),
+            "right" => Some(Self::Right),
+            "base" => Some(Self::Base),
+            "context" => Some(Self::Context),
+            _ => None,
+        }
+    }
+}
+
+/// A piece of a parsed template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+/// A validated prompt template.
+///
+/// Serializes as its source text, so a request carrying a template (and
+/// hence its cache key) changes whenever the template does.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PromptTemplate {
+    source: String,
+    parts: Vec<Part>,
+}
+
+impl PromptTemplate {
+    /// Parses and validates a template.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AiError::InvalidPrompt`] for an unknown placeholder, an
+    /// unmatched brace, or a template without `{conflict}` or both `{left}`
+    /// and `{right}`.
+    pub fn parse(source: &str) -> Result<Self, AiError> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(AiError::InvalidPrompt(
+                                    "unclosed `{`; write `{{` for a literal brace".into(),
+                                ))
+                            }
+                        }
+                    }
+                    let placeholder = Placeholder::from_name(name.trim()).ok_or_else(|| {
+                        AiError::InvalidPrompt(format!(
+                            "unknown placeholder `{{{name}}}`; use conflict, file, language, \
+                             left, right, base or context"
+                        ))
+                    })?;
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Placeholder(placeholder));
+                }
+                '}' => {
+                    return Err(AiError::InvalidPrompt(
+                        "unmatched `}`; write `}}` for a literal brace".into(),
+                    ))
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        let has = |placeholder| parts.contains(&Part::Placeholder(placeholder));
+        let complete =
+            has(Placeholder::Conflict) || (has(Placeholder::Left) && has(Placeholder::Right));
+        if !complete {
+            return Err(AiError::InvalidPrompt(
+                "the template must contain `{conflict}`, or both `{left}` and `{right}`".into(),
+            ));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            parts,
+        })
+    }
+
+    /// Returns the built-in merge template.
+    #[must_use]
+    pub fn default_merge() -> Self {
+        Self::builtin(DEFAULT_MERGE)
+    }
+
+    /// Returns the built-in explain template.
+    #[must_use]
+    pub fn default_explain() -> Self {
+        Self::builtin(DEFAULT_EXPLAIN)
+    }
+
+    /// Parses a built-in template, which is known to be valid.
+    fn builtin(source: &str) -> Self {
+        match Self::parse(source) {
+            Ok(template) => template,
+            Err(e) => unreachable!("built-in prompt template is invalid: {e}"),
+        }
+    }
+
+    /// Returns the template as written.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Fills in the placeholders from `request`.
+    #[must_use]
+    pub fn render(&self, request: &AiRequest) -> String {
+        let context = &request.context;
+        let mut prompt = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => prompt.push_str(text),
+                Part::Placeholder(Placeholder::Conflict) => {
+                    prompt.push_str(&conflict_section(request));
+                }
+                Part::Placeholder(Placeholder::File) => {
+                    prompt.push_str(context.file_path.as_deref().unwrap_or_default());
+                }
+                Part::Placeholder(Placeholder::Language) => {
+                    prompt.push_str(context.language.as_deref().unwrap_or_default());
+                }
+                Part::Placeholder(Placeholder::Left) => prompt.push_str(&left_block(request)),
+                Part::Placeholder(Placeholder::Right) => prompt.push_str(&right_block(request)),
+                Part::Placeholder(Placeholder::Base) => prompt.push_str(&base_block(request)),
+                Part::Placeholder(Placeholder::Context) => {
+                    prompt.push_str(&before_block(request));
+                    prompt.push_str(&after_block(request));
+                }
+            }
+        }
+        prompt
+    }
+}
+
+impl TryFrom<String> for PromptTemplate {
+    type Error = AiError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::parse(&source)
+    }
+}
+
+impl From<PromptTemplate> for String {
+    fn from(template: PromptTemplate) -> Self {
+        template.source
+    }
+}
+
+impl fmt::Display for PromptTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Where a template comes from: written inline or kept in a file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TemplateSource {
+    /// The template text itself.
+    Inline(String),
+    /// A file holding the template. Config loading makes relative paths
+    /// relative to the config file (see [`PromptConfig::resolve_relative_to`]).
+    File {
+        /// Path to the file.
+        file: PathBuf,
+    },
+}
+
+impl TemplateSource {
+    /// Reads and validates the template.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AiError::InvalidPrompt`] if the file cannot be read or the
+    /// template is invalid.
+    pub fn load(&self) -> Result<PromptTemplate, AiError> {
+        match self {
+            Self::Inline(source) => PromptTemplate::parse(source),
+            Self::File { file } => {
+                let source = std::fs::read_to_string(file).map_err(|e| {
+                    AiError::InvalidPrompt(format!("cannot read {}: {e}", file.display()))
+                })?;
+                PromptTemplate::parse(&source).map_err(|e| match e {
+                    AiError::InvalidPrompt(message) => {
+                        AiError::InvalidPrompt(format!("{}: {message}", file.display()))
+                    }
+                    other => other,
+                })
+            }
+        }
+    }
+
+    /// Makes a relative file path relative to `dir` instead of the
+    /// working directory.
+    pub fn resolve_relative_to(&mut self, dir: &Path) {
+        if let Self::File { file } = self {
+            if file.is_relative() {
+                *file = dir.join(&*file);
+            }
+        }
+    }
+}
+
+/// Prompt templates replacing the built-in ones, as configured in
+/// `[ai.prompts]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PromptConfig {
+    /// Template for suggesting a resolution.
+    #[serde(default)]
+    pub merge: Option<TemplateSource>,
+
+    /// Template for explaining a conflict.
+    #[serde(default)]
+    pub explain: Option<TemplateSource>,
+
+    /// Templates for some languages or paths; the first match wins.
+    #[serde(default)]
+    pub overrides: Vec<PromptOverride>,
+}
+
+/// Templates for the files matching a language and/or a path glob.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PromptOverride {
+    /// Language the file must be in, e.g. "sql" or "rust".
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// Glob the file path must match. `*` and `?` stay within a directory,
+    /// `**` spans any number of them, and a glob without `/` is matched
+    /// against the file name.
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Template for suggesting a resolution.
+    #[serde(default)]
+    pub merge: Option<TemplateSource>,
+
+    /// Template for explaining a conflict.
+    #[serde(default)]
+    pub explain: Option<TemplateSource>,
+}
+
+impl PromptConfig {
+    /// Makes relative template files relative to `dir`, the directory of
+    /// the config file that declared them.
+    pub fn resolve_relative_to(&mut self, dir: &Path) {
+        let overrides = self
+            .overrides
+            .iter_mut()
+            .flat_map(|o| [&mut o.merge, &mut o.explain]);
+        for source in [&mut self.merge, &mut self.explain]
+            .into_iter()
+            .chain(overrides)
+            .flatten()
+        {
+            source.resolve_relative_to(dir);
+        }
+    }
+
+    /// Reads and validates every configured template.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AiError::InvalidPrompt`] for the first template that
+    /// cannot be read or is invalid.
+    pub fn load(&self) -> Result<Prompts, AiError> {
+        let load =
+            |source: &Option<TemplateSource>| source.as_ref().map(TemplateSource::load).transpose();
+        let overrides = self
+            .overrides
+            .iter()
+            .map(|o| {
+                Ok(LoadedOverride {
+                    language: o.language.clone(),
+                    path: o.path.clone(),
+                    merge: load(&o.merge)?,
+                    explain: load(&o.explain)?,
+                })
+            })
+            .collect::<Result<_, AiError>>()?;
+
+        Ok(Prompts {
+            merge: load(&self.merge)?,
+            explain: load(&self.explain)?,
+            overrides,
+        })
+    }
+}
+
+/// An override with its templates loaded.
+#[derive(Debug, Clone)]
+struct LoadedOverride {
+    language: Option<String>,
+    path: Option<String>,
+    merge: Option<PromptTemplate>,
+    explain: Option<PromptTemplate>,
+}
+
+impl LoadedOverride {
+    /// Returns whether the override applies to `request`.
+    fn matches(&self, request: &AiRequest) -> bool {
+        let context = &request.context;
+        let language = self.language.as_ref().map_or(true, |language| {
+            context
+                .language
+                .as_ref()
+                .is_some_and(|l| l.eq_ignore_ascii_case(language))
+        });
+        let path = self.path.as_ref().map_or(true, |pattern| {
+            context
+                .file_path
+                .as_deref()
+                .is_some_and(|path| glob_match(pattern, path))
+        });
+        language && path
+    }
+}
+
+/// Loaded prompt templates, ready to pick from for each request.
+#[derive(Debug, Clone, Default)]
+pub struct Prompts {
+    merge: Option<PromptTemplate>,
+    explain: Option<PromptTemplate>,
+    overrides: Vec<LoadedOverride>,
+}
+
+impl Prompts {
+    /// Returns the merge template for `request`: that of the first matching
+    /// override that has one, else the configured one. `None` means the
+    /// built-in template.
+    #[must_use]
+    pub fn merge_for(&self, request: &AiRequest) -> Option<&PromptTemplate> {
+        self.overrides
+            .iter()
+            .filter(|o| o.matches(request))
+            .find_map(|o| o.merge.as_ref())
+            .or(self.merge.as_ref())
+    }
+
+    /// Returns the explain template for `request`, chosen as for
+    /// [`merge_for`](Self::merge_for).
+    #[must_use]
+    pub fn explain_for(&self, request: &AiRequest) -> Option<&PromptTemplate> {
+        self.overrides
+            .iter()
+            .filter(|o| o.matches(request))
+            .find_map(|o| o.explain.as_ref())
+            .or(self.explain.as_ref())
+    }
+}
+
+/// Returns whether `path` matches the glob `pattern`.
+///
+/// A pattern without `/` is matched against the file name only.
+fn glob_match(pattern: &str, path: &str) -> bool {
+    let path = path.strip_prefix("./").unwrap_or(path);
+    if !pattern.contains('/') {
+        let name = Path::new(path)
+            .file_name()
+            .map_or(path.into(), |name| name.to_string_lossy());
+        return segment_match(pattern.as_bytes(), name.as_bytes());
+    }
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    segments_match(&pattern, &path)
+}
+
+/// Matches path segments, with `**` standing for any number of them.
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((first, rest)) => path.split_first().is_some_and(|(segment, path)| {
+            segment_match(first.as_bytes(), segment.as_bytes()) && segments_match(rest, path)
+        }),
+    }
+}
+
+/// Matches one segment, with `*` standing for any run of characters and `?`
+/// for a single one.
+fn segment_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|skip| segment_match(rest, &text[skip..])),
+        Some((b'?', rest)) => !text.is_empty() && segment_match(rest, &text[1..]),
+        Some((c, rest)) => text.first() == Some(c) && segment_match(rest, &text[1..]),
+    }
+}
+
+/// Describes the conflict: file, both sides, base and surrounding code.
+fn conflict_section(request: &AiRequest) -> String {
+    let context = &request.context;
+    let mut section = String::new();
+
+    if let Some(path) = &context.file_path {
+        let _ = write!(section, "\nFile: {path}");
+    }
+    if let Some(language) = &context.language {
+        let _ = write!(section, "\nLanguage: {language}");
+    }
+    section.push('\n');
+
+    section.push_str(&before_block(request));
+    section.push_str(&left_block(request));
+    section.push_str(&right_block(request));
+    section.push_str(&base_block(request));
+    section.push_str(&after_block(request));
+    section
+}
+
+/// The code before the conflict, if any.
+fn before_block(request: &AiRequest) -> String {
+    let before = &request.context.before;
+    if before.is_empty() {
+        return String::new();
+    }
+    code_block("Code before the conflict", &before.join("\n"))
+}
+
+/// The code after the conflict, if any.
+fn after_block(request: &AiRequest) -> String {
+    let after = &request.context.after;
+    if after.is_empty() {
+        return String::new();
+    }
+    code_block("Code after the conflict", &after.join("\n"))
+}
+
+/// The left side, titled with its label and commit.
+fn left_block(request: &AiRequest) -> String {
+    let context = &request.context;
+    code_block(
+        &side_heading(
+            "Left (ours/HEAD)",
+            context.labels.left.as_deref(),
+            context.left_subject.as_deref(),
+        ),
+        &request.left,
+    )
+}
+
+/// The right side, titled with its label and commit.
+fn right_block(request: &AiRequest) -> String {
+    let context = &request.context;
+    code_block(
+        &side_heading(
+            "Right (theirs/incoming)",
+            context.labels.right.as_deref(),
+            context.right_subject.as_deref(),
+        ),
+        &request.right,
+    )
+}
+
+/// The base, titled with its label, if there is one.
+fn base_block(request: &AiRequest) -> String {
+    request.base.as_ref().map_or(String::new(), |base| {
+        code_block(
+            &side_heading(
+                "Base (common ancestor)",
+                request.context.labels.base.as_deref(),
+                None,
+            ),
+            base,
+        )
+    })
+}
+
+/// Names a side with its marker label and commit subject, when known.
+fn side_heading(name: &str, label: Option<&str>, subject: Option<&str>) -> String {
+    let mut heading = name.to_string();
+    if let Some(label) = label {
+        let _ = write!(heading, " from {label}");
+    }
+    if let Some(subject) = subject {
+        let _ = write!(heading, ", commit \"{subject}\"");
+    }
+    heading
+}
+
+/// Formats a titled, fenced block of code.
+fn code_block(title: &str, code: &str) -> String {
+    format!("\n{title}:\n```\n{code}\n```\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_request::request;
+
+    #[test]
+    fn renders_placeholders() {
+        let template =
+            PromptTemplate::parse("{language} {file}: keep {{braces}}\n{left}{right}{base}")
+                .unwrap();
+        let prompt = template.render(&request("left", "right", None, "db/001.sql"));
+
+        assert!(prompt.starts_with("sql db/001.sql: keep {braces}\n"));
+        assert!(prompt.contains("Left (ours/HEAD) from HEAD:\n```\nleft\n```"));
+        assert!(prompt.contains("Right (theirs/incoming) from feature:\n```\nright\n```"));
+        assert!(!prompt.contains("Base"));
+        assert!(!prompt.contains("use a;"));
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        let error = |source| PromptTemplate::parse(source).unwrap_err().to_string();
+
+        assert!(error("Merge {left} and {rigth}").contains("unknown placeholder `{rigth}`"));
+        assert!(error("Merge {left} and {right").contains("unclosed `{`"));
+        assert!(error("Merge {left} and {right} }").contains("unmatched `}`"));
+        assert!(error("Merge {left} carefully").contains("both `{left}` and `{right}`"));
+        assert!(PromptTemplate::parse("Rules.\n{conflict}").is_ok());
+    }
+
+    #[test]
+    fn builtin_templates_are_valid() {
+        assert!(PromptTemplate::default_merge()
+            .source()
+            .ends_with("{conflict}"));
+        assert!(PromptTemplate::default_explain()
+            .render(&request("left", "right", None, "src/lib.rs"))
+            .contains("Language: rust"));
+    }
+
+    #[test]
+    fn overrides_match_language_and_path() {
+        let toml = r#"
+            merge = "Default rules.\n{conflict}"
+
+            [[overrides]]
+            language = "SQL"
+            merge = "Never reorder migrations.\n{conflict}"
+
+            [[overrides]]
+            path = "src/generated/**"
+            merge = "Prefer theirs.\n{conflict}"
+            explain = "Generated code.\n{left}{right}"
+        "#;
+        let config: PromptConfig = toml::from_str(toml).unwrap();
+        let prompts = config.load().unwrap();
+        let merge = |path| {
+            prompts
+                .merge_for(&request("left", "right", None, path))
+                .unwrap()
+                .source()
+                .to_string()
+        };
+
+        assert!(merge("db/001_init.sql").starts_with("Never reorder"));
+        assert!(merge("src/generated/api/client.rs").starts_with("Prefer theirs"));
+        assert!(merge("src/lib.rs").starts_with("Default rules"));
+        assert!(prompts
+            .explain_for(&request("left", "right", None, "src/lib.rs"))
+            .is_none());
+        assert!(prompts
+            .explain_for(&request("left", "right", None, "src/generated/mod.rs"))
+            .is_some());
+    }
+
+    #[test]
+    fn loads_templates_from_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("merge.txt");
+        std::fs::write(&file, "House rules.\n{conflict}").unwrap();
+
+        let source = TemplateSource::File { file: file.clone() };
+        assert_eq!(source.load().unwrap().source(), "House rules.\n{conflict}");
+
+        std::fs::write(&file, "No placeholders").unwrap();
+        let message = source.load().unwrap_err().to_string();
+        assert!(message.contains("merge.txt"));
+
+        let missing = TemplateSource::File {
+            file: dir.path().join("missing.txt"),
+        };
+        assert!(missing
+            .load()
+            .unwrap_err()
+            .to_string()
+            .contains("cannot read"));
+    }
+
+    #[test]
+    fn resolves_files_relative_to_config_dir() {
+        let toml = r#"
+            merge = "Inline.\n{conflict}"
+            explain = { file = "/etc/weavr/explain.txt" }
+
+            [[overrides]]
+            language = "sql"
+            merge = { file = "prompts/migrations.txt" }
+        "#;
+        let mut config: PromptConfig = toml::from_str(toml).unwrap();
+        config.resolve_relative_to(Path::new("/repo"));
+
+        assert_eq!(
+            config.merge,
+            Some(TemplateSource::Inline("Inline.\n{conflict}".into()))
+        );
+        assert_eq!(
+            config.explain,
+            Some(TemplateSource::File {
+                file: "/etc/weavr/explain.txt".into()
+            })
+        );
+        assert_eq!(
+            config.overrides[0].merge,
+            Some(TemplateSource::File {
+                file: "/repo/prompts/migrations.txt".into()
+            })
+        );
+    }
+
+    #[test]
+    fn glob_matching() {
+        assert!(glob_match("*.sql", "db/migrations/001.sql"));
+        assert!(glob_match("src/generated/**", "src/generated/a/b.rs"));
+        assert!(glob_match("src/**/*.rs", "src/lib.rs"));
+        assert!(glob_match("src/?.rs", "./src/a.rs"));
+        assert!(!glob_match("src/*.rs", "src/a/b.rs"));
+        assert!(!glob_match("*.sql", "db/001.sql.bak"));
+    }
+}
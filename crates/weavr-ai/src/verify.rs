@@ -0,0 +1,245 @@
+//! Checking AI suggestions against the conflict they resolve.
+//!
+//! Models can answer with text that still contains conflict markers, lines
+//! found on neither side, or one side's change left out. [`verify`] traces
+//! each line of a suggestion back to the left, right and base versions
+//! ([`Provenance`]) and turns what looks wrong into warnings to show before
+//! the suggestion is accepted. Conflict markers are never acceptable:
+//! [`Verification::check`] rejects them.
+
+use std::collections::HashSet;
+
+use weavr_core::is_conflict_marker;
+
+use crate::error::AiError;
+use crate::request::AiRequest;
+
+/// Where a line of a suggestion comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineOrigin {
+    /// Present on both sides (or blank).
+    Both,
+    /// Present on the left side only.
+    Left,
+    /// Present on the right side only.
+    Right,
+    /// Present in the base only: removed or changed on both sides.
+    Base,
+    /// Present in none of the versions.
+    Introduced,
+}
+
+/// The origin of each line of a suggestion.
+///
+/// Lines are compared without leading or trailing whitespace, so
+/// re-indented lines keep their origin.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    /// The origin of each line, in order.
+    pub lines: Vec<LineOrigin>,
+}
+
+impl Provenance {
+    /// Returns how many lines have the given origin.
+    #[must_use]
+    pub fn count(&self, origin: LineOrigin) -> usize {
+        self.lines.iter().filter(|&&line| line == origin).count()
+    }
+
+    /// Returns the share of lines found in some version, as a percentage;
+    /// 100 for an empty suggestion.
+    #[must_use]
+    pub fn grounded_percent(&self) -> u8 {
+        if self.lines.is_empty() {
+            return 100;
+        }
+        let grounded = self.lines.len() - self.count(LineOrigin::Introduced);
+        // At most 100, so the narrowing is lossless.
+        #[allow(clippy::cast_possible_truncation)]
+        let percent = (grounded * 100 / self.lines.len()) as u8;
+        percent
+    }
+}
+
+/// What verifying a suggestion found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Verification {
+    /// The origin of each line of the suggestion.
+    pub provenance: Provenance,
+    /// Lines of the suggestion that are conflict markers, starting at 1.
+    pub marker_lines: Vec<usize>,
+    /// Problems to show before the suggestion is accepted, e.g.
+    /// "introduces 4 lines not present in either side".
+    pub warnings: Vec<String>,
+}
+
+impl Verification {
+    /// Rejects suggestions that contain conflict markers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AiError::InvalidSuggestion`] naming the first marker line.
+    pub fn check(&self) -> Result<(), AiError> {
+        match self.marker_lines.first() {
+            Some(line) => Err(AiError::InvalidSuggestion(format!(
+                "contains a conflict marker on line {line}"
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Verifies `suggestion` as a resolution of the conflict in `request`.
+#[must_use]
+pub fn verify(request: &AiRequest, suggestion: &str) -> Verification {
+    let left = line_set(&request.left);
+    let right = line_set(&request.right);
+    let base = request.base.as_deref().map(line_set).unwrap_or_default();
+    let output = line_set(suggestion);
+
+    let mut verification = Verification::default();
+    for (index, line) in suggestion.lines().enumerate() {
+        if is_conflict_marker(line) {
+            verification.marker_lines.push(index + 1);
+        }
+        let line = line.trim();
+        let origin = match (left.contains(line), right.contains(line)) {
+            _ if line.is_empty() => LineOrigin::Both,
+            (true, true) => LineOrigin::Both,
+            (true, false) => LineOrigin::Left,
+            (false, true) => LineOrigin::Right,
+            (false, false) if base.contains(line) => LineOrigin::Base,
+            (false, false) => LineOrigin::Introduced,
+        };
+        verification.provenance.lines.push(origin);
+    }
+
+    let introduced = verification.provenance.count(LineOrigin::Introduced);
+    if introduced > 0 {
+        verification.warnings.push(format!(
+            "introduces {introduced} {} not present in either side",
+            lines(introduced)
+        ));
+    }
+    let restored = verification.provenance.count(LineOrigin::Base);
+    if restored > 0 {
+        verification.warnings.push(format!(
+            "restores {restored} {} removed on both sides",
+            lines(restored)
+        ));
+    }
+
+    // A side's change: its lines that are not in the base, or, without a
+    // base, not on the other side.
+    let changed = |side: &str, other: &HashSet<&str>| -> Vec<String> {
+        let reference = if request.base.is_some() { &base } else { other };
+        side.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !reference.contains(line))
+            .map(str::to_string)
+            .collect()
+    };
+    for (name, side, other) in [
+        ("left", &request.left, &right),
+        ("right", &request.right, &left),
+    ] {
+        let change = changed(side, other);
+        if !change.is_empty() && change.iter().all(|line| !output.contains(line.as_str())) {
+            verification
+                .warnings
+                .push(format!("drops the {name} side's change"));
+        }
+    }
+
+    verification
+}
+
+/// Returns the distinct non-blank lines of `text`, trimmed.
+fn line_set(text: &str) -> HashSet<&str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Returns "line" or "lines" for `count`.
+fn lines(count: usize) -> &'static str {
+    if count == 1 {
+        "line"
+    } else {
+        "lines"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_request::request;
+
+    #[test]
+    fn traces_lines_to_their_side() {
+        let request = request("a\nleft", "a\nright", None, "file.txt");
+        let verification = verify(&request, "a\n  left\n\nright\nnew");
+
+        assert_eq!(
+            verification.provenance.lines,
+            vec![
+                LineOrigin::Both,
+                LineOrigin::Left,
+                LineOrigin::Both,
+                LineOrigin::Right,
+                LineOrigin::Introduced,
+            ]
+        );
+        assert_eq!(verification.provenance.grounded_percent(), 80);
+        assert_eq!(
+            verification.warnings,
+            vec!["introduces 1 line not present in either side".to_string()]
+        );
+        assert!(verification.check().is_ok());
+    }
+
+    #[test]
+    fn warns_when_a_side_is_dropped() {
+        let request = request(
+            "x = 1\nkeep",
+            "x = 2\nkeep",
+            Some("x = 0\nkeep"),
+            "file.txt",
+        );
+
+        let verification = verify(&request, "x = 2\nkeep");
+        assert_eq!(verification.warnings, vec!["drops the left side's change"]);
+
+        let verification = verify(&request, "x = 0\nkeep");
+        assert_eq!(
+            verification.warnings,
+            vec![
+                "restores 1 line removed on both sides",
+                "drops the left side's change",
+                "drops the right side's change",
+            ]
+        );
+    }
+
+    #[test]
+    fn combining_both_sides_has_no_warnings() {
+        let request = request("use a;", "use b;", Some(""), "file.txt");
+        let verification = verify(&request, "use a;\nuse b;");
+        assert!(verification.warnings.is_empty());
+        assert_eq!(verification.provenance.grounded_percent(), 100);
+    }
+
+    #[test]
+    fn rejects_conflict_markers() {
+        let request = request("left", "right", None, "file.txt");
+        let verification = verify(&request, "left\n=======\nright");
+
+        assert_eq!(verification.marker_lines, vec![2]);
+        let err = verification.check().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "suggestion rejected: contains a conflict marker on line 2"
+        );
+    }
+}
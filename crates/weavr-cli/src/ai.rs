@@ -0,0 +1,152 @@
+//! Setting up the AI provider, for the TUI and the headless AI strategies.
+
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use weavr_ai::{AiConfig, AiError, AiProvider, AiRequest, AiStrategy};
+use weavr_core::{HunkId, MergeSession};
+use weavr_git::GitRepo;
+
+use crate::error::CliError;
+use crate::headless::{Suggested, Suggester};
+
+/// Builds `AiConfig` starting from the config file values, then layering
+/// env-var auto-detection for fields that weren't explicitly set.
+pub fn config(base: &AiConfig) -> AiConfig {
+    let mut config = base.clone();
+
+    // Auto-detect provider if not set in config. A configured local model
+    // wins over API keys, so code is only sent to hosted APIs on request.
+    if config.provider.is_none() {
+        #[cfg(feature = "ai-local")]
+        if config.local.endpoint.is_some() && config.local.model.is_some() {
+            config.enabled = true;
+            config.provider = Some("local".into());
+        }
+
+        #[cfg(feature = "ai-claude")]
+        if config.provider.is_none() && std::env::var("ANTHROPIC_API_KEY").is_ok() {
+            config.enabled = true;
+            config.provider = Some("claude".into());
+        }
+
+        #[cfg(feature = "ai-openai")]
+        if config.provider.is_none() && std::env::var("OPENAI_API_KEY").is_ok() {
+            config.enabled = true;
+            config.provider = Some("openai".into());
+        }
+    }
+
+    config
+}
+
+/// Builds an `AiStrategy` from the given configuration, answering from the
+/// on-disk cache unless it is disabled and using the configured prompt
+/// templates.
+pub fn strategy(config: &AiConfig) -> Result<AiStrategy, AiError> {
+    let provider: Box<dyn AiProvider> = match config.provider.as_deref().unwrap_or("claude") {
+        #[cfg(feature = "ai-claude")]
+        "claude" => Box::new(weavr_ai::providers::ClaudeProvider::with_timeout(
+            &config.claude,
+            config.timeout,
+        )?),
+        #[cfg(feature = "ai-openai")]
+        "openai" => Box::new(weavr_ai::providers::OpenAiProvider::with_timeout(
+            &config.openai,
+            config.timeout,
+        )?),
+        #[cfg(feature = "ai-local")]
+        "local" => Box::new(weavr_ai::providers::LocalProvider::with_timeout(
+            &config.local,
+            config.timeout,
+        )?),
+        other => return Err(AiError::ProviderNotAvailable(other.into())),
+    };
+
+    let provider: Box<dyn AiProvider> = match weavr_ai::AiCache::from_config(&config.cache) {
+        Some(cache) => Box::new(weavr_ai::CachedProvider::new(provider, cache)),
+        None => provider,
+    };
+
+    Ok(AiStrategy::new(provider, config.clone()).with_prompts(config.prompts.load()?))
+}
+
+/// Suggestions from the configured provider, for the headless AI strategies.
+pub struct HeadlessAi {
+    runtime: tokio::runtime::Runtime,
+    strategy: AiStrategy,
+    ours_subject: Option<String>,
+    theirs_subject: Option<String>,
+}
+
+impl HeadlessAi {
+    /// Sets up the provider configured in `base` or detected from the
+    /// environment.
+    pub fn new(base: &AiConfig) -> Result<Self, CliError> {
+        let config = config(base);
+        if !config.enabled {
+            return Err(CliError::AiUnavailable(
+                "AI is disabled; set `ai.enabled` and `ai.provider`, or an API key".into(),
+            ));
+        }
+        let strategy = strategy(&config)?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let names = GitRepo::discover().ok().map(|repo| repo.side_names());
+
+        Ok(Self {
+            runtime,
+            strategy,
+            ours_subject: names.as_ref().and_then(|n| n.ours.subject.clone()),
+            theirs_subject: names.and_then(|n| n.theirs.subject),
+        })
+    }
+
+    /// Builds the request for a hunk, with the configured window of context.
+    fn request(&self, session: &MergeSession, id: HunkId, path: &Path) -> Option<AiRequest> {
+        let mut hunk = session.hunk(id)?.clone();
+        if let Some(context) = session.context_window(id, self.strategy.context_lines()) {
+            hunk.context = context;
+        }
+        let path = path.to_string_lossy();
+        Some(
+            AiRequest::from_hunk(&hunk, (!path.is_empty()).then_some(&*path))
+                .with_subjects(self.ours_subject.clone(), self.theirs_subject.clone()),
+        )
+    }
+}
+
+impl Suggester for HeadlessAi {
+    fn suggest(&self, session: &MergeSession) -> Vec<Suggested> {
+        let path = &session.input().left.path;
+        let requests = session
+            .unresolved_hunks()
+            .into_iter()
+            .filter_map(|id| Some((id, self.request(session, id, path)?)))
+            .collect();
+
+        let mut suggestions = Vec::new();
+        self.runtime
+            .block_on(self.strategy.suggest_batch(requests, |progress| {
+                match progress.result {
+                    Ok(Some(suggestion)) => suggestions.push(Suggested {
+                        hunk_id: progress.hunk_id,
+                        resolution: suggestion.resolution,
+                        warnings: suggestion.verification.warnings,
+                    }),
+                    Ok(None) => {}
+                    Err(e) => {
+                        let number = session
+                            .hunks()
+                            .iter()
+                            .position(|hunk| hunk.id == progress.hunk_id)
+                            .map_or(0, |index| index + 1);
+                        eprintln!("{}: hunk {number}: AI error ({e})", path.display());
+                    }
+                }
+                ControlFlow::Continue(())
+            }));
+        suggestions
+    }
+}
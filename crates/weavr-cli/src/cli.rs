@@ -2,7 +2,7 @@
 
 use std::path::PathBuf;
 
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 
 /// Resolution strategy for headless mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -13,14 +13,149 @@ pub enum Strategy {
     Right,
     /// Accept both sides (combine left then right)
     Both,
+    /// Resolve only trivial hunks (identical, one-sided, trailing whitespace)
+    Auto,
+    /// Resolve trivial hunks, then apply AI suggestions at or above
+    /// `ai.min_confidence`; leave the rest
+    Ai,
+    /// Apply AI suggestions, then accept left for the rest
+    AiThenLeft,
+    /// Apply AI suggestions, then accept right for the rest
+    AiThenRight,
+    /// Apply AI suggestions, then accept both sides for the rest
+    AiThenBoth,
+    /// Same as `ai`, which already resolves trivial hunks before asking
+    AiThenAuto,
+}
+
+impl Strategy {
+    /// Returns whether the strategy asks the AI provider first.
+    pub fn uses_ai(self) -> bool {
+        matches!(
+            self,
+            Strategy::Ai
+                | Strategy::AiThenLeft
+                | Strategy::AiThenRight
+                | Strategy::AiThenBoth
+                | Strategy::AiThenAuto
+        )
+    }
+
+    /// Returns what happens to hunks the AI did not resolve. Strategies
+    /// without AI apply their fallback to every hunk.
+    pub fn fallback(self) -> Fallback {
+        match self {
+            Strategy::Ai => Fallback::Leave,
+            Strategy::Left | Strategy::AiThenLeft => Fallback::Left,
+            Strategy::Right | Strategy::AiThenRight => Fallback::Right,
+            Strategy::Both | Strategy::AiThenBoth => Fallback::Both,
+            Strategy::Auto | Strategy::AiThenAuto => Fallback::Auto,
+        }
+    }
+}
+
+/// What a [`Strategy`] does with hunks the AI did not resolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fallback {
+    /// Leave them unresolved
+    Leave,
+    /// Resolve only the trivial ones
+    Auto,
+    /// Accept left content
+    Left,
+    /// Accept right content
+    Right,
+    /// Accept both sides
+    Both,
+}
+
+/// Resolution for delete/modify and both-deleted conflicts in headless mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FileChoice {
+    /// Keep our version of the file
+    Ours,
+    /// Keep their version of the file
+    Theirs,
+    /// Delete the file
+    Delete,
+}
+
+/// Subcommands for integrating weavr with git.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Resolve one file as a `git mergetool` backend
+    Mergetool {
+        /// Common ancestor version (`$BASE`)
+        base: PathBuf,
+        /// Our version (`$LOCAL`)
+        local: PathBuf,
+        /// Their version (`$REMOTE`)
+        remote: PathBuf,
+        /// File to write the result to (`$MERGED`)
+        merged: PathBuf,
+    },
+    /// Merge one file as a git merge driver (`merge=weavr` in .gitattributes)
+    MergeDriver {
+        /// Resolution strategy for conflicting hunks
+        #[arg(long, value_enum, default_value = "auto")]
+        strategy: Strategy,
+        /// Common ancestor version (`%O`)
+        base: PathBuf,
+        /// Our version, overwritten with the result (`%A`)
+        current: PathBuf,
+        /// Their version (`%B`)
+        other: PathBuf,
+        /// Conflict marker length (`%L`)
+        marker_size: usize,
+        /// Path of the file being merged (`%P`)
+        path: PathBuf,
+    },
+    /// Register weavr as a git merge tool and merge driver
+    Install {
+        /// Write to the global git config instead of the repository's
+        #[arg(long)]
+        global: bool,
+    },
+    /// Manage resolutions recorded for replay in later merges
+    Resolutions {
+        #[command(subcommand)]
+        action: ResolutionsCommand,
+    },
+}
+
+/// Actions on the recorded resolutions.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum ResolutionsCommand {
+    /// List recorded resolutions
+    List,
+    /// Forget recorded resolutions
+    Forget {
+        /// Fingerprints of the resolutions to forget (see `list`)
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        fingerprints: Vec<String>,
+        /// Forget all recorded resolutions
+        #[arg(long)]
+        all: bool,
+    },
+    /// Export recorded resolutions as JSON
+    Export {
+        /// Write to a file instead of stdout
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
 }
 
 /// A terminal-first merge conflict resolver
 #[derive(Parser, Debug)]
 #[command(name = "weavr")]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 #[allow(clippy::struct_excessive_bools)] // CLI flags are naturally boolean
 pub struct Cli {
+    /// Git integration subcommand
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Files to resolve (defaults to all conflicted files)
     #[arg(value_name = "FILE")]
     pub files: Vec<PathBuf>,
@@ -41,14 +176,26 @@ pub struct Cli {
     #[arg(long, requires = "headless")]
     pub dry_run: bool,
 
+    /// Resolution for delete/modify conflicts (defaults to the strategy's side)
+    #[arg(long, value_enum, value_name = "CHOICE", requires = "headless")]
+    pub file_resolution: Option<FileChoice>,
+
     /// Exit with code 1 if any hunk cannot be auto-resolved
     #[arg(long, requires = "headless")]
     pub fail_on_ambiguous: bool,
 
+    /// Stage each fully resolved file with `git add` after writing it
+    #[arg(long)]
+    pub stage: bool,
+
     /// List conflicted files and exit
     #[arg(long)]
     pub list: bool,
 
+    /// Resolve, stage and continue the merge/rebase/cherry-pick/revert until it finishes
+    #[arg(long = "continue", conflicts_with_all = ["files", "headless", "list"])]
+    pub continue_operation: bool,
+
     /// Configuration file path
     #[arg(long, value_name = "PATH")]
     pub config: Option<PathBuf>,
@@ -56,6 +203,11 @@ pub struct Cli {
     /// Theme name (overrides config file)
     #[arg(long, value_name = "THEME")]
     pub theme: Option<String>,
+
+    /// Don't read or write cached AI suggestions and explanations
+    #[cfg(feature = "ai")]
+    #[arg(long)]
+    pub no_ai_cache: bool,
 }
 
 #[cfg(test)]
@@ -71,7 +223,102 @@ mod tests {
         assert!(!cli.dedupe);
         assert!(!cli.dry_run);
         assert!(!cli.fail_on_ambiguous);
+        assert!(cli.file_resolution.is_none());
+        assert!(!cli.stage);
         assert!(!cli.list);
+        assert!(!cli.continue_operation);
+    }
+
+    #[test]
+    fn cli_parse_mergetool() {
+        let cli = Cli::parse_from(["weavr", "mergetool", "b", "l", "r", "m"]);
+        assert_eq!(
+            cli.command,
+            Some(Command::Mergetool {
+                base: "b".into(),
+                local: "l".into(),
+                remote: "r".into(),
+                merged: "m".into(),
+            })
+        );
+        assert!(cli.files.is_empty());
+        assert!(Cli::try_parse_from(["weavr", "mergetool", "b", "l", "r"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_merge_driver() {
+        let cli = Cli::parse_from(["weavr", "merge-driver", "o", "a", "b", "9", "Cargo.lock"]);
+        assert_eq!(
+            cli.command,
+            Some(Command::MergeDriver {
+                strategy: Strategy::Auto,
+                base: "o".into(),
+                current: "a".into(),
+                other: "b".into(),
+                marker_size: 9,
+                path: "Cargo.lock".into(),
+            })
+        );
+
+        let cli = Cli::parse_from([
+            "weavr",
+            "merge-driver",
+            "--strategy=both",
+            "o",
+            "a",
+            "b",
+            "7",
+            "p",
+        ]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::MergeDriver {
+                strategy: Strategy::Both,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["weavr", "merge-driver", "o", "a", "b", "x", "p"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_install() {
+        let cli = Cli::parse_from(["weavr", "install", "--global"]);
+        assert_eq!(cli.command, Some(Command::Install { global: true }));
+        assert!(Cli::parse_from(["weavr", "file.rs"]).command.is_none());
+    }
+
+    #[test]
+    fn cli_parse_resolutions() {
+        let cli = Cli::parse_from(["weavr", "resolutions", "forget", "abc", "def"]);
+        assert_eq!(
+            cli.command,
+            Some(Command::Resolutions {
+                action: ResolutionsCommand::Forget {
+                    fingerprints: vec!["abc".into(), "def".into()],
+                    all: false,
+                },
+            })
+        );
+        assert!(Cli::try_parse_from(["weavr", "resolutions", "forget"]).is_err());
+        assert!(Cli::try_parse_from(["weavr", "resolutions", "forget", "abc", "--all"]).is_err());
+
+        let cli = Cli::parse_from(["weavr", "resolutions", "export", "-o", "out.json"]);
+        assert_eq!(
+            cli.command,
+            Some(Command::Resolutions {
+                action: ResolutionsCommand::Export {
+                    output: Some("out.json".into()),
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn cli_parse_continue() {
+        let cli = Cli::parse_from(["weavr", "--continue", "--stage"]);
+        assert!(cli.continue_operation);
+        assert!(Cli::try_parse_from(["weavr", "--continue", "--headless"]).is_err());
+        assert!(Cli::try_parse_from(["weavr", "--continue", "file.rs"]).is_err());
     }
 
     #[test]
@@ -101,6 +348,13 @@ mod tests {
         assert!(cli.dry_run);
     }
 
+    #[test]
+    fn cli_parse_stage() {
+        let cli = Cli::parse_from(["weavr", "--stage"]);
+        assert!(cli.stage);
+        assert!(!cli.headless);
+    }
+
     #[test]
     fn cli_parse_fail_on_ambiguous() {
         let cli = Cli::parse_from(["weavr", "--headless", "--fail-on-ambiguous"]);
@@ -122,6 +376,34 @@ mod tests {
         assert_eq!(cli.strategy, Some(Strategy::Right));
     }
 
+    #[test]
+    fn cli_parse_strategy_auto() {
+        let cli = Cli::parse_from(["weavr", "--headless", "--strategy=auto"]);
+        assert!(cli.headless);
+        assert_eq!(cli.strategy, Some(Strategy::Auto));
+    }
+
+    #[test]
+    fn cli_parse_file_resolution() {
+        let cli = Cli::parse_from(["weavr", "--headless", "--file-resolution=delete"]);
+        assert_eq!(cli.file_resolution, Some(FileChoice::Delete));
+        assert!(Cli::try_parse_from(["weavr", "--file-resolution=ours"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_strategy_ai() {
+        let cli = Cli::parse_from(["weavr", "--headless", "--strategy=ai"]);
+        assert_eq!(cli.strategy, Some(Strategy::Ai));
+        assert_eq!(Strategy::Ai.fallback(), Fallback::Leave);
+
+        let cli = Cli::parse_from(["weavr", "--headless", "--strategy=ai-then-right"]);
+        assert_eq!(cli.strategy, Some(Strategy::AiThenRight));
+        assert!(Strategy::AiThenRight.uses_ai());
+        assert_eq!(Strategy::AiThenRight.fallback(), Fallback::Right);
+        assert!(!Strategy::Right.uses_ai());
+        assert_eq!(Strategy::Right.fallback(), Fallback::Right);
+    }
+
     #[test]
     fn cli_strategy_requires_headless() {
         let result = Cli::try_parse_from(["weavr", "--strategy=left"]);
@@ -145,4 +427,11 @@ mod tests {
         let cli = Cli::parse_from(["weavr"]);
         assert!(cli.theme.is_none());
     }
+
+    #[cfg(feature = "ai")]
+    #[test]
+    fn cli_parse_no_ai_cache() {
+        assert!(!Cli::parse_from(["weavr"]).no_ai_cache);
+        assert!(Cli::parse_from(["weavr", "--no-ai-cache"]).no_ai_cache);
+    }
 }
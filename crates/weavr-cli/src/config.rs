@@ -66,6 +66,14 @@ pub struct RawHeadlessConfig {
     pub fail_on_ambiguous: Option<bool>,
 }
 
+/// Raw git configuration section.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RawGitConfig {
+    pub auto_stage: Option<bool>,
+    pub own_change_left: Option<bool>,
+}
+
 /// Raw TOML configuration. All fields optional for layered merging.
 ///
 /// Top-level struct does NOT use `deny_unknown_fields` so that `[ai]`
@@ -75,6 +83,7 @@ pub struct RawConfig {
     pub theme: Option<RawThemeConfig>,
     pub strategies: Option<RawStrategiesConfig>,
     pub headless: Option<RawHeadlessConfig>,
+    pub git: Option<RawGitConfig>,
 
     #[cfg(feature = "ai")]
     pub ai: Option<weavr_ai::AiConfig>,
@@ -98,6 +107,10 @@ impl RawConfig {
             headless: merge_option(self.headless, lower.headless, |hi, lo| RawHeadlessConfig {
                 fail_on_ambiguous: hi.fail_on_ambiguous.or(lo.fail_on_ambiguous),
             }),
+            git: merge_option(self.git, lower.git, |hi, lo| RawGitConfig {
+                auto_stage: hi.auto_stage.or(lo.auto_stage),
+                own_change_left: hi.own_change_left.or(lo.own_change_left),
+            }),
             #[cfg(feature = "ai")]
             ai: self.ai.or(lower.ai),
         }
@@ -124,11 +137,15 @@ fn merge_option<T>(
 
 /// Fully resolved configuration with concrete, validated types.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)] // One flag per config key
 pub struct WeavrConfig {
     pub theme: weavr_tui::theme::ThemeName,
     pub default_strategy: Strategy,
     pub deduplicate: bool,
     pub fail_on_ambiguous: bool,
+    pub auto_stage: bool,
+    /// Show the user's own change in the left pane, even during a rebase.
+    pub own_change_left: bool,
     #[cfg(feature = "ai")]
     pub ai: weavr_ai::AiConfig,
 }
@@ -148,7 +165,7 @@ impl WeavrConfig {
             Some(name) => parse_strategy(name).ok_or_else(|| ConfigError::InvalidValue {
                 key: "strategies.default".into(),
                 value: name.into(),
-                hint: "valid strategies: left, right, both".into(),
+                hint: "valid strategies: left, right, both, auto, ai, ai-then-<strategy>".into(),
             })?,
             None => Strategy::Left,
         };
@@ -165,11 +182,20 @@ impl WeavrConfig {
             .and_then(|h| h.fail_on_ambiguous)
             .unwrap_or(false);
 
+        let auto_stage = raw.git.as_ref().and_then(|g| g.auto_stage).unwrap_or(false);
+        let own_change_left = raw
+            .git
+            .as_ref()
+            .and_then(|g| g.own_change_left)
+            .unwrap_or(false);
+
         Ok(Self {
             theme,
             default_strategy,
             deduplicate,
             fail_on_ambiguous,
+            auto_stage,
+            own_change_left,
             #[cfg(feature = "ai")]
             ai: raw.ai.clone().unwrap_or_default(),
         })
@@ -197,6 +223,12 @@ fn parse_strategy(s: &str) -> Option<Strategy> {
         "left" => Some(Strategy::Left),
         "right" => Some(Strategy::Right),
         "both" => Some(Strategy::Both),
+        "auto" => Some(Strategy::Auto),
+        "ai" => Some(Strategy::Ai),
+        "ai-then-left" => Some(Strategy::AiThenLeft),
+        "ai-then-right" => Some(Strategy::AiThenRight),
+        "ai-then-both" => Some(Strategy::AiThenBoth),
+        "ai-then-auto" => Some(Strategy::AiThenAuto),
         _ => None,
     }
 }
@@ -246,16 +278,26 @@ pub fn load_config(cli_path: Option<&Path>) -> Result<RawConfig, ConfigError> {
 }
 
 /// Reads and parses a single TOML config file.
+///
+/// Prompt template files are made relative to the file's directory.
 fn read_config_file(path: &Path) -> Result<RawConfig, ConfigError> {
     let content = std::fs::read_to_string(path).map_err(|source| ConfigError::ReadError {
         path: path.to_path_buf(),
         source,
     })?;
 
-    toml::from_str(&content).map_err(|source| ConfigError::ParseError {
-        path: path.to_path_buf(),
-        source,
-    })
+    #[allow(unused_mut)] // only changed with the `ai` feature
+    let mut config: RawConfig =
+        toml::from_str(&content).map_err(|source| ConfigError::ParseError {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    #[cfg(feature = "ai")]
+    if let Some(ai) = &mut config.ai {
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        ai.prompts.resolve_relative_to(dir);
+    }
+    Ok(config)
 }
 
 #[cfg(test)]
@@ -354,6 +396,8 @@ mod tests {
         assert_eq!(config.default_strategy, Strategy::Left);
         assert!(!config.deduplicate);
         assert!(!config.fail_on_ambiguous);
+        assert!(!config.auto_stage);
+        assert!(!config.own_change_left);
     }
 
     #[test]
@@ -423,6 +467,10 @@ deduplicate = true
 
 [headless]
 fail_on_ambiguous = true
+
+[git]
+auto_stage = true
+own_change_left = true
 "#;
         let raw: RawConfig = toml::from_str(toml_str).unwrap();
         let config = WeavrConfig::from_raw(&raw).unwrap();
@@ -430,6 +478,8 @@ fail_on_ambiguous = true
         assert_eq!(config.default_strategy, Strategy::Both);
         assert!(config.deduplicate);
         assert!(config.fail_on_ambiguous);
+        assert!(config.auto_stage);
+        assert!(config.own_change_left);
     }
 
     #[test]
@@ -464,6 +514,9 @@ foo = "bar"
         assert_eq!(parse_strategy("left"), Some(Strategy::Left));
         assert_eq!(parse_strategy("RIGHT"), Some(Strategy::Right));
         assert_eq!(parse_strategy("Both"), Some(Strategy::Both));
+        assert_eq!(parse_strategy("auto"), Some(Strategy::Auto));
+        assert_eq!(parse_strategy("ai"), Some(Strategy::Ai));
+        assert_eq!(parse_strategy("AI-then-both"), Some(Strategy::AiThenBoth));
     }
 
     #[test]
@@ -526,4 +579,27 @@ name = "nord"
             Some("nord")
         );
     }
+
+    #[cfg(feature = "ai")]
+    #[test]
+    fn prompt_files_are_relative_to_the_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+[ai.prompts]
+merge = { file = "prompts/merge.txt" }
+"#,
+        )
+        .unwrap();
+
+        let raw = read_config_file(&path).unwrap();
+        assert_eq!(
+            raw.ai.unwrap().prompts.merge,
+            Some(weavr_ai::TemplateSource::File {
+                file: dir.path().join("prompts/merge.txt")
+            })
+        );
+    }
 }
@@ -1,10 +1,33 @@
 //! Git conflict file discovery.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use weavr_git::GitRepo;
+use weavr_git::{ConflictType, GitRepo};
 
 use crate::error::CliError;
+use crate::file_conflicts::PendingFileConflict;
+
+/// Conflicted files to process, split by how they are resolved.
+#[derive(Debug, Default)]
+pub struct ConflictedFiles {
+    /// Files with conflict markers, resolved hunk by hunk.
+    pub content: Vec<PathBuf>,
+    /// Delete/modify and both-deleted conflicts, resolved as whole files.
+    pub file_level: Vec<PendingFileConflict>,
+}
+
+impl ConflictedFiles {
+    /// Returns the total number of conflicted files.
+    pub fn len(&self) -> usize {
+        self.content.len() + self.file_level.len()
+    }
+
+    /// Returns true if there are no conflicted files.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
 
 /// Discovers files with Git merge conflicts in the current repository.
 pub fn discover_conflicted_files() -> Result<Vec<PathBuf>, CliError> {
@@ -13,36 +36,102 @@ pub fn discover_conflicted_files() -> Result<Vec<PathBuf>, CliError> {
     Ok(files)
 }
 
+/// Returns the Git conflict type of each conflicted file, keyed by canonical path.
+///
+/// Best effort: returns an empty map outside a repository.
+pub fn conflict_types() -> HashMap<PathBuf, ConflictType> {
+    let Ok(repo) = GitRepo::discover() else {
+        return HashMap::new();
+    };
+    let Ok(entries) = repo.conflicted_entries() else {
+        return HashMap::new();
+    };
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let path = repo.root().join(&entry.path).canonicalize().ok()?;
+            Some((path, entry.conflict_type))
+        })
+        .collect()
+}
+
 /// Checks if a file contains conflict markers.
 pub fn has_conflict_markers(path: &Path) -> Result<bool, CliError> {
     let content = std::fs::read_to_string(path)?;
-    Ok(content.contains("<<<<<<<") && content.contains("=======") && content.contains(">>>>>>>"))
+    Ok(content_has_conflict_markers(&content))
+}
+
+/// Checks if content contains conflict markers.
+pub fn content_has_conflict_markers(content: &str) -> bool {
+    content.contains("<<<<<<<") && content.contains("=======") && content.contains(">>>>>>>")
 }
 
 /// Filters provided paths to only those with conflicts, or discovers all.
-pub fn resolve_files(provided: Vec<PathBuf>) -> Result<Vec<PathBuf>, CliError> {
+///
+/// Delete/modify and both-deleted conflicts have no conflict markers and
+/// may have no working copy, so they are recognised from the Git index.
+pub fn resolve_files(provided: Vec<PathBuf>) -> Result<ConflictedFiles, CliError> {
+    let mut files = ConflictedFiles::default();
+
     if provided.is_empty() {
-        let files = discover_conflicted_files()?;
-        if files.is_empty() {
-            return Err(CliError::NoConflictedFiles);
+        let repo = GitRepo::discover()?;
+        for entry in repo.conflicted_entries()? {
+            if entry.conflict_type.is_content_conflict() {
+                files.content.push(entry.path);
+            } else {
+                let conflict = repo.file_conflict(&entry.path)?;
+                files.file_level.push(PendingFileConflict {
+                    path: entry.path,
+                    repo: repo.clone(),
+                    conflict,
+                });
+            }
         }
-        Ok(files)
     } else {
-        let mut valid = Vec::new();
         for path in provided {
-            if !path.exists() {
+            if path.exists() && has_conflict_markers(&path)? {
+                files.content.push(path);
+            } else if let Some(pending) = file_level_conflict(&path) {
+                files.file_level.push(pending);
+            } else if !path.exists() {
                 return Err(CliError::FileNotFound(path));
             }
-            if has_conflict_markers(&path)? {
-                valid.push(path);
-            }
-        }
-        if valid.is_empty() {
-            Err(CliError::NoConflictedFiles)
-        } else {
-            Ok(valid)
         }
     }
+
+    if files.is_empty() {
+        Err(CliError::NoConflictedFiles)
+    } else {
+        Ok(files)
+    }
+}
+
+/// Looks up a file-level conflict for a path given on the command line.
+///
+/// The path need not exist, since a deleted side may have removed it.
+fn file_level_conflict(path: &Path) -> Option<PendingFileConflict> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let parent = parent.canonicalize().ok()?;
+    let repo = GitRepo::discover_from(&parent).ok()?;
+    let root = repo.root().canonicalize().ok()?;
+    let relative = parent
+        .join(path.file_name()?)
+        .strip_prefix(root)
+        .ok()?
+        .to_path_buf();
+
+    let conflict = repo.file_conflict(&relative).ok()?;
+    if conflict.conflict_type.is_content_conflict() {
+        return None;
+    }
+    Some(PendingFileConflict {
+        path: path.to_path_buf(),
+        repo,
+        conflict,
+    })
 }
 
 /// Lists conflicted files to stdout.
@@ -0,0 +1,200 @@
+//! Running as a git merge driver.
+//!
+//! With `*.lock merge=weavr` in `.gitattributes` and the driver registered
+//! by `weavr install`, git runs `weavr merge-driver %O %A %B %L %P` for
+//! every matching file changed on both sides. The three versions are
+//! merged and resolved with a non-interactive strategy, the result is
+//! written to `%A`, and the exit code tells git whether conflicts remain.
+
+use std::path::PathBuf;
+
+use weavr_core::{FileVersion, MergeInput, MergeSession};
+
+use crate::cli::Strategy;
+use crate::error::{exit_codes, CliError};
+use crate::headless::{ai_report, resolve_session, HeadlessResult, Suggester};
+
+/// The command git runs for `merge=weavr`.
+pub const DRIVER_CMD: &str = "weavr merge-driver %O %A %B %L %P";
+
+/// The arguments git passes to a merge driver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriverArgs {
+    /// Common ancestor version (`%O`); empty for add/add conflicts.
+    pub base: PathBuf,
+    /// Our version (`%A`), overwritten with the result.
+    pub current: PathBuf,
+    /// Their version (`%B`).
+    pub other: PathBuf,
+    /// Conflict marker length (`%L`).
+    pub marker_size: usize,
+    /// Path of the file being merged (`%P`).
+    pub path: PathBuf,
+}
+
+/// Merges the three versions, resolves them with `strategy` and writes
+/// the result to `%A`. `dedupe` applies to [`Strategy::Both`], and
+/// `suggester` to the AI strategies.
+///
+/// With [`Strategy::Auto`] only trivial hunks are resolved; the rest are
+/// left with conflict markers of the requested size. Returns
+/// [`exit_codes::SUCCESS`] if the file merged cleanly, or
+/// [`exit_codes::UNRESOLVED`] if conflicts remain.
+pub fn run(
+    args: &DriverArgs,
+    strategy: Strategy,
+    suggester: Option<&dyn Suggester>,
+    dedupe: bool,
+) -> Result<i32, CliError> {
+    let result = merge(args, strategy, suggester, dedupe)?;
+    weavr_tui::save::write_atomic(&args.current, &result.output)?;
+
+    if result.hunks_unresolved > 0 {
+        eprintln!(
+            "{}: {} hunks resolved, {} left with conflict markers",
+            args.path.display(),
+            result.hunks_resolved,
+            result.hunks_unresolved
+        );
+    }
+    for line in ai_report(&result) {
+        eprintln!("{line}");
+    }
+    if result.hunks_unresolved > 0 {
+        return Ok(exit_codes::UNRESOLVED);
+    }
+    Ok(exit_codes::SUCCESS)
+}
+
+/// Merges and resolves the three versions without writing anything.
+fn merge(
+    args: &DriverArgs,
+    strategy: Strategy,
+    suggester: Option<&dyn Suggester>,
+    dedupe: bool,
+) -> Result<HeadlessResult, CliError> {
+    let version = |path: &PathBuf| -> Result<FileVersion, CliError> {
+        Ok(FileVersion {
+            path: args.path.clone(),
+            content: std::fs::read_to_string(path)?,
+        })
+    };
+    // git passes an empty %O when there is no common ancestor
+    let base = Some(version(&args.base)?).filter(|base| !base.content.is_empty());
+    let input = MergeInput {
+        left: version(&args.current)?,
+        right: version(&args.other)?,
+        base,
+    };
+    let session = MergeSession::new(input)?;
+
+    if session.hunks().is_empty() {
+        return Ok(HeadlessResult {
+            path: args.path.clone(),
+            hunks_resolved: 0,
+            hunks_unresolved: 0,
+            output: session.conflicted_output(),
+            ai_resolved: Vec::new(),
+            ai_withheld: Vec::new(),
+        });
+    }
+
+    resolve_session(
+        args.path.clone(),
+        session,
+        strategy,
+        suggester,
+        dedupe,
+        false,
+        args.marker_size,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn write_versions(dir: &Path, base: &str, ours: &str, theirs: &str) -> DriverArgs {
+        let args = DriverArgs {
+            base: dir.join(".merge_file_O"),
+            current: dir.join(".merge_file_A"),
+            other: dir.join(".merge_file_B"),
+            marker_size: 7,
+            path: PathBuf::from("Cargo.lock"),
+        };
+        std::fs::write(&args.base, base).unwrap();
+        std::fs::write(&args.current, ours).unwrap();
+        std::fs::write(&args.other, theirs).unwrap();
+        args
+    }
+
+    #[test]
+    fn clean_merge_succeeds() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = write_versions(dir.path(), "a\nb\nc\n", "A\nb\nc\n", "a\nb\nC\n");
+
+        let code = run(&args, Strategy::Auto, None, false).unwrap();
+        assert_eq!(code, exit_codes::SUCCESS);
+        assert_eq!(std::fs::read_to_string(&args.current).unwrap(), "A\nb\nC\n");
+    }
+
+    #[test]
+    fn real_conflicts_keep_markers_of_requested_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut args = write_versions(dir.path(), "a\nb\nc\n", "a\nours\nc\n", "a\ntheirs\nc\n");
+        args.marker_size = 9;
+
+        let code = run(&args, Strategy::Auto, None, false).unwrap();
+        assert_eq!(code, exit_codes::UNRESOLVED);
+        assert_eq!(
+            std::fs::read_to_string(&args.current).unwrap(),
+            "a\n<<<<<<<<< ours\nours\n||||||||| base\nb\n=========\ntheirs\n>>>>>>>>> theirs\nc\n"
+        );
+    }
+
+    #[test]
+    fn strategy_resolves_every_hunk() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = write_versions(dir.path(), "a\nb\nc\n", "a\nours\nc\n", "a\ntheirs\nc\n");
+
+        let code = run(&args, Strategy::Both, None, false).unwrap();
+        assert_eq!(code, exit_codes::SUCCESS);
+        assert_eq!(
+            std::fs::read_to_string(&args.current).unwrap(),
+            "a\nours\ntheirs\nc\n"
+        );
+    }
+
+    #[test]
+    fn output_keeps_line_endings_and_final_newline() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = write_versions(
+            dir.path(),
+            "a\r\nb\r\nc",
+            "a\r\nours\r\nc",
+            "a\r\ntheirs\r\nc",
+        );
+
+        let code = run(&args, Strategy::Left, None, false).unwrap();
+        assert_eq!(code, exit_codes::SUCCESS);
+        assert_eq!(
+            std::fs::read_to_string(&args.current).unwrap(),
+            "a\r\nours\r\nc"
+        );
+    }
+
+    #[test]
+    fn empty_base_merges_two_way() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = write_versions(dir.path(), "", "a\nours\nc\n", "a\ntheirs\nc\n");
+
+        let code = run(&args, Strategy::Auto, None, false).unwrap();
+        assert_eq!(code, exit_codes::UNRESOLVED);
+        assert_eq!(
+            std::fs::read_to_string(&args.current).unwrap(),
+            "a\n<<<<<<< ours\nours\n=======\ntheirs\n>>>>>>> theirs\nc\n"
+        );
+    }
+}
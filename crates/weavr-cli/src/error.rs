@@ -48,8 +48,17 @@ pub enum CliError {
     Config(#[from] crate::config::ConfigError),
 
     #[error("Ambiguous hunks remain: {0} hunks could not be auto-resolved")]
-    #[allow(dead_code)] // Reserved for --fail-on-ambiguous implementation
     AmbiguousHunks(usize),
+
+    #[error("File-level conflict needs --file-resolution: {0}")]
+    UnresolvedFileConflict(PathBuf),
+
+    #[error("AI strategy unavailable: {0}")]
+    AiUnavailable(String),
+
+    #[cfg(feature = "ai")]
+    #[error("AI error: {0}")]
+    Ai(#[from] weavr_ai::AiError),
 }
 
 impl CliError {
@@ -57,7 +66,9 @@ impl CliError {
     pub fn exit_code(&self) -> i32 {
         match self {
             CliError::NoConflictedFiles => exit_codes::SUCCESS,
-            CliError::AmbiguousHunks(_) => exit_codes::UNRESOLVED,
+            CliError::AmbiguousHunks(_) | CliError::UnresolvedFileConflict(_) => {
+                exit_codes::UNRESOLVED
+            }
             _ => exit_codes::ERROR,
         }
     }
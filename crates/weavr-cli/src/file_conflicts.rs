@@ -0,0 +1,136 @@
+//! Resolving file-level conflicts (delete/modify, both-deleted).
+//!
+//! These conflicts are carried out with git: keeping a side checks it out
+//! and runs `git add`, deleting runs `git rm`. Either way the path is
+//! staged, so `--stage` does not apply to them.
+
+use std::path::PathBuf;
+
+use weavr_git::{FileConflict, FileResolution, GitRepo};
+
+use crate::cli::{Fallback, FileChoice, Strategy};
+use crate::error::CliError;
+
+/// A file-level conflict waiting to be resolved.
+#[derive(Debug)]
+pub struct PendingFileConflict {
+    /// The path as given on the command line or discovered.
+    pub path: PathBuf,
+    /// The repository containing the path.
+    pub repo: GitRepo,
+    /// The conflict, with its path relative to the repository root.
+    pub conflict: FileConflict,
+}
+
+impl From<FileChoice> for FileResolution {
+    fn from(choice: FileChoice) -> Self {
+        match choice {
+            FileChoice::Ours => FileResolution::KeepOurs,
+            FileChoice::Theirs => FileResolution::KeepTheirs,
+            FileChoice::Delete => FileResolution::Delete,
+        }
+    }
+}
+
+/// Picks the headless resolution for file-level conflicts.
+///
+/// An explicit `--file-resolution` wins; otherwise `left` and `ai-then-left`
+/// keep ours, and `right` and `ai-then-right` keep theirs. Other strategies
+/// have no file-level equivalent.
+pub fn headless_resolution(
+    choice: Option<FileChoice>,
+    strategy: Strategy,
+) -> Option<FileResolution> {
+    choice
+        .map(FileResolution::from)
+        .or(match strategy.fallback() {
+            Fallback::Left => Some(FileResolution::KeepOurs),
+            Fallback::Right => Some(FileResolution::KeepTheirs),
+            _ => None,
+        })
+}
+
+/// Describes what a resolution does to the file, e.g. `keep theirs (deleted)`.
+pub fn describe(conflict: &FileConflict, resolution: FileResolution) -> String {
+    if resolution != FileResolution::Delete && conflict.deletes(resolution) {
+        format!("{resolution} (deleted)")
+    } else {
+        resolution.to_string()
+    }
+}
+
+/// Applies a resolution with git, or with `dry_run` only reports it.
+pub fn apply(
+    pending: &PendingFileConflict,
+    resolution: FileResolution,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let label = pending.conflict.conflict_type.label();
+    let action = describe(&pending.conflict, resolution);
+
+    if dry_run {
+        println!("{}: {label}, would {action}", pending.path.display());
+        return Ok(());
+    }
+
+    pending
+        .repo
+        .resolve_file(&pending.conflict.path, resolution)?;
+    println!("{}: {label}, {action} (staged)", pending.path.display());
+    Ok(())
+}
+
+/// Reports a file-level conflict that was left unresolved.
+pub fn report_unresolved(pending: &PendingFileConflict, hint: &str) {
+    eprintln!(
+        "{}: {}, left unresolved ({hint})",
+        pending.path.display(),
+        pending.conflict.conflict_type.label()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use weavr_git::ConflictType;
+
+    use super::*;
+
+    fn modify_delete() -> FileConflict {
+        FileConflict {
+            path: PathBuf::from("file.txt"),
+            conflict_type: ConflictType::AddedByUsDeletedByThem,
+            ours: Some("ours\n".into()),
+            theirs: None,
+        }
+    }
+
+    #[test]
+    fn explicit_choice_overrides_strategy() {
+        assert_eq!(
+            headless_resolution(Some(FileChoice::Delete), Strategy::Left),
+            Some(FileResolution::Delete)
+        );
+        assert_eq!(
+            headless_resolution(None, Strategy::Right),
+            Some(FileResolution::KeepTheirs)
+        );
+        assert_eq!(headless_resolution(None, Strategy::Both), None);
+        assert_eq!(headless_resolution(None, Strategy::Auto), None);
+        assert_eq!(
+            headless_resolution(None, Strategy::AiThenLeft),
+            Some(FileResolution::KeepOurs)
+        );
+        assert_eq!(headless_resolution(None, Strategy::Ai), None);
+    }
+
+    #[test]
+    fn describe_notes_deletions() {
+        let conflict = modify_delete();
+        assert_eq!(describe(&conflict, FileResolution::KeepOurs), "keep ours");
+        assert_eq!(
+            describe(&conflict, FileResolution::KeepTheirs),
+            "keep theirs (deleted)"
+        );
+        assert_eq!(describe(&conflict, FileResolution::Delete), "delete");
+    }
+}
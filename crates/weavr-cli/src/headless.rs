@@ -2,44 +2,159 @@
 
 use std::path::{Path, PathBuf};
 
-use crate::cli::Strategy;
+use weavr_core::{
+    HunkId, MergeSession, RecordedResolutions, Resolution, ResolutionSource,
+    ResolutionStrategyKind, DEFAULT_MARKER_SIZE,
+};
+
+use crate::cli::{Fallback, Strategy};
 use crate::error::CliError;
+use crate::stage::{stage_resolved, summary_suffix, StageOutcome};
 
 /// Result of headless processing for a single file.
+#[derive(Debug)]
 pub struct HeadlessResult {
     /// Path to the processed file.
     pub path: PathBuf,
     /// Number of hunks that were resolved.
     pub hunks_resolved: usize,
+    /// Number of hunks left unresolved (written back with conflict markers).
+    pub hunks_unresolved: usize,
     /// The merged output content.
     pub output: String,
+    /// Hunks resolved with an AI suggestion, in file order.
+    pub ai_resolved: Vec<AiResolved>,
+    /// Hunks whose AI suggestion was not applied because verification
+    /// raised warnings, in file order.
+    pub ai_withheld: Vec<AiWithheld>,
+}
+
+/// A hunk resolved with an AI suggestion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AiResolved {
+    /// Position of the hunk in the file, starting at 1.
+    pub hunk: usize,
+    /// Name of the provider that suggested the resolution.
+    pub provider: String,
+    /// Confidence reported by the provider (0-100).
+    pub confidence: Option<u8>,
+}
+
+/// A hunk whose AI suggestion was not applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AiWithheld {
+    /// Position of the hunk in the file, starting at 1.
+    pub hunk: usize,
+    /// Name of the provider that suggested the resolution.
+    pub provider: String,
+    /// Problems found when checking the suggestion against the conflict.
+    pub warnings: Vec<String>,
+}
+
+/// A suggested resolution for one hunk.
+#[derive(Debug, Clone)]
+pub struct Suggested {
+    /// The hunk the suggestion is for.
+    pub hunk_id: HunkId,
+    /// The suggested resolution.
+    pub resolution: Resolution,
+    /// Problems found when checking the suggestion against the conflict.
+    pub warnings: Vec<String>,
+}
+
+/// Suggests resolutions for the AI strategies.
+pub trait Suggester {
+    /// Returns the suggestions to apply to unresolved hunks of `session`.
+    ///
+    /// Suggestions below the confidence threshold or failing verification
+    /// are left out. Failures for single hunks are reported on stderr and
+    /// leave them out too.
+    fn suggest(&self, session: &MergeSession) -> Vec<Suggested>;
 }
 
 /// Runs headless merge on a single file.
+///
+/// Hunks with a resolution in `recorded` are
+/// resolved with it first. [`Strategy::Auto`] and the AI strategies then
+/// resolve trivial hunks, and the AI strategies, which require
+/// `suggester`, ask it about the rest. Suggestions with verification
+/// warnings are not applied. After that, [`Strategy::Auto`] and
+/// [`Strategy::Ai`] resolve nothing more. Any
+/// remaining hunks are either left in place with conflict markers or, when
+/// `fail_on_ambiguous` is set, reported as [`CliError::AmbiguousHunks`].
 pub fn process_file(
     path: &Path,
+    recorded: &RecordedResolutions,
     strategy: Strategy,
+    suggester: Option<&dyn Suggester>,
     dedupe: bool,
+    fail_on_ambiguous: bool,
 ) -> Result<HeadlessResult, CliError> {
-    let content = std::fs::read_to_string(path)?;
-    let mut session = weavr_core::MergeSession::from_conflicted(&content, path.to_path_buf())?;
-
-    let hunks: Vec<_> = session.hunks().to_vec();
+    let loaded = crate::input::load_session(path)?;
 
     // Handle files without conflicts (already clean)
-    if hunks.is_empty() {
+    if loaded.session.hunks().is_empty() {
         return Ok(HeadlessResult {
             path: path.to_path_buf(),
             hunks_resolved: 0,
-            output: content,
+            hunks_unresolved: 0,
+            output: loaded.content,
+            ai_resolved: Vec::new(),
+            ai_withheld: Vec::new(),
         });
     }
 
+    let mut session = loaded.session;
+    session.apply_recorded(recorded);
+    resolve_session(
+        path.to_path_buf(),
+        session,
+        strategy,
+        suggester,
+        dedupe,
+        fail_on_ambiguous,
+        DEFAULT_MARKER_SIZE,
+    )
+}
+
+/// Resolves a loaded session with `strategy`.
+///
+/// Behaves like [`process_file`]; hunks left unresolved are rendered with
+/// conflict markers `marker_size` characters long.
+pub fn resolve_session(
+    path: PathBuf,
+    mut session: MergeSession,
+    strategy: Strategy,
+    suggester: Option<&dyn Suggester>,
+    dedupe: bool,
+    fail_on_ambiguous: bool,
+    marker_size: usize,
+) -> Result<HeadlessResult, CliError> {
+    let hunks: Vec<_> = session.hunks().to_vec();
+
+    let fallback = strategy.fallback();
+    if strategy.uses_ai() || fallback == Fallback::Auto {
+        session.resolve_trivial();
+    }
+
+    let (ai_resolved, ai_withheld) = if strategy.uses_ai() {
+        let suggester = suggester.ok_or_else(|| {
+            CliError::AiUnavailable("no AI provider for the AI strategies".into())
+        })?;
+        apply_suggestions(&mut session, suggester)?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
     for hunk in &hunks {
-        let resolution = match strategy {
-            Strategy::Left => weavr_core::Resolution::accept_left(hunk),
-            Strategy::Right => weavr_core::Resolution::accept_right(hunk),
-            Strategy::Both => {
+        if session.resolutions().contains_key(&hunk.id) {
+            continue;
+        }
+        let resolution = match fallback {
+            Fallback::Leave | Fallback::Auto => continue,
+            Fallback::Left => weavr_core::Resolution::accept_left(hunk),
+            Fallback::Right => weavr_core::Resolution::accept_right(hunk),
+            Fallback::Both => {
                 let options = weavr_core::AcceptBothOptions {
                     order: weavr_core::BothOrder::LeftThenRight,
                     deduplicate: dedupe,
@@ -52,29 +167,373 @@ pub fn process_file(
         session.set_resolution(hunk.id, resolution)?;
     }
 
+    let remaining = session.unresolved_hunks().len();
+    if remaining > 0 {
+        if fail_on_ambiguous {
+            return Err(CliError::AmbiguousHunks(remaining));
+        }
+        return Ok(HeadlessResult {
+            path,
+            hunks_resolved: session.resolutions().len(),
+            hunks_unresolved: remaining,
+            output: session.conflicted_output_with_marker_size(marker_size),
+            ai_resolved,
+            ai_withheld,
+        });
+    }
+
     session.apply()?;
     session.validate()?;
     let result = session.complete()?;
 
     Ok(HeadlessResult {
-        path: path.to_path_buf(),
+        path,
         hunks_resolved: result.summary.resolved_hunks,
+        hunks_unresolved: 0,
         output: result.content,
+        ai_resolved,
+        ai_withheld,
     })
 }
 
+/// Applies the suggestions of `suggester` to unresolved hunks, marking
+/// them as coming from AI. Suggestions with verification warnings are
+/// returned as withheld and leave their hunks unresolved.
+fn apply_suggestions(
+    session: &mut MergeSession,
+    suggester: &dyn Suggester,
+) -> Result<(Vec<AiResolved>, Vec<AiWithheld>), CliError> {
+    let mut suggestions = suggester.suggest(session);
+    let ids: Vec<HunkId> = session.hunks().iter().map(|hunk| hunk.id).collect();
+
+    let mut resolved = Vec::new();
+    let mut withheld = Vec::new();
+    for (index, id) in ids.into_iter().enumerate() {
+        if session.resolutions().contains_key(&id) {
+            continue;
+        }
+        let Some(at) = suggestions.iter().position(|s| s.hunk_id == id) else {
+            continue;
+        };
+        let Suggested {
+            mut resolution,
+            warnings,
+            ..
+        } = suggestions.swap_remove(at);
+        resolution.metadata.source = ResolutionSource::Ai;
+        let provider = match &resolution.kind {
+            ResolutionStrategyKind::AiSuggested { provider } => provider.clone(),
+            _ => "ai".into(),
+        };
+        if !warnings.is_empty() {
+            withheld.push(AiWithheld {
+                hunk: index + 1,
+                provider,
+                warnings,
+            });
+            continue;
+        }
+        resolved.push(AiResolved {
+            hunk: index + 1,
+            provider,
+            confidence: resolution.metadata.confidence,
+        });
+        session.set_resolution(id, resolution)?;
+    }
+    Ok((resolved, withheld))
+}
+
 /// Writes the result to the file or prints it for dry-run.
-pub fn write_or_print(result: &HeadlessResult, dry_run: bool) -> Result<(), CliError> {
+///
+/// With `stage`, a fully resolved file is staged after writing; a file
+/// left with conflict markers is reported as not staged. Hunks resolved by
+/// AI are listed after the summary, or on stderr for dry-run.
+pub fn write_or_print(result: &HeadlessResult, dry_run: bool, stage: bool) -> Result<(), CliError> {
     if dry_run {
         println!("=== {} ===", result.path.display());
         print!("{}", result.output);
+        for line in ai_report(result) {
+            eprintln!("{line}");
+        }
     } else {
-        std::fs::write(&result.path, &result.output)?;
-        println!(
-            "{}: {} hunks resolved",
-            result.path.display(),
-            result.hunks_resolved
-        );
+        weavr_tui::save::write_atomic(&result.path, &result.output)?;
+        if result.hunks_unresolved > 0 {
+            let refused =
+                stage.then(|| StageOutcome::Refused("file contains conflict markers".into()));
+            eprintln!(
+                "{}: {} hunks resolved, {} left with conflict markers{}",
+                result.path.display(),
+                result.hunks_resolved,
+                result.hunks_unresolved,
+                summary_suffix(refused.as_ref())
+            );
+        } else {
+            let staged = stage.then(|| stage_resolved(&result.path));
+            println!(
+                "{}: {} hunks resolved{}",
+                result.path.display(),
+                result.hunks_resolved,
+                summary_suffix(staged.as_ref())
+            );
+        }
+        for line in ai_report(result) {
+            println!("{line}");
+        }
     }
     Ok(())
 }
+
+/// Returns one line per hunk resolved by AI, e.g.
+/// `src/lib.rs: hunk 2 resolved by AI (claude, 85% confidence)`, followed
+/// by one line per warning of each withheld suggestion.
+pub fn ai_report(result: &HeadlessResult) -> Vec<String> {
+    let path = result.path.display();
+    let mut report = Vec::new();
+    for ai in &result.ai_resolved {
+        let confidence = ai
+            .confidence
+            .map(|c| format!(", {c}% confidence"))
+            .unwrap_or_default();
+        report.push(format!(
+            "{path}: hunk {} resolved by AI ({}{confidence})",
+            ai.hunk, ai.provider
+        ));
+    }
+    for ai in &result.ai_withheld {
+        for warning in &ai.warnings {
+            report.push(format!(
+                "{path}: hunk {}: AI suggestion not applied ({}): {warning}",
+                ai.hunk, ai.provider
+            ));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIXED: &str = "a\n<<<<<<< HEAD\nours\n||||||| base\nbase\n=======\nbase\n>>>>>>> feature\nb\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\nc";
+
+    fn write_conflict(content: &str) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        std::fs::write(&path, content).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn auto_resolves_trivial_and_keeps_markers() {
+        let (_dir, path) = write_conflict(MIXED);
+        let result = process_file(
+            &path,
+            &RecordedResolutions::new(),
+            Strategy::Auto,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(result.hunks_resolved, 1);
+        assert_eq!(result.hunks_unresolved, 1);
+        assert!(result.output.starts_with("a\nours\nb\n<<<<<<< "));
+        assert!(result.output.contains("left\n=======\nright"));
+    }
+
+    #[test]
+    fn auto_fails_on_ambiguous() {
+        let (_dir, path) = write_conflict(MIXED);
+        let err = process_file(
+            &path,
+            &RecordedResolutions::new(),
+            Strategy::Auto,
+            None,
+            false,
+            true,
+        )
+        .unwrap_err();
+        assert!(matches!(err, CliError::AmbiguousHunks(1)));
+    }
+
+    #[test]
+    fn auto_completes_when_all_trivial() {
+        let (_dir, path) =
+            write_conflict("a\n<<<<<<< HEAD\nsame\n=======\nsame\n>>>>>>> feature\nb");
+        let result = process_file(
+            &path,
+            &RecordedResolutions::new(),
+            Strategy::Auto,
+            None,
+            false,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(result.hunks_resolved, 1);
+        assert_eq!(result.hunks_unresolved, 0);
+        assert_eq!(result.output, "a\nsame\nb");
+    }
+
+    #[test]
+    fn recorded_resolutions_apply_before_strategy() {
+        let (_dir, path) = write_conflict(MIXED);
+        let mut earlier = weavr_core::MergeSession::from_conflicted(MIXED, path.clone()).unwrap();
+        let hunk = earlier.hunks()[1].clone();
+        earlier
+            .set_resolution(hunk.id, weavr_core::Resolution::accept_right(&hunk))
+            .unwrap();
+        let recorded = earlier.recorded_resolutions().into_iter().collect();
+
+        let result = process_file(&path, &recorded, Strategy::Auto, None, false, true).unwrap();
+        assert_eq!(result.hunks_resolved, 2);
+        assert_eq!(result.hunks_unresolved, 0);
+        assert_eq!(result.output, "a\nours\nb\nright\nc");
+    }
+
+    /// Suggests fixed content for the last hunk of every file, with the
+    /// given confidence and verification warnings.
+    struct LastHunk(Option<u8>, &'static [&'static str]);
+
+    impl Suggester for LastHunk {
+        fn suggest(&self, session: &MergeSession) -> Vec<Suggested> {
+            assert_eq!(session.unresolved_hunks().len(), 1, "trivial hunk sent");
+            let hunk = session.hunks().last().unwrap();
+            let resolution = Resolution {
+                kind: ResolutionStrategyKind::AiSuggested {
+                    provider: "fake".into(),
+                },
+                content: "merged".into(),
+                metadata: weavr_core::ResolutionMetadata {
+                    confidence: self.0,
+                    ..weavr_core::ResolutionMetadata::default()
+                },
+            };
+            vec![Suggested {
+                hunk_id: hunk.id,
+                resolution,
+                warnings: self.1.iter().map(|w| (*w).to_string()).collect(),
+            }]
+        }
+    }
+
+    #[test]
+    fn ai_runs_after_the_trivial_pass() {
+        let (_dir, path) = write_conflict(MIXED);
+        let result = process_file(
+            &path,
+            &RecordedResolutions::new(),
+            Strategy::Ai,
+            Some(&LastHunk(Some(85), &[])),
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(result.hunks_resolved, 2);
+        assert_eq!(result.hunks_unresolved, 0);
+        assert_eq!(result.output, "a\nours\nb\nmerged\nc");
+        assert_eq!(
+            result.ai_resolved,
+            vec![AiResolved {
+                hunk: 2,
+                provider: "fake".into(),
+                confidence: Some(85),
+            }]
+        );
+        assert_eq!(
+            ai_report(&result),
+            vec![format!(
+                "{}: hunk 2 resolved by AI (fake, 85% confidence)",
+                path.display()
+            )]
+        );
+    }
+
+    #[test]
+    fn ai_withholds_suggestions_with_warnings() {
+        let (_dir, path) = write_conflict(MIXED);
+        let result = process_file(
+            &path,
+            &RecordedResolutions::new(),
+            Strategy::Ai,
+            Some(&LastHunk(
+                Some(85),
+                &["introduces 1 line not present in either side"],
+            )),
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(result.hunks_resolved, 1);
+        assert_eq!(result.hunks_unresolved, 1);
+        assert!(result.output.contains("<<<<<<< HEAD\nleft\n"));
+        assert!(result.ai_resolved.is_empty());
+        assert_eq!(
+            ai_report(&result),
+            vec![format!(
+                "{}: hunk 2: AI suggestion not applied (fake): introduces 1 line not present in either side",
+                path.display()
+            )]
+        );
+
+        let (_dir, path) = write_conflict(MIXED);
+        let result = process_file(
+            &path,
+            &RecordedResolutions::new(),
+            Strategy::AiThenRight,
+            Some(&LastHunk(None, &["drops the left side's change"])),
+            false,
+            true,
+        )
+        .unwrap();
+        assert_eq!(result.output, "a\nours\nb\nright\nc");
+    }
+
+    #[test]
+    fn ai_then_fallback_resolves_the_rest() {
+        let (_dir, path) = write_conflict(MIXED);
+        let result = process_file(
+            &path,
+            &RecordedResolutions::new(),
+            Strategy::AiThenRight,
+            Some(&LastHunk(None, &[])),
+            false,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(result.hunks_resolved, 2);
+        assert_eq!(result.output, "a\nours\nb\nmerged\nc");
+        assert_eq!(result.ai_resolved[0].confidence, None);
+
+        let (_dir, path) = write_conflict(MIXED);
+        let result = process_file(
+            &path,
+            &RecordedResolutions::new(),
+            Strategy::AiThenAuto,
+            Some(&LastHunk(Some(90), &[])),
+            false,
+            true,
+        )
+        .unwrap();
+        assert_eq!(result.output, "a\nours\nb\nmerged\nc");
+    }
+
+    #[test]
+    fn ai_needs_a_suggester() {
+        let (_dir, path) = write_conflict(MIXED);
+        let err = process_file(
+            &path,
+            &RecordedResolutions::new(),
+            Strategy::Ai,
+            None,
+            false,
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, CliError::AiUnavailable(_)));
+    }
+}
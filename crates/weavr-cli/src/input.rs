@@ -0,0 +1,55 @@
+//! Loading merge sessions for conflicted files.
+
+use std::path::Path;
+
+use weavr_core::{FileVersion, MergeInput, MergeSession};
+use weavr_git::GitRepo;
+
+use crate::error::CliError;
+
+/// A merge session loaded for a file, along with its working copy.
+pub struct LoadedSession {
+    /// The merge session.
+    pub session: MergeSession,
+    /// The working copy content at load time.
+    pub content: String,
+}
+
+/// Loads a merge session for `path`.
+///
+/// The conflict markers in the working copy are parsed, so edits made
+/// since Git wrote them are kept. When the file is unmerged in the Git
+/// index, hunks written without a base (`merge.conflictStyle=merge`) get
+/// theirs from index stages 1/2/3 if Git's hunks still match.
+pub fn load_session(path: &Path) -> Result<LoadedSession, CliError> {
+    let content = std::fs::read_to_string(path)?;
+    let mut session = MergeSession::from_conflicted(&content, path.to_path_buf())?;
+
+    if session.hunks().iter().any(|hunk| hunk.base.is_none()) {
+        if let Some(input) = index_input(path) {
+            session.fill_missing_bases(&input);
+        }
+    }
+
+    Ok(LoadedSession { session, content })
+}
+
+/// Reads the index stages for `path`, if it is inside a repository and unmerged.
+fn index_input(path: &Path) -> Option<MergeInput> {
+    let absolute = path.canonicalize().ok()?;
+    let repo = GitRepo::discover_from(absolute.parent()?).ok()?;
+    let root = repo.root().canonicalize().ok()?;
+    let relative = absolute.strip_prefix(root).ok()?;
+
+    let input = repo.merge_input(relative).ok()?;
+    let with_path = |version: FileVersion| FileVersion {
+        path: path.to_path_buf(),
+        content: version.content,
+    };
+
+    Some(MergeInput {
+        left: with_path(input.left),
+        right: with_path(input.right),
+        base: input.base.map(with_path),
+    })
+}
@@ -3,24 +3,53 @@
 //! This binary provides:
 //! - Interactive mode (launches TUI)
 //! - Headless mode (applies rules automatically)
+//! - `git mergetool` backend mode and `install` to register it
+//! - Continue mode (resolves and continues a merge/rebase/cherry-pick/revert)
 //! - File discovery and orchestration
 
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "ai")]
+mod ai;
 mod cli;
 mod config;
 mod discovery;
+mod driver;
 mod error;
+mod file_conflicts;
 mod headless;
+mod input;
+mod mergetool;
+mod resolutions;
+mod resume;
+mod stage;
 mod tui;
 
 use clap::Parser;
 
-use cli::Cli;
+use cli::{Cli, Command, Strategy};
 use config::WeavrConfig;
 use error::{exit_codes, CliError};
+use weavr_git::OperationStatus;
 
 fn run(cli: &Cli) -> Result<i32, CliError> {
+    // Mode: Register weavr with git
+    if let Some(Command::Install { global }) = cli.command {
+        let scope = if global {
+            weavr_git::ConfigScope::Global
+        } else {
+            weavr_git::ConfigScope::Local
+        };
+        mergetool::install(&std::env::current_dir()?, scope)?;
+        return Ok(exit_codes::SUCCESS);
+    }
+
+    // Mode: Manage recorded resolutions
+    if let Some(Command::Resolutions { action }) = &cli.command {
+        resolutions::run(action)?;
+        return Ok(exit_codes::SUCCESS);
+    }
+
     // Mode: List conflicted files
     if cli.list {
         discovery::list_conflicted_files()?;
@@ -44,53 +73,254 @@ fn run(cli: &Cli) -> Result<i32, CliError> {
     if cli.fail_on_ambiguous {
         config.fail_on_ambiguous = true;
     }
+    if cli.stage {
+        config.auto_stage = true;
+    }
+    #[cfg(feature = "ai")]
+    if cli.no_ai_cache {
+        config.ai.cache.enabled = false;
+    }
+
+    // Mode: git mergetool backend
+    if let Some(Command::Mergetool {
+        base,
+        local,
+        remote,
+        merged,
+    }) = &cli.command
+    {
+        let paths = mergetool::MergetoolPaths {
+            base: base.clone(),
+            local: local.clone(),
+            remote: remote.clone(),
+            merged: merged.clone(),
+        };
+        return mergetool::run(&paths, &config);
+    }
+
+    // Mode: git merge driver
+    if let Some(Command::MergeDriver {
+        strategy,
+        base,
+        current,
+        other,
+        marker_size,
+        path,
+    }) = &cli.command
+    {
+        let args = driver::DriverArgs {
+            base: base.clone(),
+            current: current.clone(),
+            other: other.clone(),
+            marker_size: *marker_size,
+            path: path.clone(),
+        };
+        let suggester = suggester(*strategy, &config)?;
+        return driver::run(&args, *strategy, suggester.as_deref(), config.deduplicate);
+    }
+
+    // Mode: Resolve and continue the Git operation until it finishes
+    if cli.continue_operation {
+        return run_continue(&config);
+    }
 
     // Resolve which files to process
     let files = discovery::resolve_files(cli.files.clone())?;
 
     // Mode: Headless
     if cli.headless {
-        let strategy = config.default_strategy;
+        return run_headless(cli, &config, &files);
+    }
+
+    // Mode: Interactive (TUI)
+    run_interactive(&config, &files)
+}
 
-        for path in &files {
-            let result = headless::process_file(path, strategy, config.deduplicate)?;
-            headless::write_or_print(&result, cli.dry_run)?;
+/// Resolves all files without the TUI, using the configured strategy.
+fn run_headless(
+    cli: &Cli,
+    config: &WeavrConfig,
+    files: &discovery::ConflictedFiles,
+) -> Result<i32, CliError> {
+    let strategy = config.default_strategy;
+    let suggester = suggester(strategy, config)?;
+    let recorded = resolutions::ResolutionHistory::discover()
+        .map(|history| history.load())
+        .unwrap_or_default();
+    let mut any_unresolved = false;
+
+    for path in &files.content {
+        let result = headless::process_file(
+            path,
+            &recorded,
+            strategy,
+            suggester.as_deref(),
+            config.deduplicate,
+            config.fail_on_ambiguous,
+        )?;
+        any_unresolved |= result.hunks_unresolved > 0;
+        headless::write_or_print(&result, cli.dry_run, config.auto_stage)?;
+    }
+
+    let file_resolution = file_conflicts::headless_resolution(cli.file_resolution, strategy);
+    for pending in &files.file_level {
+        match file_resolution {
+            Some(resolution) => file_conflicts::apply(pending, resolution, cli.dry_run)?,
+            None if config.fail_on_ambiguous => {
+                return Err(CliError::UnresolvedFileConflict(pending.path.clone()));
+            }
+            None => {
+                any_unresolved = true;
+                file_conflicts::report_unresolved(pending, "use --file-resolution");
+            }
         }
+    }
 
-        return Ok(exit_codes::SUCCESS);
+    Ok(if any_unresolved {
+        exit_codes::UNRESOLVED
+    } else {
+        exit_codes::SUCCESS
+    })
+}
+
+/// Sets up the AI provider when `strategy` asks it for suggestions.
+#[cfg(feature = "ai")]
+fn suggester(
+    strategy: Strategy,
+    config: &WeavrConfig,
+) -> Result<Option<Box<dyn headless::Suggester>>, CliError> {
+    if !strategy.uses_ai() {
+        return Ok(None);
     }
+    Ok(Some(Box::new(ai::HeadlessAi::new(&config.ai)?)))
+}
 
-    // Mode: Interactive (TUI)
-    let mut any_unresolved = false;
+/// Rejects the AI strategies, which need a build with AI support.
+#[cfg(not(feature = "ai"))]
+fn suggester(
+    strategy: Strategy,
+    _config: &WeavrConfig,
+) -> Result<Option<Box<dyn headless::Suggester>>, CliError> {
+    if strategy.uses_ai() {
+        return Err(CliError::AiUnavailable(
+            "weavr was built without AI support (feature `ai`)".into(),
+        ));
+    }
+    Ok(None)
+}
 
-    for path in &files {
-        let result = tui::process_file(path, &config)?;
+/// Runs the TUI over all files, then writes and reports the results.
+fn run_interactive(
+    config: &WeavrConfig,
+    files: &discovery::ConflictedFiles,
+) -> Result<i32, CliError> {
+    let store = resume::session_store();
+    let history = resolutions::ResolutionHistory::discover();
+    let results = tui::process_files(files, config, store.as_ref(), history.as_ref())?;
+    let mut resolved_files = 0;
 
+    for result in &results.content {
+        let path = &result.path;
         if let Some(ref content) = result.content {
-            std::fs::write(path, content)?;
+            resolved_files += 1;
+            weavr_tui::save::write_atomic(path, content)?;
+            if let Some(ref store) = store {
+                let _ = store.remove(path);
+            }
+            let staged = config.auto_stage.then(|| stage::stage_resolved(path));
             println!(
-                "{}: {} hunks resolved",
+                "{}: {} hunks resolved{}",
                 path.display(),
-                result.hunks_resolved
+                result.hunks_resolved,
+                stage::summary_suffix(staged.as_ref())
             );
         } else {
-            any_unresolved = true;
+            let refused = config
+                .auto_stage
+                .then(|| stage::StageOutcome::Refused("quit with unresolved hunks".into()));
             eprintln!(
-                "{}: exited with {}/{} hunks unresolved",
+                "{}: exited with {}/{} hunks unresolved{}",
                 path.display(),
                 result.total_hunks - result.hunks_resolved,
-                result.total_hunks
+                result.total_hunks,
+                stage::summary_suffix(refused.as_ref())
             );
         }
     }
 
-    if any_unresolved {
+    for (pending, choice) in files.file_level.iter().zip(&results.file_level) {
+        if let Some(resolution) = *choice {
+            file_conflicts::apply(pending, resolution, false)?;
+            resolved_files += 1;
+        } else {
+            file_conflicts::report_unresolved(pending, "quit without choosing");
+        }
+    }
+
+    if files.len() > 1 {
+        println!("{resolved_files}/{} files resolved", files.len());
+    }
+
+    if resolved_files < files.len() {
         Ok(exit_codes::UNRESOLVED)
     } else {
         Ok(exit_codes::SUCCESS)
     }
 }
 
+/// Resolves conflicts in the TUI, stages them and continues the Git
+/// operation, repeating at each later step that stops on conflicts.
+///
+/// Stops early, leaving the operation in progress, when the user quits
+/// with conflicts unresolved or git stops without conflicts.
+fn run_continue(config: &WeavrConfig) -> Result<i32, CliError> {
+    let repo = weavr_git::GitRepo::discover()?;
+    let operation = repo.current_operation();
+    if !operation.has_conflicts() {
+        return Err(weavr_git::GitError::NoOperation.into());
+    }
+
+    // Resolved files must be staged before git can continue
+    let config = WeavrConfig {
+        auto_stage: true,
+        ..config.clone()
+    };
+
+    loop {
+        match discovery::resolve_files(Vec::new()) {
+            Ok(files) => {
+                let code = run_interactive(&config, &files)?;
+                if code != exit_codes::SUCCESS {
+                    eprintln!(
+                        "weavr: {operation} paused with unresolved conflicts; run `weavr --continue` to pick up again"
+                    );
+                    return Ok(code);
+                }
+            }
+            Err(CliError::NoConflictedFiles) => {}
+            Err(e) => return Err(e),
+        }
+
+        match repo.continue_operation()? {
+            OperationStatus::Finished => {
+                println!("{operation} complete");
+                return Ok(exit_codes::SUCCESS);
+            }
+            OperationStatus::Conflicted => {
+                let names = repo.side_names();
+                match names.theirs.subject {
+                    Some(subject) => println!("{operation} stopped with conflicts at: {subject}"),
+                    None => println!("{operation} stopped with conflicts"),
+                }
+            }
+            OperationStatus::Stopped => {
+                println!("{operation} stopped; run `weavr --continue` when ready");
+                return Ok(exit_codes::SUCCESS);
+            }
+        }
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
 
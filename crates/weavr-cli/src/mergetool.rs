@@ -0,0 +1,185 @@
+//! Running as a `git mergetool` backend.
+//!
+//! git invokes `weavr mergetool "$BASE" "$LOCAL" "$REMOTE" "$MERGED"` with
+//! temporary files for the three versions. The session is built from
+//! those files, the TUI opens on `$MERGED`, and the result is written to
+//! `$MERGED`. With `trustExitCode`, git treats a zero exit code as
+//! resolved and anything else as unresolved.
+
+use std::path::{Path, PathBuf};
+
+use weavr_core::{FileVersion, MergeInput, MergeSession};
+use weavr_git::{set_config, ConfigScope};
+
+use crate::config::WeavrConfig;
+use crate::driver::DRIVER_CMD;
+use crate::error::{exit_codes, CliError};
+
+/// The command git runs for `git mergetool --tool=weavr`.
+pub const MERGETOOL_CMD: &str = r#"weavr mergetool "$BASE" "$LOCAL" "$REMOTE" "$MERGED""#;
+
+/// The four paths git passes to a merge tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergetoolPaths {
+    /// Common ancestor (`$BASE`); missing or empty for add/add conflicts.
+    pub base: PathBuf,
+    /// Our version (`$LOCAL`).
+    pub local: PathBuf,
+    /// Their version (`$REMOTE`).
+    pub remote: PathBuf,
+    /// The file to write the result to (`$MERGED`).
+    pub merged: PathBuf,
+}
+
+/// Builds the merge input from the files git passed.
+///
+/// All versions are labelled with the `$MERGED` path. git passes an empty
+/// (or no) `$BASE` file when there is no common ancestor, as for add/add
+/// conflicts; either gives a two-way merge.
+pub fn merge_input(paths: &MergetoolPaths) -> Result<MergeInput, CliError> {
+    let version = |path: &Path| -> Result<FileVersion, CliError> {
+        Ok(FileVersion {
+            path: paths.merged.clone(),
+            content: std::fs::read_to_string(path)?,
+        })
+    };
+
+    let base = match std::fs::metadata(&paths.base) {
+        Ok(metadata) if metadata.len() > 0 => Some(version(&paths.base)?),
+        Ok(_) => None,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(MergeInput {
+        left: version(&paths.local)?,
+        right: version(&paths.remote)?,
+        base,
+    })
+}
+
+/// Resolves `$MERGED` in the TUI and writes the result.
+///
+/// Returns [`exit_codes::SUCCESS`] if the file was fully resolved and
+/// written, or [`exit_codes::UNRESOLVED`] (leaving `$MERGED` untouched) if
+/// the user quit first.
+pub fn run(paths: &MergetoolPaths, config: &WeavrConfig) -> Result<i32, CliError> {
+    let input = merge_input(paths)?;
+    let session = MergeSession::new(input)?;
+    let result = crate::tui::process_session(paths.merged.clone(), session, config)?;
+
+    let Some(content) = result.content else {
+        eprintln!(
+            "{}: exited with {}/{} hunks unresolved",
+            paths.merged.display(),
+            result.total_hunks - result.hunks_resolved,
+            result.total_hunks
+        );
+        return Ok(exit_codes::UNRESOLVED);
+    };
+
+    weavr_tui::save::write_atomic(&paths.merged, &content)?;
+    println!(
+        "{}: {} hunks resolved",
+        paths.merged.display(),
+        result.hunks_resolved
+    );
+    Ok(exit_codes::SUCCESS)
+}
+
+/// Registers weavr as a merge tool and merge driver in the git
+/// configuration, running git in `dir`.
+///
+/// Writes `mergetool.weavr.cmd` and `mergetool.weavr.trustExitCode`, so
+/// `git mergetool --tool=weavr` works, and `merge.weavr.name` and
+/// `merge.weavr.driver`, so `merge=weavr` can be used in `.gitattributes`.
+pub fn install(dir: &Path, scope: ConfigScope) -> Result<(), CliError> {
+    set_config(dir, scope, "mergetool.weavr.cmd", MERGETOOL_CMD)?;
+    set_config(dir, scope, "mergetool.weavr.trustExitCode", "true")?;
+    set_config(dir, scope, "merge.weavr.name", "weavr merge driver")?;
+    set_config(dir, scope, "merge.weavr.driver", DRIVER_CMD)?;
+
+    let scope = match scope {
+        ConfigScope::Local => "repository",
+        ConfigScope::Global => "global",
+    };
+    println!("Registered weavr as a merge tool and merge driver in the {scope} git config");
+    println!(
+        "Run `git mergetool --tool=weavr`, or set `merge.tool = weavr` to make it the default"
+    );
+    println!("Add `<pattern> merge=weavr` to .gitattributes to merge matching files with weavr");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_versions(dir: &Path, base: Option<&str>) -> MergetoolPaths {
+        let paths = MergetoolPaths {
+            base: dir.join("file_BASE.txt"),
+            local: dir.join("file_LOCAL.txt"),
+            remote: dir.join("file_REMOTE.txt"),
+            merged: dir.join("file.txt"),
+        };
+        if let Some(base) = base {
+            std::fs::write(&paths.base, base).unwrap();
+        }
+        std::fs::write(&paths.local, "a\nours\nc\n").unwrap();
+        std::fs::write(&paths.remote, "a\ntheirs\nc\n").unwrap();
+        paths
+    }
+
+    #[test]
+    fn builds_three_way_input_labelled_with_merged_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_versions(dir.path(), Some("a\nb\nc\n"));
+
+        let input = merge_input(&paths).unwrap();
+        assert_eq!(input.left.content, "a\nours\nc\n");
+        assert_eq!(input.right.content, "a\ntheirs\nc\n");
+        assert_eq!(input.base.unwrap().content, "a\nb\nc\n");
+        assert_eq!(input.left.path, paths.merged);
+
+        let session = MergeSession::new(merge_input(&paths).unwrap()).unwrap();
+        assert_eq!(session.hunks().len(), 1);
+    }
+
+    #[test]
+    fn missing_base_gives_two_way_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_versions(dir.path(), None);
+        assert!(merge_input(&paths).unwrap().base.is_none());
+    }
+
+    #[test]
+    fn empty_base_gives_two_way_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_versions(dir.path(), Some(""));
+        assert!(merge_input(&paths).unwrap().base.is_none());
+    }
+
+    #[test]
+    fn install_writes_mergetool_and_driver_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        install(dir.path(), ConfigScope::Local).unwrap();
+
+        let output = git(&["config", "mergetool.weavr.cmd"]);
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            MERGETOOL_CMD
+        );
+        let output = git(&["config", "mergetool.weavr.trustExitCode"]);
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "true");
+        let output = git(&["config", "merge.weavr.driver"]);
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), DRIVER_CMD);
+    }
+}
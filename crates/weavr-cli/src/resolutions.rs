@@ -0,0 +1,309 @@
+//! Recording resolutions and replaying them in later merges.
+//!
+//! Resolutions made in the TUI are recorded under `.git/weavr/resolutions/`
+//! when a file is completed. Later sessions offer them, along with those
+//! recorded by `git rerere` in `.git/rr-cache/`, as the first candidate for
+//! the same conflict, and headless mode applies them before the configured
+//! strategy. `weavr resolutions` lists, forgets and exports weavr's records.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use weavr_core::{MergeSession, RecordedResolution, RecordedResolutions};
+use weavr_git::{GitRepo, RerereCache};
+use weavr_tui::persist::JsonStore;
+
+use crate::cli::ResolutionsCommand;
+use crate::error::CliError;
+
+/// On-disk store of recorded resolutions, one JSON file per fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionStore {
+    store: JsonStore,
+}
+
+impl ResolutionStore {
+    /// Creates a store that keeps recorded resolutions in `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            store: JsonStore::new(dir),
+        }
+    }
+
+    /// Creates the store for a repository, in `<git-dir>/weavr/resolutions`.
+    pub fn for_git_dir(git_dir: &Path) -> Self {
+        Self::new(git_dir.join("weavr").join("resolutions"))
+    }
+
+    /// Records the resolved hunks of `session`, replacing earlier records
+    /// of the same conflicts. Returns the number of hunks recorded.
+    pub fn record(&self, session: &MergeSession) -> io::Result<usize> {
+        let records = session.recorded_resolutions();
+        for record in &records {
+            self.store.save(&name_for(&record.fingerprint), record)?;
+        }
+        Ok(records.len())
+    }
+
+    /// Returns all recorded resolutions, ordered by fingerprint.
+    ///
+    /// A missing store directory gives an empty list.
+    pub fn list(&self) -> io::Result<Vec<RecordedResolution>> {
+        let mut records: Vec<RecordedResolution> = self.store.list()?;
+        records.sort_by(|a, b| a.fingerprint.cmp(&b.fingerprint));
+        Ok(records)
+    }
+
+    /// Removes the recorded resolution with `fingerprint`.
+    ///
+    /// Returns `false` if there was none.
+    pub fn forget(&self, fingerprint: &str) -> io::Result<bool> {
+        self.store.remove(&name_for(fingerprint))
+    }
+}
+
+/// Returns the record name for `fingerprint`.
+///
+/// Anything but hex digits is dropped, so a fingerprint given on the
+/// command line cannot name a file outside the store.
+fn name_for(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(char::is_ascii_hexdigit)
+        .collect()
+}
+
+/// The recorded resolutions of a repository: weavr's own store and the
+/// `git rerere` cache.
+pub struct ResolutionHistory {
+    store: ResolutionStore,
+    rerere: RerereCache,
+}
+
+impl ResolutionHistory {
+    /// Returns the history of the current repository, if there is one.
+    pub fn discover() -> Option<Self> {
+        GitRepo::discover().ok().map(|repo| Self {
+            store: ResolutionStore::for_git_dir(repo.git_dir()),
+            rerere: repo.rerere_cache(),
+        })
+    }
+
+    /// Loads all recorded resolutions.
+    ///
+    /// weavr's records take precedence over rerere's for the same
+    /// conflict. An unreadable store or cache is reported on stderr and
+    /// skipped, so a damaged record never blocks resolving.
+    pub fn load(&self) -> RecordedResolutions {
+        let rerere = self.rerere.resolutions().unwrap_or_else(|e| {
+            eprintln!("weavr: ignoring git rerere cache ({e})");
+            Vec::new()
+        });
+        let own = self.store.list().unwrap_or_else(|e| {
+            eprintln!("weavr: ignoring recorded resolutions ({e})");
+            Vec::new()
+        });
+        rerere.into_iter().chain(own).collect()
+    }
+
+    /// Records the resolutions of a completed file, reporting failures on stderr.
+    pub fn record(&self, path: &Path, session: &MergeSession) {
+        if let Err(e) = self.store.record(session) {
+            eprintln!("{}: could not record resolutions ({e})", path.display());
+        }
+    }
+}
+
+/// Runs a `weavr resolutions` subcommand.
+pub fn run(command: &ResolutionsCommand) -> Result<(), CliError> {
+    let repo = GitRepo::discover()?;
+    let store = ResolutionStore::for_git_dir(repo.git_dir());
+
+    match command {
+        ResolutionsCommand::List => list(&store),
+        ResolutionsCommand::Forget { fingerprints, all } => forget(&store, fingerprints, *all),
+        ResolutionsCommand::Export { output } => {
+            let json = export(&store)?;
+            match output {
+                Some(path) => weavr_tui::save::write_atomic(path, &json)?,
+                None => println!("{json}"),
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Prints one line per recorded resolution.
+fn list(store: &ResolutionStore) -> Result<(), CliError> {
+    let records = store.list()?;
+    if records.is_empty() {
+        println!("No recorded resolutions");
+        return Ok(());
+    }
+    for record in &records {
+        println!("{}", summary(record));
+    }
+    Ok(())
+}
+
+/// Returns the listing line for a record: fingerprint, file and size.
+fn summary(record: &RecordedResolution) -> String {
+    let lines = record.content.lines().count();
+    let noun = if lines == 1 { "line" } else { "lines" };
+    format!(
+        "{}  {}  ({lines} {noun})",
+        record.fingerprint,
+        record.path.display()
+    )
+}
+
+/// Removes the given records, or all of them with `all`.
+fn forget(store: &ResolutionStore, fingerprints: &[String], all: bool) -> Result<(), CliError> {
+    let fingerprints: Vec<String> = if all {
+        store
+            .list()?
+            .into_iter()
+            .map(|record| record.fingerprint)
+            .collect()
+    } else {
+        fingerprints.to_vec()
+    };
+
+    let mut forgotten = 0;
+    for fingerprint in &fingerprints {
+        if store.forget(fingerprint)? {
+            forgotten += 1;
+        } else {
+            eprintln!("weavr: no recorded resolution {fingerprint}");
+        }
+    }
+    println!("Forgot {forgotten} recorded resolutions");
+    Ok(())
+}
+
+/// Returns all records as a pretty-printed JSON array.
+fn export(store: &ResolutionStore) -> Result<String, CliError> {
+    let records = store.list()?;
+    serde_json::to_string_pretty(&records)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use weavr_core::Resolution;
+
+    use super::*;
+
+    fn store_with_record(dir: &Path) -> ResolutionStore {
+        store_and_history(dir).0
+    }
+
+    fn store_and_history(dir: &Path) -> (ResolutionStore, ResolutionHistory) {
+        let content = "a\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\nb";
+        let mut session =
+            MergeSession::from_conflicted(content, PathBuf::from("src/lib.rs")).unwrap();
+        let hunk = session.hunks()[0].clone();
+        session
+            .set_resolution(hunk.id, Resolution::accept_left(&hunk))
+            .unwrap();
+
+        let history = ResolutionHistory {
+            store: ResolutionStore::new(dir.join("resolutions")),
+            rerere: RerereCache::new(dir.join("rr-cache")),
+        };
+        history.record(Path::new("src/lib.rs"), &session);
+        (history.store.clone(), history)
+    }
+
+    #[test]
+    fn records_lists_and_forgets() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ResolutionStore::new(dir.path().join("resolutions"));
+        assert!(store.list().unwrap().is_empty());
+
+        let (store, history) = store_and_history(dir.path());
+        let records = store.list().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].content, "left");
+        let fingerprint = records[0].fingerprint.clone();
+        assert!(history.load().get(&fingerprint).is_some());
+
+        assert!(store.forget(&fingerprint).unwrap());
+        assert!(!store.forget(&fingerprint).unwrap());
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn list_reports_corrupt_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ResolutionStore::new(dir.path());
+        std::fs::write(dir.path().join("0123.json"), "not json").unwrap();
+
+        let err = store.list().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn fingerprints_cannot_escape_the_store() {
+        assert_eq!(name_for("../../etc/passwd"), "ecad");
+    }
+
+    #[test]
+    fn summary_names_file_and_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store_with_record(dir.path());
+        let record = &store.list().unwrap()[0];
+
+        assert_eq!(
+            summary(record),
+            format!("{}  src/lib.rs  (1 line)", record.fingerprint)
+        );
+    }
+
+    #[test]
+    fn export_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store_with_record(dir.path());
+
+        let json = export(&store).unwrap();
+        let records: Vec<RecordedResolution> = serde_json::from_str(&json).unwrap();
+        assert_eq!(records, store.list().unwrap());
+    }
+
+    #[test]
+    fn forget_all_empties_the_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, history) = store_and_history(dir.path());
+        assert_eq!(history.load().len(), 1);
+
+        forget(&store, &[], true).unwrap();
+        assert!(store.list().unwrap().is_empty());
+        assert!(history.load().is_empty());
+    }
+
+    #[test]
+    fn own_records_take_precedence_over_rerere() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, history) = store_and_history(dir.path());
+        let fingerprint = store.list().unwrap()[0].fingerprint.clone();
+
+        let entry = dir.path().join("rr-cache").join("0123");
+        std::fs::create_dir_all(&entry).unwrap();
+        std::fs::write(
+            entry.join("preimage"),
+            "a\n<<<<<<<\nleft\n=======\nright\n>>>>>>>\nb\n",
+        )
+        .unwrap();
+        std::fs::write(entry.join("postimage"), "a\nfrom rerere\nb\n").unwrap();
+
+        let recorded = history.load();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded.get(&fingerprint).unwrap().content, "left");
+
+        forget(&store, &[], true).unwrap();
+        assert_eq!(
+            history.load().get(&fingerprint).unwrap().content,
+            "from rerere"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Resuming sessions saved by an earlier TUI run.
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use weavr_core::{ActionHistory, HunkState, MergeSession, SnapshotError};
+use weavr_git::GitRepo;
+use weavr_tui::persist::SessionStore;
+
+/// A saved session that matches the file's current conflict.
+pub struct Resumable {
+    /// The session with the saved resolutions applied.
+    pub session: MergeSession,
+    /// The saved undo/redo history.
+    pub history: ActionHistory,
+}
+
+impl Resumable {
+    /// Returns the number of resolved hunks in the saved session.
+    pub fn resolved_hunks(&self) -> usize {
+        self.session
+            .hunks()
+            .iter()
+            .filter(|h| matches!(h.state, HunkState::Resolved(_)))
+            .count()
+    }
+}
+
+/// Returns the session store of the current repository, if there is one.
+pub fn session_store() -> Option<SessionStore> {
+    GitRepo::discover()
+        .ok()
+        .map(|repo| SessionStore::for_git_dir(repo.git_dir()))
+}
+
+/// Looks up a saved session for `path` that can be restored onto `session`.
+///
+/// A snapshot taken from different conflict content, or one that cannot be
+/// read, is removed with a note on stderr, so stale decisions are never
+/// applied to a changed file.
+pub fn find_resumable(
+    store: &SessionStore,
+    path: &Path,
+    session: &MergeSession,
+) -> Option<Resumable> {
+    let snapshot = match store.load(path) {
+        Ok(snapshot) => snapshot?,
+        Err(e) => {
+            discard(store, path, &format!("unreadable ({e})"));
+            return None;
+        }
+    };
+
+    let mut restored = session.clone();
+    match restored.restore(snapshot) {
+        Ok(history) => Some(Resumable {
+            session: restored,
+            history,
+        }),
+        Err(SnapshotError::InputChanged) => {
+            discard(store, path, "file changed");
+            None
+        }
+        Err(e) => {
+            discard(store, path, &e.to_string());
+            None
+        }
+    }
+}
+
+/// Asks whether to resume saved progress. An empty answer means yes.
+///
+/// # Errors
+///
+/// Returns an error if the prompt cannot be written or the answer read.
+pub fn confirm_resume(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    files: usize,
+    hunks: usize,
+) -> io::Result<bool> {
+    let noun = if files == 1 { "file" } else { "files" };
+    write!(
+        output,
+        "Found saved progress for {files} {noun} ({hunks} hunks resolved). Resume? [Y/n] "
+    )?;
+    output.flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    let answer = answer.trim().to_ascii_lowercase();
+    Ok(answer.is_empty() || answer == "y" || answer == "yes")
+}
+
+/// Removes a saved session that will not be resumed.
+pub fn discard(store: &SessionStore, path: &Path, reason: &str) {
+    if store.remove(path).is_ok() {
+        eprintln!("{}: discarded saved session ({reason})", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFLICT: &str = "a\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\nb";
+
+    fn saved_session(dir: &Path) -> (SessionStore, std::path::PathBuf, MergeSession) {
+        let path = dir.join("file.txt");
+        std::fs::write(&path, CONFLICT).unwrap();
+        let store = SessionStore::new(dir.join("sessions"));
+
+        let mut session = MergeSession::from_conflicted(CONFLICT, path.clone()).unwrap();
+        let hunk = session.hunks()[0].clone();
+        session
+            .set_resolution(hunk.id, weavr_core::Resolution::accept_left(&hunk))
+            .unwrap();
+        store
+            .save(&path, &session.snapshot(&ActionHistory::new()))
+            .unwrap();
+
+        let fresh = MergeSession::from_conflicted(CONFLICT, path.clone()).unwrap();
+        (store, path, fresh)
+    }
+
+    #[test]
+    fn finds_matching_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, path, fresh) = saved_session(dir.path());
+
+        let resumable = find_resumable(&store, &path, &fresh).unwrap();
+        assert_eq!(resumable.resolved_hunks(), 1);
+        assert!(store.load(&path).unwrap().is_some());
+    }
+
+    #[test]
+    fn discards_snapshot_when_file_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, path, _) = saved_session(dir.path());
+        let changed =
+            MergeSession::from_conflicted(&CONFLICT.replace("right", "other"), path.clone())
+                .unwrap();
+
+        assert!(find_resumable(&store, &path, &changed).is_none());
+        assert!(store.load(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn confirm_resume_defaults_to_yes() {
+        let mut output = Vec::new();
+        assert!(confirm_resume(&mut &b"\n"[..], &mut output, 1, 3).unwrap());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Found saved progress for 1 file (3 hunks resolved). Resume? [Y/n] "
+        );
+        assert!(confirm_resume(&mut &b"Y\n"[..], &mut Vec::new(), 2, 1).unwrap());
+        assert!(!confirm_resume(&mut &b"n\n"[..], &mut Vec::new(), 2, 1).unwrap());
+    }
+}
@@ -0,0 +1,110 @@
+//! Staging resolved files in the Git index.
+
+use std::fmt;
+use std::path::Path;
+
+use weavr_git::GitRepo;
+
+use crate::discovery::has_conflict_markers;
+
+/// Outcome of staging a file after it was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageOutcome {
+    /// The file was added to the index.
+    Staged,
+    /// The file was deliberately left unstaged.
+    Refused(String),
+    /// Running `git add` failed.
+    Failed(String),
+}
+
+impl fmt::Display for StageOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StageOutcome::Staged => write!(f, "staged"),
+            StageOutcome::Refused(reason) => write!(f, "not staged: {reason}"),
+            StageOutcome::Failed(error) => write!(f, "staging failed: {error}"),
+        }
+    }
+}
+
+/// Stages a fully validated file with `git add`.
+///
+/// The file on disk is checked for conflict markers first, so a file is
+/// never staged while it still contains them.
+pub fn stage_resolved(path: &Path) -> StageOutcome {
+    match has_conflict_markers(path) {
+        Ok(false) => {}
+        Ok(true) => return StageOutcome::Refused("file contains conflict markers".into()),
+        Err(e) => return StageOutcome::Failed(e.to_string()),
+    }
+
+    let absolute = match path.canonicalize() {
+        Ok(absolute) => absolute,
+        Err(e) => return StageOutcome::Failed(e.to_string()),
+    };
+    let repo = match absolute
+        .parent()
+        .map_or_else(GitRepo::discover, GitRepo::discover_from)
+    {
+        Ok(repo) => repo,
+        Err(e) => return StageOutcome::Failed(e.to_string()),
+    };
+
+    match repo.stage_file(&absolute) {
+        Ok(()) => StageOutcome::Staged,
+        Err(e) => StageOutcome::Failed(e.to_string()),
+    }
+}
+
+/// Formats the staging suffix for a per-file summary line, e.g. ` (staged)`.
+pub fn summary_suffix(outcome: Option<&StageOutcome>) -> String {
+    outcome.map_or_else(String::new, |o| format!(" ({o})"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_display() {
+        assert_eq!(StageOutcome::Staged.to_string(), "staged");
+        assert_eq!(
+            StageOutcome::Refused("force-quit".into()).to_string(),
+            "not staged: force-quit"
+        );
+        assert_eq!(summary_suffix(None), "");
+        assert_eq!(summary_suffix(Some(&StageOutcome::Staged)), " (staged)");
+    }
+
+    #[test]
+    fn refuses_file_with_markers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        std::fs::write(&path, "<<<<<<< HEAD\na\n=======\nb\n>>>>>>> x\n").unwrap();
+
+        assert_eq!(
+            stage_resolved(&path),
+            StageOutcome::Refused("file contains conflict markers".into())
+        );
+    }
+
+    #[test]
+    fn stages_file_in_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        let path = dir.path().join("file.txt");
+        std::fs::write(&path, "resolved\n").unwrap();
+
+        assert_eq!(stage_resolved(&path), StageOutcome::Staged);
+        let status = git(&["status", "--porcelain=v1"]);
+        assert_eq!(String::from_utf8_lossy(&status.stdout), "A  file.txt\n");
+    }
+}
@@ -1,15 +1,23 @@
 //! TUI mode implementation.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use weavr_core::MergeSession;
+use weavr_git::{FileResolution, GitRepo};
+use weavr_tui::files::FileEntry;
+use weavr_tui::persist::SessionStore;
 use weavr_tui::App;
 
 use crate::config::WeavrConfig;
+use crate::discovery::{conflict_types, ConflictedFiles};
 use crate::error::CliError;
+use crate::resolutions::ResolutionHistory;
+use crate::resume::{self, Resumable};
 
 /// Result of TUI processing for a single file.
 pub struct TuiResult {
+    /// Path to the file.
+    pub path: PathBuf,
     /// The resolved content (if fully resolved and saved).
     pub content: Option<String>,
     /// Number of hunks that were resolved.
@@ -18,27 +26,145 @@ pub struct TuiResult {
     pub total_hunks: usize,
 }
 
-/// Runs the TUI for a single file.
+/// Results of a TUI run over all conflicted files.
+pub struct TuiResults {
+    /// One result per content conflict, in the order given.
+    pub content: Vec<TuiResult>,
+    /// The chosen resolution for each file-level conflict, in the order given.
+    pub file_level: Vec<Option<FileResolution>>,
+}
+
+/// Runs a single TUI over all files.
+///
+/// Files without conflicts are passed through without opening the TUI.
+/// File-level conflicts are shown alongside content conflicts; their
+/// chosen resolutions are returned for the caller to carry out with git.
 ///
-/// Returns the resolution result after the user quits the TUI.
-pub fn process_file(path: &Path, config: &WeavrConfig) -> Result<TuiResult, CliError> {
-    let content = std::fs::read_to_string(path)?;
-    let session = MergeSession::from_conflicted(&content, path.to_path_buf())?;
+/// With a `store`, progress saved by an earlier run is offered for resume
+/// and the TUI autosaves each change. With a `history`, recorded
+/// resolutions are offered as candidates and the resolutions of each
+/// completed file are recorded.
+pub fn process_files(
+    files: &ConflictedFiles,
+    config: &WeavrConfig,
+    store: Option<&SessionStore>,
+    history: Option<&ResolutionHistory>,
+) -> Result<TuiResults, CliError> {
+    let types = conflict_types();
+    let mut results: Vec<Option<TuiResult>> = Vec::with_capacity(files.content.len());
+    let mut sessions = Vec::new();
+    let mut slots = Vec::new();
+
+    for path in &files.content {
+        let loaded = crate::input::load_session(path)?;
+
+        // Handle files without conflicts (already clean)
+        if loaded.session.hunks().is_empty() {
+            results.push(Some(TuiResult {
+                path: path.clone(),
+                content: Some(loaded.content),
+                hunks_resolved: 0,
+                total_hunks: 0,
+            }));
+            continue;
+        }
+
+        let saved = store.and_then(|store| resume::find_resumable(store, path, &loaded.session));
+        sessions.push((path, loaded.session, saved));
+        slots.push(results.len());
+        results.push(None);
+    }
+
+    let resume = offer_resume(&sessions, store)?;
+    let mut entries = Vec::with_capacity(sessions.len() + files.file_level.len());
+    for (path, session, saved) in sessions {
+        let mut entry = match saved {
+            Some(saved) if resume => FileEntry::new(saved.session).with_history(saved.history),
+            _ => FileEntry::new(session),
+        };
+        if let Some(conflict_type) = lookup(&types, path) {
+            entry = entry.with_conflict_type(conflict_type);
+        }
+        entries.push(entry);
+    }
+
+    let content_entries = entries.len();
+    entries.extend(
+        files
+            .file_level
+            .iter()
+            .map(|pending| FileEntry::file_level(pending.path.clone(), pending.conflict.clone())),
+    );
+    let mut file_level = vec![None; files.file_level.len()];
+
+    if !entries.is_empty() {
+        let mut taken = run_tui(entries, config, store, history)?;
+        let file_entries = taken.split_off(content_entries);
+        for (choice, entry) in file_level.iter_mut().zip(file_entries) {
+            *choice = entry.file_resolution();
+        }
+
+        for (slot, entry) in slots.into_iter().zip(taken) {
+            let path = entry.path().to_path_buf();
+            let session = entry.into_session().ok_or_else(|| {
+                std::io::Error::other("merge session unexpectedly missing after TUI run")
+            })?;
+            if let Some(history) = history.filter(|_| session.is_fully_resolved()) {
+                history.record(&path, &session);
+            }
+            results[slot] = Some(finish_session(path, session)?);
+        }
+    }
 
-    // Handle files without conflicts (already clean)
+    Ok(TuiResults {
+        content: results.into_iter().flatten().collect(),
+        file_level,
+    })
+}
+
+/// Runs the TUI on a single session that was built by the caller.
+///
+/// A session without conflicts is completed without opening the TUI.
+pub fn process_session(
+    path: PathBuf,
+    session: MergeSession,
+    config: &WeavrConfig,
+) -> Result<TuiResult, CliError> {
     if session.hunks().is_empty() {
         return Ok(TuiResult {
-            content: Some(content),
+            path,
+            content: Some(session.conflicted_output()),
             hunks_resolved: 0,
             total_hunks: 0,
         });
     }
 
-    let total_hunks = session.hunks().len();
+    let session = run_tui(vec![FileEntry::new(session)], config, None, None)?
+        .pop()
+        .and_then(FileEntry::into_session)
+        .ok_or_else(|| std::io::Error::other("merge session unexpectedly missing after TUI run"))?;
+    finish_session(path, session)
+}
 
+/// Runs the TUI over `entries` and returns them once the user quits.
+fn run_tui(
+    entries: Vec<FileEntry>,
+    config: &WeavrConfig,
+    store: Option<&SessionStore>,
+    history: Option<&ResolutionHistory>,
+) -> Result<Vec<FileEntry>, CliError> {
     // Create and configure App
     let mut app = App::with_theme(config.theme);
-    app.set_session(session);
+    app.set_files(entries);
+    if let Some(store) = store {
+        app.set_session_store(store.clone());
+    }
+    if let Some(history) = history {
+        app.add_recorded_candidates(&history.load());
+    }
+    if let Ok(repo) = GitRepo::discover() {
+        app.set_side_names(repo.side_names(), config.own_change_left);
+    }
 
     // Wire up AI if configured
     #[cfg(feature = "ai")]
@@ -49,10 +175,42 @@ pub fn process_file(path: &Path, config: &WeavrConfig) -> Result<TuiResult, CliE
     // Run TUI event loop
     weavr_tui::run(&mut app)?;
 
-    // Extract session and check resolution state
-    let session = app
-        .take_session()
-        .ok_or_else(|| std::io::Error::other("merge session unexpectedly missing after TUI run"))?;
+    Ok(app.take_files())
+}
+
+/// Asks whether to resume saved sessions, if there are any.
+///
+/// Declined sessions are discarded so they are not offered again.
+fn offer_resume(
+    sessions: &[(&PathBuf, MergeSession, Option<Resumable>)],
+    store: Option<&SessionStore>,
+) -> Result<bool, CliError> {
+    let saved: Vec<_> = sessions
+        .iter()
+        .filter_map(|(path, _, saved)| saved.as_ref().map(|saved| (path, saved)))
+        .collect();
+    let Some(store) = store.filter(|_| !saved.is_empty()) else {
+        return Ok(false);
+    };
+
+    let hunks = saved.iter().map(|(_, s)| s.resolved_hunks()).sum();
+    let resume = resume::confirm_resume(
+        &mut std::io::stdin().lock(),
+        &mut std::io::stdout(),
+        saved.len(),
+        hunks,
+    )?;
+    if !resume {
+        for (path, _) in saved {
+            resume::discard(store, path, "declined");
+        }
+    }
+    Ok(resume)
+}
+
+/// Completes a session after the TUI exits, if it is fully resolved.
+fn finish_session(path: PathBuf, session: MergeSession) -> Result<TuiResult, CliError> {
+    let total_hunks = session.hunks().len();
     let resolved_count = session
         .hunks()
         .iter()
@@ -67,6 +225,7 @@ pub fn process_file(path: &Path, config: &WeavrConfig) -> Result<TuiResult, CliE
         let result = session.complete()?;
 
         Ok(TuiResult {
+            path,
             content: Some(result.content),
             hunks_resolved: result.summary.resolved_hunks,
             total_hunks,
@@ -74,6 +233,7 @@ pub fn process_file(path: &Path, config: &WeavrConfig) -> Result<TuiResult, CliE
     } else {
         // User quit without resolving all hunks
         Ok(TuiResult {
+            path,
             content: None,
             hunks_resolved: resolved_count,
             total_hunks,
@@ -81,6 +241,15 @@ pub fn process_file(path: &Path, config: &WeavrConfig) -> Result<TuiResult, CliE
     }
 }
 
+/// Finds the Git conflict type for a path given on the command line.
+fn lookup(
+    types: &std::collections::HashMap<PathBuf, weavr_git::ConflictType>,
+    path: &Path,
+) -> Option<weavr_git::ConflictType> {
+    let canonical = path.canonicalize().ok()?;
+    types.get(&canonical).copied()
+}
+
 // ---------------------------------------------------------------------------
 // AI background worker (feature-gated)
 // ---------------------------------------------------------------------------
@@ -93,12 +262,19 @@ fn spawn_ai_worker(ai_config: &weavr_ai::AiConfig) -> Option<weavr_tui::ai::AiHa
     use std::sync::mpsc;
     use weavr_tui::ai::{AiCommand, AiEvent, AiHandle};
 
-    let config = build_ai_config(ai_config);
+    let config = crate::ai::config(ai_config);
     if !config.enabled {
         return None;
     }
 
-    let strategy = build_ai_strategy(&config)?;
+    let strategy = match crate::ai::strategy(&config) {
+        Ok(strategy) => strategy,
+        Err(e) => {
+            eprintln!("weavr: AI provider error: {e}");
+            return None;
+        }
+    };
+    let context_lines = strategy.context_lines();
 
     let (cmd_tx, cmd_rx) = mpsc::channel::<AiCommand>();
     let (evt_tx, evt_rx) = mpsc::channel::<AiEvent>();
@@ -114,64 +290,32 @@ fn spawn_ai_worker(ai_config: &weavr_ai::AiConfig) -> Option<weavr_tui::ai::AiHa
         });
     });
 
-    Some(AiHandle::new(cmd_tx, evt_rx))
+    Some(AiHandle::new(cmd_tx, evt_rx).with_context_lines(context_lines))
 }
 
-/// Builds `AiConfig` starting from the config file values, then layering
-/// env-var auto-detection for fields that weren't explicitly set.
+/// Builds the provider request for a hunk sent by the TUI.
 #[cfg(feature = "ai")]
-fn build_ai_config(base: &weavr_ai::AiConfig) -> weavr_ai::AiConfig {
-    let mut config = base.clone();
-
-    // Auto-detect provider from env vars if not set in config
-    if config.provider.is_none() {
-        #[cfg(feature = "ai-claude")]
-        if std::env::var("ANTHROPIC_API_KEY").is_ok() {
-            config.enabled = true;
-            config.provider = Some("claude".into());
-        }
-
-        #[cfg(feature = "ai-openai")]
-        if !config.enabled && std::env::var("OPENAI_API_KEY").is_ok() {
-            config.enabled = true;
-            config.provider = Some("openai".into());
-        }
-    }
-
-    config
+fn ai_request(
+    hunk: &weavr_core::ConflictHunk,
+    file: &weavr_tui::ai::AiFileContext,
+) -> weavr_ai::AiRequest {
+    let path = file.path.to_string_lossy();
+    weavr_ai::AiRequest::from_hunk(hunk, (!path.is_empty()).then_some(&*path))
+        .with_subjects(file.ours_subject.clone(), file.theirs_subject.clone())
 }
 
-/// Builds an `AiStrategy` from the given configuration.
+/// Builds the event announcing a suggestion, with its verification warnings.
 #[cfg(feature = "ai")]
-fn build_ai_strategy(config: &weavr_ai::AiConfig) -> Option<weavr_ai::AiStrategy> {
-    let provider_name = config.provider.as_deref().unwrap_or("claude");
-    let provider: Box<dyn weavr_ai::AiProvider> = match provider_name {
-        #[cfg(feature = "ai-claude")]
-        "claude" => {
-            match weavr_ai::providers::ClaudeProvider::with_timeout(&config.claude, config.timeout)
-            {
-                Ok(p) => Box::new(p),
-                Err(e) => {
-                    eprintln!("weavr: AI provider error: {e}");
-                    return None;
-                }
-            }
-        }
-        #[cfg(feature = "ai-openai")]
-        "openai" => match weavr_ai::providers::OpenAiProvider::new(&config.openai) {
-            Ok(p) => Box::new(p),
-            Err(e) => {
-                eprintln!("weavr: AI provider error: {e}");
-                return None;
-            }
-        },
-        other => {
-            eprintln!("weavr: unknown AI provider '{other}'");
-            return None;
-        }
-    };
-
-    Some(weavr_ai::AiStrategy::new(provider, config.clone()))
+fn suggestion_event(
+    hunk_id: weavr_core::HunkId,
+    suggestion: weavr_ai::Suggestion,
+) -> weavr_tui::ai::AiEvent {
+    weavr_tui::ai::AiEvent::Suggestion {
+        hunk_id,
+        confidence: suggestion.resolution.metadata.confidence,
+        resolution: suggestion.resolution,
+        warnings: suggestion.verification.warnings,
+    }
 }
 
 /// Main loop for the AI background worker.
@@ -186,8 +330,9 @@ async fn ai_worker_loop(
     use weavr_tui::ai::{AiCommand, AiEvent};
 
     let cancelled = Arc::new(AtomicBool::new(false));
+    let mut deferred = std::collections::VecDeque::new();
 
-    while let Ok(cmd) = cmd_rx.recv() {
+    while let Some(cmd) = deferred.pop_front().or_else(|| cmd_rx.recv().ok()) {
         match cmd {
             AiCommand::Shutdown => break,
 
@@ -195,17 +340,16 @@ async fn ai_worker_loop(
                 cancelled.store(true, Ordering::Relaxed);
             }
 
-            AiCommand::Suggest { hunk_id, hunk } => {
+            AiCommand::Suggest {
+                hunk_id,
+                hunk,
+                file,
+            } => {
                 cancelled.store(false, Ordering::Relaxed);
-                match strategy.suggest(&hunk).await {
-                    Ok(Some(resolution)) => {
+                match strategy.suggest(&ai_request(&hunk, &file)).await {
+                    Ok(Some(suggestion)) => {
                         if !cancelled.load(Ordering::Relaxed) {
-                            let confidence = resolution.metadata.confidence;
-                            let _ = evt_tx.send(AiEvent::Suggestion {
-                                hunk_id,
-                                resolution,
-                                confidence,
-                            });
+                            let _ = evt_tx.send(suggestion_event(hunk_id, suggestion));
                         }
                     }
                     Ok(None) => {
@@ -225,41 +369,21 @@ async fn ai_worker_loop(
                 }
             }
 
-            AiCommand::SuggestAll { hunks } => {
+            AiCommand::SuggestAll { hunks, file } => {
                 cancelled.store(false, Ordering::Relaxed);
-                for (hunk_id, hunk) in hunks {
-                    if cancelled.load(Ordering::Relaxed) {
-                        break;
-                    }
-                    match strategy.suggest(&hunk).await {
-                        Ok(Some(resolution)) => {
-                            let confidence = resolution.metadata.confidence;
-                            let _ = evt_tx.send(AiEvent::Suggestion {
-                                hunk_id,
-                                resolution,
-                                confidence,
-                            });
-                        }
-                        Ok(None) => {
-                            let _ = evt_tx.send(AiEvent::NoSuggestion {
-                                hunk_id,
-                                reason: "Provider declined".into(),
-                            });
-                        }
-                        Err(e) => {
-                            let _ = evt_tx.send(AiEvent::Error {
-                                hunk_id,
-                                message: e.to_string(),
-                            });
-                        }
-                    }
+                if !suggest_batch(&strategy, hunks, &file, &cmd_rx, &evt_tx, &mut deferred).await {
+                    break;
                 }
                 let _ = evt_tx.send(AiEvent::BatchComplete);
             }
 
-            AiCommand::Explain { hunk_id, hunk } => {
+            AiCommand::Explain {
+                hunk_id,
+                hunk,
+                file,
+            } => {
                 cancelled.store(false, Ordering::Relaxed);
-                match strategy.explain(&hunk).await {
+                match strategy.explain(&ai_request(&hunk, &file)).await {
                     Ok(Some(text)) => {
                         if !cancelled.load(Ordering::Relaxed) {
                             let _ = evt_tx.send(AiEvent::Explanation { hunk_id, text });
@@ -284,3 +408,59 @@ async fn ai_worker_loop(
         }
     }
 }
+
+/// Suggests resolutions for a batch of hunks, sending each result and the
+/// progress as they arrive.
+///
+/// Commands received meanwhile are queued in `deferred`. Returns `false`
+/// if a shutdown was requested, which abandons the batch.
+#[cfg(feature = "ai")]
+async fn suggest_batch(
+    strategy: &weavr_ai::AiStrategy,
+    hunks: Vec<(weavr_core::HunkId, weavr_core::ConflictHunk)>,
+    file: &weavr_tui::ai::AiFileContext,
+    cmd_rx: &std::sync::mpsc::Receiver<weavr_tui::ai::AiCommand>,
+    evt_tx: &std::sync::mpsc::Sender<weavr_tui::ai::AiEvent>,
+    deferred: &mut std::collections::VecDeque<weavr_tui::ai::AiCommand>,
+) -> bool {
+    use std::ops::ControlFlow;
+    use weavr_tui::ai::{AiCommand, AiEvent};
+
+    let requests = hunks
+        .iter()
+        .map(|(hunk_id, hunk)| (*hunk_id, ai_request(hunk, file)))
+        .collect();
+    let mut running = true;
+
+    strategy
+        .suggest_batch(requests, |progress| {
+            let hunk_id = progress.hunk_id;
+            let _ = evt_tx.send(match progress.result {
+                Ok(Some(suggestion)) => suggestion_event(hunk_id, suggestion),
+                Ok(None) => AiEvent::NoSuggestion {
+                    hunk_id,
+                    reason: "Provider declined".into(),
+                },
+                Err(e) => AiEvent::Error {
+                    hunk_id,
+                    message: e.to_string(),
+                },
+            });
+            let _ = evt_tx.send(AiEvent::BatchProgress {
+                done: progress.done,
+                total: progress.total,
+            });
+
+            while let Ok(cmd) = cmd_rx.try_recv() {
+                if matches!(cmd, AiCommand::Shutdown) {
+                    running = false;
+                    return ControlFlow::Break(());
+                }
+                deferred.push_back(cmd);
+            }
+            ControlFlow::Continue(())
+        })
+        .await;
+
+    running
+}
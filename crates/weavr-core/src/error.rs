@@ -67,6 +67,20 @@ pub enum CompletionError {
     LifecycleError(LifecycleError),
 }
 
+/// Error restoring a [`SessionSnapshot`](crate::SessionSnapshot).
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The merge input differs from the one the snapshot was taken from.
+    #[error("merge input changed since the snapshot was taken")]
+    InputChanged,
+    /// The snapshot refers to a hunk the session does not have.
+    #[error("snapshot refers to unknown hunk: {0:?}")]
+    UnknownHunk(HunkId),
+    /// A saved resolution could not be applied.
+    #[error("failed to restore resolution: {0}")]
+    Resolution(#[from] ResolutionError),
+}
+
 /// Error for invalid lifecycle transitions.
 #[derive(Debug, Clone, Error, PartialEq, Eq)]
 pub enum LifecycleError {
@@ -0,0 +1,45 @@
+//! Stable, non-cryptographic hashing.
+
+/// FNV-1a 64-bit offset basis.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+/// FNV-1a 64-bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a 64-bit hasher, stable across platforms and Rust versions.
+///
+/// Used for fingerprints and file names that must not change between
+/// runs. It is not collision resistant, so anything keyed by it should
+/// also check what it stored.
+///
+/// Shared with the other weavr crates; it is not part of the stable API and
+/// may change or be removed in any release.
+#[derive(Debug, Clone)]
+pub struct Fnv1a(u64);
+
+impl Fnv1a {
+    /// Creates a hasher with the FNV-1a offset basis.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(FNV_OFFSET)
+    }
+
+    /// Adds `bytes` to the hash.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Returns the hash as 16 hex digits.
+    #[must_use]
+    pub fn finish_hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -8,13 +8,15 @@
 
 use std::collections::VecDeque;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{HunkId, Resolution};
 
 /// Default maximum history depth.
 const DEFAULT_MAX_DEPTH: usize = 100;
 
 /// A reversible action performed on a merge session.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Action {
     /// A resolution was set (or overridden) on a hunk.
     SetResolution {
@@ -61,7 +63,7 @@ impl Action {
 /// Uses two stacks: actions that have been performed (undo stack) and
 /// actions that have been undone (redo stack). When a new action is
 /// recorded, the redo stack is cleared.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActionHistory {
     undo_stack: VecDeque<Action>,
     redo_stack: Vec<Action>,
@@ -152,6 +154,12 @@ impl ActionHistory {
         self.redo_stack.len()
     }
 
+    /// Iterates over all recorded actions, undo stack first (oldest to newest),
+    /// then the redo stack.
+    pub fn actions(&self) -> impl Iterator<Item = &Action> {
+        self.undo_stack.iter().chain(self.redo_stack.iter())
+    }
+
     /// Clears all history (both undo and redo stacks).
     pub fn clear(&mut self) {
         self.undo_stack.clear();
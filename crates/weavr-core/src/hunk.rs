@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use crate::Resolution;
 
 /// Unique identifier for a conflict hunk.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct HunkId(pub u32);
 
 /// Content within a conflict hunk.
@@ -44,6 +44,21 @@ pub enum HunkState {
     Invalid,
 }
 
+/// Labels written after a hunk's conflict markers.
+///
+/// For `<<<<<<< HEAD` and `>>>>>>> feature/payments` the left label is
+/// `HEAD` and the right label is `feature/payments`. Hunks produced by a
+/// merge rather than parsed from markers have no labels.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MarkerLabels {
+    /// Label after `<<<<<<<`.
+    pub left: Option<String>,
+    /// Label after `|||||||` (diff3 only).
+    pub base: Option<String>,
+    /// Label after `>>>>>>>`.
+    pub right: Option<String>,
+}
+
 /// A contiguous region of conflicting content.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConflictHunk {
@@ -59,6 +74,9 @@ pub struct ConflictHunk {
     pub context: HunkContext,
     /// Resolution state.
     pub state: HunkState,
+    /// Conflict marker labels.
+    #[serde(default)]
+    pub labels: MarkerLabels,
 }
 
 #[cfg(test)]
@@ -21,19 +21,33 @@
 #![warn(missing_docs)]
 
 mod error;
+mod hash;
 mod history;
 mod hunk;
 mod input;
+mod merge;
 mod parser;
+mod recorded;
 mod resolution;
 mod result;
 mod session;
+mod snapshot;
+mod strategy;
 
 pub use error::*;
 pub use history::*;
 pub use hunk::*;
 pub use input::*;
+pub use merge::*;
 pub use parser::*;
+pub use recorded::*;
 pub use resolution::*;
 pub use result::*;
 pub use session::*;
+pub use snapshot::*;
+pub use strategy::*;
+
+// Used by the other weavr crates for file names and cache keys; hidden
+// because it is not part of the stable API.
+#[doc(hidden)]
+pub use hash::Fnv1a;
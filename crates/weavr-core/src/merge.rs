@@ -0,0 +1,549 @@
+//! Line-based three-way merge generation.
+//!
+//! All types in this module are **stable** and covered by semantic versioning.
+
+use crate::parser::DEFAULT_CONTEXT_LINES;
+use crate::{
+    ConflictHunk, HunkContent, HunkContext, HunkId, HunkState, MarkerLabels, ParsedConflict,
+    Segment,
+};
+
+/// Merges two file versions against an optional common ancestor.
+///
+/// With a base, this performs a line-based diff3 merge: regions changed on
+/// only one side (or changed identically on both) are merged automatically
+/// into [`Segment::Clean`], and overlapping changes become [`ConflictHunk`]s
+/// with `base` populated.
+///
+/// Without a base, `left` and `right` are compared directly: common lines are
+/// clean and every differing region becomes a conflict with `base: None`.
+///
+/// Lines are compared without their terminators, but clean segments keep
+/// them (`\n` or `\r\n`), so concatenating the clean text with the
+/// resolved hunks reproduces the file byte for byte. Hunk texts are the
+/// conflicting lines joined with `\n`.
+///
+/// Hunk context line numbers (`start_line_left`, `start_line_right`) are
+/// 1-indexed positions of the conflicting region within `left` and `right`.
+///
+/// # Examples
+///
+/// ```
+/// use weavr_core::{merge_three_way, Segment};
+///
+/// let base = "a\nb\nc\nd\ne\n";
+/// let left = "a\nB\nc\nd\ne\n";
+/// let right = "a\nb\nc\nD\ne\n";
+///
+/// let merged = merge_three_way(left, right, Some(base));
+/// assert!(merged.hunks.is_empty());
+/// assert_eq!(merged.segments, vec![Segment::Clean("a\nB\nc\nD\ne\n".to_string())]);
+/// ```
+#[must_use]
+pub fn merge_three_way(left: &str, right: &str, base: Option<&str>) -> ParsedConflict {
+    let left = Lines::new(left);
+    let right = Lines::new(right);
+    let mut builder = MergeBuilder::new(&left, line_ending(left.text));
+
+    match base {
+        Some(base) => merge_with_base(&mut builder, &Lines::new(base), &left, &right),
+        None => merge_without_base(&mut builder, &left, &right),
+    }
+
+    builder.finish()
+}
+
+/// The lines of one version, with and without their terminators.
+struct Lines<'a> {
+    text: &'a str,
+    /// Each line including its `\n` or `\r\n`, if it has one.
+    raw: Vec<&'a str>,
+    /// Each line without its terminator, for comparing.
+    keys: Vec<&'a str>,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Self {
+        let raw: Vec<&str> = text.split_inclusive('\n').collect();
+        let keys = raw.iter().map(|line| strip_line_ending(line)).collect();
+        Self { text, raw, keys }
+    }
+
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+}
+
+/// Returns `line` without its `\n` or `\r\n`.
+pub(crate) fn strip_line_ending(line: &str) -> &str {
+    line.strip_suffix('\n')
+        .map_or(line, |line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+/// Returns the line ending `text` uses: that of its first line, or `\n`.
+pub(crate) fn line_ending(text: &str) -> &'static str {
+    match text.find('\n') {
+        Some(end) if text[..end].ends_with('\r') => "\r\n",
+        _ => "\n",
+    }
+}
+
+/// Runs diff3 over `base`, `left` and `right`.
+///
+/// Base lines matched on both sides act as sync points. Everything between
+/// two sync points is an unstable chunk that is either merged automatically
+/// or emitted as a conflict.
+fn merge_with_base(builder: &mut MergeBuilder, base: &Lines, left: &Lines, right: &Lines) {
+    let mut match_left = vec![None; base.len()];
+    for (b, l) in lcs_pairs(&base.keys, &left.keys) {
+        match_left[b] = Some(l);
+    }
+    let mut match_right = vec![None; base.len()];
+    for (b, r) in lcs_pairs(&base.keys, &right.keys) {
+        match_right[b] = Some(r);
+    }
+
+    let (mut bo, mut lo, mut ro) = (0, 0, 0);
+    loop {
+        let sync = (bo..base.len()).find_map(|b| Some((b, match_left[b]?, match_right[b]?)));
+        let (bs, ls, rs) = sync.unwrap_or((base.len(), left.len(), right.len()));
+
+        let chunk_base = &base.keys[bo..bs];
+        let chunk_left = &left.keys[lo..ls];
+        let chunk_right = &right.keys[ro..rs];
+
+        if chunk_left == chunk_base {
+            builder.push_clean(&right.raw[ro..rs]);
+        } else if chunk_right == chunk_base || chunk_left == chunk_right {
+            builder.push_clean(&left.raw[lo..ls]);
+        } else {
+            builder.push_conflict(chunk_left, chunk_right, Some(chunk_base), lo, ro);
+        }
+
+        if sync.is_none() {
+            break;
+        }
+        builder.push_clean(&left.raw[ls..=ls]);
+        (bo, lo, ro) = (bs + 1, ls + 1, rs + 1);
+    }
+}
+
+/// Compares `left` and `right` directly, treating every difference as a conflict.
+fn merge_without_base(builder: &mut MergeBuilder, left: &Lines, right: &Lines) {
+    let (mut lo, mut ro) = (0, 0);
+    let pairs = lcs_pairs(&left.keys, &right.keys);
+
+    for (ls, rs) in pairs.into_iter().chain([(left.len(), right.len())]) {
+        if ls > lo || rs > ro {
+            builder.push_conflict(&left.keys[lo..ls], &right.keys[ro..rs], None, lo, ro);
+        }
+        if ls < left.len() {
+            builder.push_clean(&left.raw[ls..=ls]);
+        }
+        (lo, ro) = (ls + 1, rs + 1);
+    }
+}
+
+/// Accumulates merge output into segments and hunks.
+struct MergeBuilder<'a> {
+    /// Left version lines, used for hunk context.
+    left: &'a [&'a str],
+    /// Line ending for lines that lack one but are followed by more text.
+    line_ending: &'static str,
+    hunks: Vec<ConflictHunk>,
+    segments: Vec<Segment>,
+    clean_buffer: String,
+}
+
+impl<'a> MergeBuilder<'a> {
+    fn new(left: &'a Lines<'a>, line_ending: &'static str) -> Self {
+        Self {
+            left: &left.keys,
+            line_ending,
+            hunks: Vec::new(),
+            segments: Vec::new(),
+            clean_buffer: String::new(),
+        }
+    }
+
+    /// Appends lines with their terminators. The last line of a version may
+    /// lack one; it gets one if anything follows it.
+    fn push_clean(&mut self, lines: &[&str]) {
+        for line in lines {
+            self.terminate_clean();
+            self.clean_buffer.push_str(line);
+        }
+    }
+
+    /// Ends the clean text with a line ending, unless it is empty or
+    /// already ends with one.
+    fn terminate_clean(&mut self) {
+        if !self.clean_buffer.is_empty() && !self.clean_buffer.ends_with('\n') {
+            self.clean_buffer.push_str(self.line_ending);
+        }
+    }
+
+    /// Emits a conflict. `left_offset`/`right_offset` are 0-indexed line
+    /// positions of the conflicting region in each version.
+    fn push_conflict(
+        &mut self,
+        left: &[&str],
+        right: &[&str],
+        base: Option<&[&str]>,
+        left_offset: usize,
+        right_offset: usize,
+    ) {
+        self.terminate_clean();
+        self.flush_clean();
+
+        let before_start = left_offset.saturating_sub(DEFAULT_CONTEXT_LINES);
+        let after_start = left_offset + left.len();
+        let after_end = (after_start + DEFAULT_CONTEXT_LINES).min(self.left.len());
+
+        let hunk = ConflictHunk {
+            id: HunkId(u32::try_from(self.hunks.len()).unwrap_or(u32::MAX)),
+            left: HunkContent {
+                text: left.join("\n"),
+            },
+            right: HunkContent {
+                text: right.join("\n"),
+            },
+            base: base.map(|b| HunkContent { text: b.join("\n") }),
+            context: HunkContext {
+                before: to_owned_lines(&self.left[before_start..left_offset]),
+                after: to_owned_lines(&self.left[after_start..after_end]),
+                start_line_left: left_offset + 1,
+                start_line_right: right_offset + 1,
+            },
+            state: HunkState::Unresolved,
+            labels: MarkerLabels::default(),
+        };
+
+        self.segments.push(Segment::Conflict(self.hunks.len()));
+        self.hunks.push(hunk);
+    }
+
+    fn flush_clean(&mut self) {
+        if !self.clean_buffer.is_empty() {
+            self.segments
+                .push(Segment::Clean(std::mem::take(&mut self.clean_buffer)));
+        }
+    }
+
+    fn finish(mut self) -> ParsedConflict {
+        self.flush_clean();
+        ParsedConflict {
+            hunks: self.hunks,
+            segments: self.segments,
+        }
+    }
+}
+
+fn to_owned_lines(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|s| (*s).to_string()).collect()
+}
+
+/// Returns index pairs `(i, j)` with `a[i] == b[j]` forming a longest common
+/// subsequence of `a` and `b`, in increasing order.
+fn lcs_pairs(a: &[&str], b: &[&str]) -> Vec<(usize, usize)> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+    pairs.extend(
+        myers_pairs(a_mid, b_mid)
+            .into_iter()
+            .map(|(i, j)| (i + prefix, j + prefix)),
+    );
+    pairs.extend((0..suffix).map(|k| (a.len() - suffix + k, b.len() - suffix + k)));
+    pairs
+}
+
+/// Myers' O((N+M)D) shortest edit script, returning the matched line pairs.
+///
+/// Uses the paper's notation. Diagonal indices are signed, so this works in
+/// `isize` throughout; all values are bounded by `a.len() + b.len()`.
+#[allow(
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    clippy::many_single_char_names
+)]
+fn myers_pairs(a: &[&str], b: &[&str]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    if n == 0 || m == 0 {
+        return Vec::new();
+    }
+
+    // trace[d][k + d] is the furthest x reached on diagonal k with d edits.
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let mut end = (n, m);
+
+    'search: for d in 0..=(n + m) {
+        let mut v = vec![0; (2 * d + 1) as usize];
+        for k in (-d..=d).step_by(2) {
+            let mut x = if d == 0 {
+                0
+            } else {
+                let prev = &trace[(d - 1) as usize];
+                let at = |k: isize| prev[(k + d - 1) as usize];
+                if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+                    at(k + 1)
+                } else {
+                    at(k - 1) + 1
+                }
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[(k + d) as usize] = x;
+            if x >= n && y >= m {
+                end = (x, y);
+                trace.push(v);
+                break 'search;
+            }
+        }
+        trace.push(v);
+    }
+
+    let mut pairs = Vec::new();
+    let (mut x, mut y) = end;
+    for d in (1..trace.len() as isize).rev() {
+        let prev = &trace[(d - 1) as usize];
+        let at = |k: isize| prev[(k + d - 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let mid_x = if prev_k == k + 1 { prev_x } else { prev_x + 1 };
+
+        while x > mid_x {
+            x -= 1;
+            y -= 1;
+            pairs.push((x as usize, y as usize));
+        }
+        x = prev_x;
+        y = prev_x - prev_k;
+    }
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        pairs.push((x as usize, y as usize));
+    }
+
+    pairs.reverse();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clean(text: &str) -> Segment {
+        Segment::Clean(text.to_string())
+    }
+
+    #[test]
+    fn lcs_pairs_identical() {
+        let a = ["a", "b", "c"];
+        assert_eq!(lcs_pairs(&a, &a), vec![(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn lcs_pairs_disjoint() {
+        assert!(lcs_pairs(&["a", "b"], &["c", "d"]).is_empty());
+    }
+
+    #[test]
+    fn lcs_pairs_insertions_and_deletions() {
+        let a = ["a", "b", "c", "a", "b", "b", "a"];
+        let b = ["c", "b", "a", "b", "a", "c"];
+        let pairs = lcs_pairs(&a, &b);
+
+        // Classic Myers example: LCS length is 4.
+        assert_eq!(pairs.len(), 4);
+        for window in pairs.windows(2) {
+            assert!(window[0].0 < window[1].0 && window[0].1 < window[1].1);
+        }
+        for (i, j) in pairs {
+            assert_eq!(a[i], b[j]);
+        }
+    }
+
+    #[test]
+    fn lcs_pairs_empty_side() {
+        assert!(lcs_pairs(&[], &["a"]).is_empty());
+        assert!(lcs_pairs(&["a"], &[]).is_empty());
+    }
+
+    #[test]
+    fn identical_inputs_are_clean() {
+        let merged = merge_three_way("a\nb", "a\nb", Some("a\nb"));
+        assert!(merged.hunks.is_empty());
+        assert_eq!(merged.segments, vec![clean("a\nb")]);
+    }
+
+    #[test]
+    fn one_sided_changes_auto_merge() {
+        let base = "1\n2\n3\n4\n5";
+        let left = "1\nTWO\n3\n4\n5";
+        let right = "1\n2\n3\n4\nFIVE\n6";
+
+        let merged = merge_three_way(left, right, Some(base));
+        assert!(merged.hunks.is_empty());
+        assert_eq!(merged.segments, vec![clean("1\nTWO\n3\n4\nFIVE\n6")]);
+    }
+
+    #[test]
+    fn identical_changes_on_both_sides_are_clean() {
+        let merged = merge_three_way("a\nX\nc", "a\nX\nc", Some("a\nb\nc"));
+        assert!(merged.hunks.is_empty());
+        assert_eq!(merged.segments, vec![clean("a\nX\nc")]);
+    }
+
+    #[test]
+    fn deletion_on_one_side_auto_merges() {
+        let merged = merge_three_way("a\nc", "a\nb\nc", Some("a\nb\nc"));
+        assert!(merged.hunks.is_empty());
+        assert_eq!(merged.segments, vec![clean("a\nc")]);
+    }
+
+    #[test]
+    fn overlapping_changes_conflict() {
+        let base = "a\nb\nc";
+        let left = "a\nleft\nc";
+        let right = "a\nright\nc";
+
+        let merged = merge_three_way(left, right, Some(base));
+        assert_eq!(merged.hunks.len(), 1);
+        assert_eq!(
+            merged.segments,
+            vec![clean("a\n"), Segment::Conflict(0), clean("c")]
+        );
+
+        let hunk = &merged.hunks[0];
+        assert_eq!(hunk.id, HunkId(0));
+        assert_eq!(hunk.left.text, "left");
+        assert_eq!(hunk.right.text, "right");
+        assert_eq!(hunk.base.as_ref().unwrap().text, "b");
+        assert_eq!(hunk.state, HunkState::Unresolved);
+    }
+
+    #[test]
+    fn conflict_context_uses_real_line_numbers() {
+        let base = "1\n2\n3\n4\n5\n6\n7\n8";
+        let left = "1\n2\n3\n4\nL\n6\n7\n8";
+        let right = "0\n1\n2\n3\n4\nR\n6\n7\n8";
+
+        let merged = merge_three_way(left, right, Some(base));
+        assert_eq!(merged.hunks.len(), 1);
+
+        let context = &merged.hunks[0].context;
+        assert_eq!(context.start_line_left, 5);
+        assert_eq!(context.start_line_right, 6);
+        assert_eq!(context.before, vec!["2", "3", "4"]);
+        assert_eq!(context.after, vec!["6", "7", "8"]);
+    }
+
+    #[test]
+    fn multiple_conflicts_get_sequential_ids() {
+        let base = "a\nb\nc\nd\ne";
+        let left = "a\nB1\nc\nD1\ne";
+        let right = "a\nB2\nc\nD2\ne";
+
+        let merged = merge_three_way(left, right, Some(base));
+        assert_eq!(merged.hunks.len(), 2);
+        assert_eq!(merged.hunks[0].id, HunkId(0));
+        assert_eq!(merged.hunks[1].id, HunkId(1));
+        assert_eq!(
+            merged.segments,
+            vec![
+                clean("a\n"),
+                Segment::Conflict(0),
+                clean("c\n"),
+                Segment::Conflict(1),
+                clean("e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicting_insertions_at_same_point() {
+        let merged = merge_three_way("a\nL\nb", "a\nR\nb", Some("a\nb"));
+        assert_eq!(merged.hunks.len(), 1);
+        assert_eq!(merged.hunks[0].base.as_ref().unwrap().text, "");
+        assert_eq!(merged.hunks[0].left.text, "L");
+        assert_eq!(merged.hunks[0].right.text, "R");
+    }
+
+    #[test]
+    fn delete_versus_modify_conflicts() {
+        let merged = merge_three_way("a\nc", "a\nB\nc", Some("a\nb\nc"));
+        assert_eq!(merged.hunks.len(), 1);
+        assert_eq!(merged.hunks[0].left.text, "");
+        assert_eq!(merged.hunks[0].right.text, "B");
+    }
+
+    #[test]
+    fn without_base_differences_conflict() {
+        let merged = merge_three_way("a\nleft\nc", "a\nright\nc", None);
+        assert_eq!(merged.hunks.len(), 1);
+        assert!(merged.hunks[0].base.is_none());
+        assert_eq!(merged.hunks[0].left.text, "left");
+        assert_eq!(merged.hunks[0].right.text, "right");
+        assert_eq!(
+            merged.segments,
+            vec![clean("a\n"), Segment::Conflict(0), clean("c")]
+        );
+    }
+
+    #[test]
+    fn without_base_one_sided_insertion_still_conflicts() {
+        let merged = merge_three_way("a\nb", "a\nnew\nb", None);
+        assert_eq!(merged.hunks.len(), 1);
+        assert_eq!(merged.hunks[0].left.text, "");
+        assert_eq!(merged.hunks[0].right.text, "new");
+        assert_eq!(merged.hunks[0].context.start_line_right, 2);
+    }
+
+    #[test]
+    fn clean_segments_keep_line_endings() {
+        let merged = merge_three_way(
+            "a\r\nL\r\nc\r\n",
+            "a\r\nR\r\nc\r\n",
+            Some("a\r\nb\r\nc\r\n"),
+        );
+        assert_eq!(
+            merged.segments,
+            vec![clean("a\r\n"), Segment::Conflict(0), clean("c\r\n")]
+        );
+        assert_eq!(merged.hunks[0].left.text, "L");
+        assert_eq!(merged.hunks[0].base.as_ref().unwrap().text, "b");
+    }
+
+    #[test]
+    fn missing_final_newline_is_only_kept_at_the_end() {
+        let merged = merge_three_way("a", "a\nb\n", Some("a\n"));
+        assert!(merged.hunks.is_empty());
+        assert_eq!(merged.segments, vec![clean("a\nb\n")]);
+    }
+
+    #[test]
+    fn empty_inputs_produce_no_segments() {
+        let merged = merge_three_way("", "", Some(""));
+        assert!(merged.hunks.is_empty());
+        assert!(merged.segments.is_empty());
+    }
+}
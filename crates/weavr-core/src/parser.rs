@@ -4,15 +4,16 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{ConflictHunk, HunkContent, HunkContext, HunkId, HunkState, ParseError};
+use crate::merge::strip_line_ending;
+use crate::{ConflictHunk, HunkContent, HunkContext, HunkId, HunkState, MarkerLabels, ParseError};
 
 /// Default number of context lines before and after a conflict.
-const DEFAULT_CONTEXT_LINES: usize = 3;
+pub(crate) const DEFAULT_CONTEXT_LINES: usize = 3;
 
 /// A segment of a file - either clean text or a conflict.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Segment {
-    /// Non-conflicting text (preserved exactly).
+    /// Non-conflicting text (preserved exactly, line endings included).
     Clean(String),
     /// A conflict hunk (index into `ParsedConflict::hunks`).
     Conflict(usize),
@@ -40,17 +41,17 @@ enum ParserState {
     InRight,
 }
 
-/// Detected conflict marker type.
+/// Detected conflict marker type, with the marker's label if it has one.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Marker {
+enum Marker<'a> {
     /// <<<<<<< - Start of conflict.
-    Start,
+    Start(Option<&'a str>),
     /// ||||||| - Base content (diff3).
-    Base,
+    Base(Option<&'a str>),
     /// ======= - Separator between sides.
     Separator,
     /// >>>>>>> - End of conflict.
-    End,
+    End(Option<&'a str>),
 }
 
 /// Detects if a line is a conflict marker.
@@ -60,22 +61,34 @@ enum Marker {
 /// - `|||||||` - 7 pipe signs, optionally followed by space and label
 /// - `=======` - Exactly 7 equals signs (nothing after except whitespace)
 /// - `>>>>>>>` - 7 greater-than signs, optionally followed by space and label
-fn detect_marker(line: &str) -> Option<Marker> {
-    if line.starts_with("<<<<<<<") {
-        Some(Marker::Start)
-    } else if line.starts_with("|||||||") {
-        Some(Marker::Base)
+fn detect_marker(line: &str) -> Option<Marker<'_>> {
+    if let Some(rest) = line.strip_prefix("<<<<<<<") {
+        Some(Marker::Start(marker_label(rest)))
+    } else if let Some(rest) = line.strip_prefix("|||||||") {
+        Some(Marker::Base(marker_label(rest)))
     } else if line == "======="
         || line.starts_with("=======") && line[7..].chars().all(char::is_whitespace)
     {
         Some(Marker::Separator)
-    } else if line.starts_with(">>>>>>>") {
-        Some(Marker::End)
     } else {
-        None
+        line.strip_prefix(">>>>>>>")
+            .map(|rest| Marker::End(marker_label(rest)))
     }
 }
 
+/// Returns whether `line` is a conflict marker, by the same rules as
+/// [`parse_conflict_markers`].
+#[must_use]
+pub fn is_conflict_marker(line: &str) -> bool {
+    detect_marker(line).is_some()
+}
+
+/// Extracts the label following a marker, if there is one.
+fn marker_label(rest: &str) -> Option<&str> {
+    let label = rest.trim();
+    (!label.is_empty()).then_some(label)
+}
+
 /// Parses conflict markers from file content.
 ///
 /// Supports both standard 2-way conflicts and diff3 3-way conflicts.
@@ -109,12 +122,16 @@ fn detect_marker(line: &str) -> Option<Marker> {
 /// ```
 #[allow(clippy::too_many_lines)]
 pub fn parse_conflict_markers(content: &str) -> Result<ParsedConflict, ParseError> {
-    let lines: Vec<&str> = content.lines().collect();
+    let raw_lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let lines: Vec<&str> = raw_lines
+        .iter()
+        .map(|line| strip_line_ending(line))
+        .collect();
     let mut state = ParserState::Clean;
     let mut segments: Vec<Segment> = Vec::new();
     let mut hunks: Vec<ConflictHunk> = Vec::new();
 
-    let mut clean_buffer: Vec<String> = Vec::new();
+    let mut clean_buffer = String::new();
     let mut left_buffer: Vec<String> = Vec::new();
     let mut base_buffer: Option<Vec<String>> = None;
     let mut right_buffer: Vec<String> = Vec::new();
@@ -123,44 +140,46 @@ pub fn parse_conflict_markers(content: &str) -> Result<ParsedConflict, ParseErro
     let mut left_content_start: usize = 0;
     let mut right_content_start: usize = 0;
     let mut hunk_id_counter: u32 = 0;
+    let mut labels = MarkerLabels::default();
 
     for (line_num, line) in lines.iter().enumerate() {
         let one_indexed = line_num + 1;
 
         match (detect_marker(line), state) {
             // Start marker in clean state - begin new conflict
-            (Some(Marker::Start), ParserState::Clean) => {
+            (Some(Marker::Start(label)), ParserState::Clean) => {
                 // Flush clean buffer to segments
                 if !clean_buffer.is_empty() {
-                    segments.push(Segment::Clean(clean_buffer.join("\n")));
-                    clean_buffer.clear();
+                    segments.push(Segment::Clean(std::mem::take(&mut clean_buffer)));
                 }
+                labels.left = label.map(str::to_string);
                 hunk_start_line = one_indexed;
                 left_content_start = one_indexed + 1;
                 state = ParserState::InLeft;
             }
 
             // Start marker while already in conflict - nested conflict error
-            (Some(Marker::Start), _) => {
+            (Some(Marker::Start(_)), _) => {
                 return Err(ParseError::InvalidMarkers(format!(
                     "nested conflict marker at line {one_indexed}"
                 )));
             }
 
             // Base marker after left - enter diff3 base section
-            (Some(Marker::Base), ParserState::InLeft) => {
+            (Some(Marker::Base(label)), ParserState::InLeft) => {
+                labels.base = label.map(str::to_string);
                 base_buffer = Some(Vec::new());
                 state = ParserState::InBase;
             }
 
             // Base marker in wrong state
-            (Some(Marker::Base), ParserState::InBase) => {
+            (Some(Marker::Base(_)), ParserState::InBase) => {
                 return Err(ParseError::InvalidMarkers(format!(
                     "duplicate base marker at line {one_indexed}"
                 )));
             }
 
-            (Some(Marker::Base), _) => {
+            (Some(Marker::Base(_)), _) => {
                 return Err(ParseError::InvalidMarkers(format!(
                     "unexpected base marker at line {one_indexed}"
                 )));
@@ -186,7 +205,9 @@ pub fn parse_conflict_markers(content: &str) -> Result<ParsedConflict, ParseErro
             }
 
             // End marker after right - complete the hunk
-            (Some(Marker::End), ParserState::InRight) => {
+            (Some(Marker::End(label)), ParserState::InRight) => {
+                labels.right = label.map(str::to_string);
+
                 // Extract context lines
                 let context_start = if hunk_start_line > DEFAULT_CONTEXT_LINES {
                     hunk_start_line - DEFAULT_CONTEXT_LINES - 1
@@ -217,6 +238,7 @@ pub fn parse_conflict_markers(content: &str) -> Result<ParsedConflict, ParseErro
                         start_line_right: right_content_start,
                     },
                     state: HunkState::Unresolved,
+                    labels: std::mem::take(&mut labels),
                 };
 
                 let hunk_index = hunks.len();
@@ -230,7 +252,7 @@ pub fn parse_conflict_markers(content: &str) -> Result<ParsedConflict, ParseErro
             }
 
             // End marker in wrong state
-            (Some(Marker::End), _) => {
+            (Some(Marker::End(_)), _) => {
                 return Err(ParseError::InvalidMarkers(format!(
                     "unexpected end marker at line {one_indexed}"
                 )));
@@ -238,7 +260,7 @@ pub fn parse_conflict_markers(content: &str) -> Result<ParsedConflict, ParseErro
 
             // Regular line - add to appropriate buffer
             (None, ParserState::Clean) => {
-                clean_buffer.push((*line).to_string());
+                clean_buffer.push_str(raw_lines[line_num]);
             }
 
             (None, ParserState::InLeft) => {
@@ -266,7 +288,7 @@ pub fn parse_conflict_markers(content: &str) -> Result<ParsedConflict, ParseErro
 
     // Flush remaining clean content
     if !clean_buffer.is_empty() {
-        segments.push(Segment::Clean(clean_buffer.join("\n")));
+        segments.push(Segment::Clean(clean_buffer));
     }
 
     // Fill in 'after' context for all hunks
@@ -532,6 +554,17 @@ right content
         assert_eq!(result.hunks[0].context.start_line_right, 5);
     }
 
+    #[test]
+    fn is_conflict_marker_matches_parser_rules() {
+        assert!(is_conflict_marker("<<<<<<< HEAD"));
+        assert!(is_conflict_marker("|||||||"));
+        assert!(is_conflict_marker("======= "));
+        assert!(is_conflict_marker(">>>>>>> feature"));
+        assert!(!is_conflict_marker("======== heading"));
+        assert!(!is_conflict_marker("  <<<<<<< indented"));
+        assert!(!is_conflict_marker("a <<<<<<< b"));
+    }
+
     #[test]
     fn error_on_nested_start_marker() {
         let content = r"<<<<<<< HEAD
@@ -626,6 +659,33 @@ right
         assert_eq!(result.hunks[0].left.text, "left");
     }
 
+    #[test]
+    fn marker_labels_captured_per_hunk() {
+        let content = r"<<<<<<< HEAD
+left
+||||||| merged common ancestors
+base
+=======
+right
+>>>>>>> feature/payments
+<<<<<<<
+a
+=======
+b
+>>>>>>>   ";
+
+        let result = parse_conflict_markers(content).unwrap();
+        assert_eq!(
+            result.hunks[0].labels,
+            MarkerLabels {
+                left: Some("HEAD".into()),
+                base: Some("merged common ancestors".into()),
+                right: Some("feature/payments".into()),
+            }
+        );
+        assert_eq!(result.hunks[1].labels, MarkerLabels::default());
+    }
+
     #[test]
     fn six_equals_is_not_separator() {
         let content = "======\nnot a separator";
@@ -676,9 +736,9 @@ after";
 
         let result = parse_conflict_markers(content).unwrap();
         assert_eq!(result.segments.len(), 5);
-        assert!(matches!(&result.segments[0], Segment::Clean(s) if s == "before"));
+        assert!(matches!(&result.segments[0], Segment::Clean(s) if s == "before\n"));
         assert!(matches!(&result.segments[1], Segment::Conflict(0)));
-        assert!(matches!(&result.segments[2], Segment::Clean(s) if s == "middle"));
+        assert!(matches!(&result.segments[2], Segment::Clean(s) if s == "middle\n"));
         assert!(matches!(&result.segments[3], Segment::Conflict(1)));
         assert!(matches!(&result.segments[4], Segment::Clean(s) if s == "after"));
     }
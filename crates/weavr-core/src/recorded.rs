@@ -0,0 +1,375 @@
+//! Resolutions recorded from earlier merges.
+//!
+//! Long-lived branches that are rebased again and again hit the same
+//! conflicts repeatedly. A [`RecordedResolution`] remembers how a conflict
+//! was resolved, keyed by [`ConflictHunk::fingerprint`], so the decision
+//! can be offered again (or applied) when the conflict comes back. Storing
+//! the records is up to the caller.
+//!
+//! All types in this module are **stable** and covered by semantic versioning.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::Fnv1a;
+use crate::{
+    ConflictHunk, HunkId, HunkState, MergeSession, Resolution, ResolutionMetadata,
+    ResolutionSource, ResolutionStrategyKind,
+};
+
+/// How a conflict was resolved in an earlier merge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResolution {
+    /// Fingerprint of the conflict (see [`ConflictHunk::fingerprint`]).
+    pub fingerprint: String,
+    /// The file the resolution was recorded in.
+    pub path: PathBuf,
+    /// Left side of the conflict.
+    pub left: String,
+    /// Right side of the conflict.
+    pub right: String,
+    /// Base of the conflict, if it was a three-way conflict.
+    pub base: Option<String>,
+    /// The resolved content.
+    pub content: String,
+}
+
+impl RecordedResolution {
+    /// Records `content` as the resolution of `hunk` in the file at `path`.
+    #[must_use]
+    pub fn new(hunk: &ConflictHunk, content: String, path: PathBuf) -> Self {
+        Self {
+            fingerprint: hunk.fingerprint(),
+            path,
+            left: hunk.left.text.clone(),
+            right: hunk.right.text.clone(),
+            base: hunk.base.as_ref().map(|base| base.text.clone()),
+            content,
+        }
+    }
+
+    /// Returns the recorded content as a resolution to replay.
+    #[must_use]
+    pub fn replay(&self) -> Resolution {
+        Resolution {
+            kind: ResolutionStrategyKind::Recorded,
+            content: self.content.clone(),
+            metadata: ResolutionMetadata {
+                source: ResolutionSource::User,
+                notes: Some(format!("recorded in {}", self.path.display())),
+                confidence: None,
+            },
+        }
+    }
+}
+
+/// Recorded resolutions, keyed by fingerprint.
+///
+/// Collecting records keeps the last one for each fingerprint.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordedResolutions {
+    by_fingerprint: HashMap<String, RecordedResolution>,
+}
+
+impl RecordedResolutions {
+    /// Creates an empty collection.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the record for a fingerprint, if any.
+    #[must_use]
+    pub fn get(&self, fingerprint: &str) -> Option<&RecordedResolution> {
+        self.by_fingerprint.get(fingerprint)
+    }
+
+    /// Returns the number of records.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.by_fingerprint.len()
+    }
+
+    /// Returns true if there are no records.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.by_fingerprint.is_empty()
+    }
+}
+
+impl FromIterator<RecordedResolution> for RecordedResolutions {
+    fn from_iter<I: IntoIterator<Item = RecordedResolution>>(records: I) -> Self {
+        Self {
+            by_fingerprint: records
+                .into_iter()
+                .map(|record| (record.fingerprint.clone(), record))
+                .collect(),
+        }
+    }
+}
+
+impl ConflictHunk {
+    /// Returns a fingerprint of the conflict, as 16 hex digits.
+    ///
+    /// Line endings, trailing whitespace and blank lines at either end are
+    /// ignored. The sides are put in a fixed order before hashing, so a
+    /// conflict seen again with ours and theirs swapped (as when a merged
+    /// branch is later rebased) has the same fingerprint.
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        fingerprint(
+            &self.left.text,
+            &self.right.text,
+            self.base.as_ref().map(|base| base.text.as_str()),
+        )
+    }
+
+    /// Returns the fingerprint the conflict would have without its base.
+    ///
+    /// Records of two-way conflicts (such as those kept by `git rerere`,
+    /// which drops the base) are matched by this fingerprint.
+    #[must_use]
+    pub fn sides_fingerprint(&self) -> String {
+        fingerprint(&self.left.text, &self.right.text, None)
+    }
+}
+
+/// Hashes the normalized sides, in a fixed order, and the base.
+fn fingerprint(left: &str, right: &str, base: Option<&str>) -> String {
+    let left = normalize(left);
+    let right = normalize(right);
+    let (first, second) = if left <= right {
+        (left, right)
+    } else {
+        (right, left)
+    };
+
+    let mut hash = Fnv1a::new();
+    hash.feed(first.as_bytes());
+    hash.feed(&[0xff]);
+    hash.feed(second.as_bytes());
+    match base {
+        Some(base) => {
+            hash.feed(&[0xfe]);
+            hash.feed(normalize(base).as_bytes());
+        }
+        None => hash.feed(&[0xfd]),
+    }
+    hash.finish_hex()
+}
+
+/// Normalizes conflict text for fingerprinting.
+fn normalize(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let start = lines
+        .iter()
+        .position(|line| !line.is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(start, |i| i + 1);
+    lines[start..end].join("\n")
+}
+
+impl MergeSession {
+    /// Returns a record of each resolved hunk, to remember for later merges.
+    ///
+    /// Trivial resolutions are left out, since they are found again without
+    /// a record.
+    #[must_use]
+    pub fn recorded_resolutions(&self) -> Vec<RecordedResolution> {
+        let path = &self.input().left.path;
+        self.hunks()
+            .iter()
+            .filter_map(|hunk| match &hunk.state {
+                HunkState::Resolved(resolution)
+                    if resolution.kind != ResolutionStrategyKind::Trivial =>
+                {
+                    Some(RecordedResolution::new(
+                        hunk,
+                        resolution.content.clone(),
+                        path.clone(),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Resolves every unresolved hunk that has a recorded resolution.
+    ///
+    /// A hunk matches a record by [`ConflictHunk::fingerprint`], or failing
+    /// that by [`ConflictHunk::sides_fingerprint`]. Returns the IDs of the
+    /// hunks that were resolved.
+    pub fn apply_recorded(&mut self, recorded: &RecordedResolutions) -> Vec<HunkId> {
+        let matches = self.recorded_matches(recorded);
+        matches
+            .into_iter()
+            .filter(|(hunk_id, resolution)| {
+                self.set_resolution(*hunk_id, resolution.clone()).is_ok()
+            })
+            .map(|(hunk_id, _)| hunk_id)
+            .collect()
+    }
+
+    /// Offers recorded resolutions as the first candidate for every
+    /// unresolved hunk that has one.
+    ///
+    /// An existing candidate with the same content is replaced. Returns the
+    /// IDs of the hunks that received a recorded candidate.
+    pub fn propose_recorded(&mut self, recorded: &RecordedResolutions) -> Vec<HunkId> {
+        let matches = self.recorded_matches(recorded);
+        matches
+            .into_iter()
+            .filter(|(hunk_id, resolution)| {
+                let mut candidates: Vec<Resolution> = self
+                    .proposals(*hunk_id)
+                    .iter()
+                    .filter(|c| c.content != resolution.content)
+                    .cloned()
+                    .collect();
+                candidates.insert(0, resolution.clone());
+                self.set_proposals(*hunk_id, candidates).is_ok()
+            })
+            .map(|(hunk_id, _)| hunk_id)
+            .collect()
+    }
+
+    /// Returns the replayed resolution for each unresolved hunk with a record.
+    fn recorded_matches(&self, recorded: &RecordedResolutions) -> Vec<(HunkId, Resolution)> {
+        self.hunks()
+            .iter()
+            .filter(|hunk| !matches!(hunk.state, HunkState::Resolved(_)))
+            .filter_map(|hunk| {
+                recorded
+                    .get(&hunk.fingerprint())
+                    .or_else(|| recorded.get(&hunk.sides_fingerprint()))
+                    .map(|record| (hunk.id, record.replay()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFLICT: &str =
+        "a\n<<<<<<< HEAD\none\n||||||| base\nzero\n=======\nuno\n>>>>>>> b\nb\n<<<<<<< HEAD\ntwo\n=======\ndos\n>>>>>>> b\nc";
+
+    fn session(content: &str) -> MergeSession {
+        MergeSession::from_conflicted(content, PathBuf::from("file.txt")).unwrap()
+    }
+
+    fn recorded(session: &MergeSession) -> RecordedResolutions {
+        session.recorded_resolutions().into_iter().collect()
+    }
+
+    #[test]
+    fn fingerprint_ignores_whitespace_and_side_order() {
+        let a = session(CONFLICT);
+        let spaced = session(&CONFLICT.replace("one\n", "one  \r\n\n"));
+        let swapped =
+            session("a\n<<<<<<< HEAD\nuno\n||||||| base\nzero\n=======\none\n>>>>>>> b\nb");
+
+        let fingerprint = a.hunks()[0].fingerprint();
+        assert_eq!(fingerprint.len(), 16);
+        assert_eq!(spaced.hunks()[0].fingerprint(), fingerprint);
+        assert_eq!(swapped.hunks()[0].fingerprint(), fingerprint);
+        assert_ne!(a.hunks()[1].fingerprint(), fingerprint);
+    }
+
+    #[test]
+    fn fingerprint_depends_on_base() {
+        let with_base = session(CONFLICT);
+        let other_base = session(&CONFLICT.replace("zero", "cero"));
+        assert_ne!(
+            with_base.hunks()[0].fingerprint(),
+            other_base.hunks()[0].fingerprint()
+        );
+    }
+
+    #[test]
+    fn two_way_records_match_diff3_hunks() {
+        let mut two_way = session("a\n<<<<<<< HEAD\none\n=======\nuno\n>>>>>>> b\nb");
+        let hunk = two_way.hunks()[0].clone();
+        assert_eq!(hunk.fingerprint(), hunk.sides_fingerprint());
+        two_way
+            .set_resolution(hunk.id, Resolution::accept_left(&hunk))
+            .unwrap();
+
+        let mut diff3 = session(CONFLICT);
+        assert_ne!(diff3.hunks()[0].fingerprint(), hunk.fingerprint());
+        assert_eq!(diff3.hunks()[0].sides_fingerprint(), hunk.fingerprint());
+        assert_eq!(diff3.apply_recorded(&recorded(&two_way)).len(), 1);
+    }
+
+    #[test]
+    fn records_resolved_hunks_except_trivial() {
+        let mut session = session(
+            "<<<<<<< HEAD\nsame\n=======\nsame\n>>>>>>> b\nx\n<<<<<<< HEAD\ntwo\n=======\ndos\n>>>>>>> b",
+        );
+        session.resolve_trivial();
+        let hunk = session.hunks()[1].clone();
+        session
+            .set_resolution(hunk.id, Resolution::accept_right(&hunk))
+            .unwrap();
+
+        let records = session.recorded_resolutions();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].fingerprint, hunk.fingerprint());
+        assert_eq!(records[0].content, "dos");
+        assert_eq!(records[0].path, PathBuf::from("file.txt"));
+    }
+
+    #[test]
+    fn apply_recorded_resolves_matching_hunks() {
+        let mut earlier = session(CONFLICT);
+        let hunk = earlier.hunks()[0].clone();
+        earlier
+            .set_resolution(hunk.id, Resolution::accept_right(&hunk))
+            .unwrap();
+
+        let mut later = session(CONFLICT);
+        let applied = later.apply_recorded(&recorded(&earlier));
+        assert_eq!(applied, vec![hunk.id]);
+
+        let HunkState::Resolved(resolution) = &later.hunks()[0].state else {
+            panic!("hunk should be resolved");
+        };
+        assert_eq!(resolution.kind, ResolutionStrategyKind::Recorded);
+        assert_eq!(resolution.content, "uno");
+        assert_eq!(later.hunks()[1].state, HunkState::Unresolved);
+    }
+
+    #[test]
+    fn propose_recorded_goes_first() {
+        let mut earlier = session(CONFLICT);
+        let hunk = earlier.hunks()[1].clone();
+        earlier
+            .set_resolution(hunk.id, Resolution::accept_left(&hunk))
+            .unwrap();
+
+        let mut later = session(CONFLICT);
+        let other = later.hunks()[1].clone();
+        later
+            .set_proposals(
+                other.id,
+                vec![
+                    Resolution::accept_left(&other),
+                    Resolution::accept_right(&other),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(later.propose_recorded(&recorded(&earlier)), vec![other.id]);
+        let proposals = later.proposals(other.id);
+        assert_eq!(proposals.len(), 2);
+        assert_eq!(proposals[0].kind, ResolutionStrategyKind::Recorded);
+        assert_eq!(proposals[0].content, "two");
+        assert_eq!(proposals[1].kind, ResolutionStrategyKind::AcceptRight);
+    }
+}
@@ -59,6 +59,20 @@ fn combine_with_dedup(first: &str, second: &str, trim_whitespace: bool) -> Strin
     result
 }
 
+/// Normalizes text for comparison ignoring trailing whitespace and line
+/// endings.
+///
+/// Indentation, whitespace inside lines and blank lines are kept: in
+/// Python, YAML or Makefiles they change what the file means.
+fn trim_line_ends(text: &str) -> Vec<&str> {
+    text.lines().map(str::trim_end).collect()
+}
+
+/// Returns whether any line of `text` ends in whitespace.
+fn has_trailing_whitespace(text: &str) -> bool {
+    text.lines().any(|line| line.trim_end().len() != line.len())
+}
+
 /// Order for `AcceptBoth` strategy.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum BothOrder {
@@ -101,6 +115,13 @@ pub enum ResolutionStrategyKind {
         /// The AI provider name.
         provider: String,
     },
+    /// Trivially resolvable conflict (one-sided change, identical sides,
+    /// or trailing-whitespace-only divergence). The reason is recorded in the
+    /// resolution's `metadata.notes`.
+    Trivial,
+    /// Replayed from the resolution recorded for the same conflict in an
+    /// earlier merge (see [`RecordedResolution`](crate::RecordedResolution)).
+    Recorded,
 }
 
 /// Source of a resolution.
@@ -113,6 +134,8 @@ pub enum ResolutionSource {
     Ai,
     /// Resolution from AST analysis.
     Ast,
+    /// Resolution from a deterministic heuristic (e.g. trivial resolution).
+    Heuristic,
 }
 
 /// Metadata about a resolution.
@@ -210,6 +233,91 @@ impl Resolution {
         }
     }
 
+    /// Create a resolution for a trivially resolvable hunk, if it is one.
+    ///
+    /// A hunk is trivial when:
+    /// - both sides are identical (the conflict is spurious),
+    /// - only one side changed relative to `base`,
+    /// - one side changed only trailing whitespace or line endings relative
+    ///   to `base`, and the other side is kept, or
+    /// - the sides differ only in trailing whitespace and exactly one of
+    ///   them has none, which is kept.
+    ///
+    /// Other whitespace, such as indentation, is a real change.
+    ///
+    /// Returns `None` if the hunk needs a real decision. The returned
+    /// resolution has kind [`ResolutionStrategyKind::Trivial`], source
+    /// [`ResolutionSource::Heuristic`], and a note explaining the choice.
+    ///
+    /// # Examples
+    /// ```
+    /// use weavr_core::{
+    ///     ConflictHunk, HunkContent, HunkContext, HunkId, HunkState, MarkerLabels, Resolution,
+    /// };
+    ///
+    /// let text = |t: &str| HunkContent { text: t.to_string() };
+    /// let hunk = ConflictHunk {
+    ///     id: HunkId(0),
+    ///     left: text("original"),
+    ///     right: text("changed"),
+    ///     base: Some(text("original")),
+    ///     context: HunkContext::default(),
+    ///     state: HunkState::Unresolved,
+    ///     labels: MarkerLabels::default(),
+    /// };
+    ///
+    /// let resolution = Resolution::trivial(&hunk).unwrap();
+    /// assert_eq!(resolution.content, "changed");
+    /// ```
+    #[must_use]
+    pub fn trivial(hunk: &ConflictHunk) -> Option<Resolution> {
+        let left = &hunk.left.text;
+        let right = &hunk.right.text;
+        let base = hunk.base.as_ref().map(|b| &b.text);
+
+        let (content, note) = if left == right {
+            (left, "both sides made the same change")
+        } else if base == Some(left) {
+            (right, "only the right side changed")
+        } else if base == Some(right) {
+            (left, "only the left side changed")
+        } else if base.is_some_and(|b| trim_line_ends(b) == trim_line_ends(left)) {
+            (
+                right,
+                "left side changed only trailing whitespace; kept right",
+            )
+        } else if base.is_some_and(|b| trim_line_ends(b) == trim_line_ends(right)) {
+            (
+                left,
+                "right side changed only trailing whitespace; kept left",
+            )
+        } else if trim_line_ends(left) == trim_line_ends(right) {
+            match (
+                has_trailing_whitespace(left),
+                has_trailing_whitespace(right),
+            ) {
+                (true, false) => (
+                    right,
+                    "sides differ only in trailing whitespace; kept right",
+                ),
+                (false, true) => (left, "sides differ only in trailing whitespace; kept left"),
+                _ => return None,
+            }
+        } else {
+            return None;
+        };
+
+        Some(Resolution {
+            kind: ResolutionStrategyKind::Trivial,
+            content: content.clone(),
+            metadata: ResolutionMetadata {
+                source: ResolutionSource::Heuristic,
+                notes: Some(note.to_string()),
+                confidence: None,
+            },
+        })
+    }
+
     /// Create a resolution with user-provided content.
     ///
     /// This is the escape hatch for complex merges where automated strategies
@@ -240,7 +348,7 @@ impl Resolution {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::hunk::{HunkContent, HunkContext, HunkId, HunkState};
+    use crate::hunk::{HunkContent, HunkContext, HunkId, HunkState, MarkerLabels};
 
     fn test_hunk(left: &str, right: &str) -> ConflictHunk {
         ConflictHunk {
@@ -254,6 +362,7 @@ mod tests {
             base: None,
             context: HunkContext::default(),
             state: HunkState::default(),
+            labels: MarkerLabels::default(),
         }
     }
 
@@ -555,4 +664,75 @@ mod tests {
         let res2 = Resolution::manual(content);
         assert_eq!(res1, res2);
     }
+
+    // --- Trivial resolution ---
+
+    fn test_hunk_with_base(left: &str, right: &str, base: &str) -> ConflictHunk {
+        ConflictHunk {
+            base: Some(HunkContent {
+                text: base.to_string(),
+            }),
+            ..test_hunk(left, right)
+        }
+    }
+
+    #[test]
+    fn trivial_identical_sides() {
+        let resolution = Resolution::trivial(&test_hunk("same", "same")).unwrap();
+        assert_eq!(resolution.kind, ResolutionStrategyKind::Trivial);
+        assert_eq!(resolution.content, "same");
+        assert_eq!(resolution.metadata.source, ResolutionSource::Heuristic);
+        assert!(resolution.metadata.notes.is_some());
+    }
+
+    #[test]
+    fn trivial_only_right_changed() {
+        let hunk = test_hunk_with_base("base", "right", "base");
+        assert_eq!(Resolution::trivial(&hunk).unwrap().content, "right");
+    }
+
+    #[test]
+    fn trivial_only_left_changed() {
+        let hunk = test_hunk_with_base("left", "base", "base");
+        assert_eq!(Resolution::trivial(&hunk).unwrap().content, "left");
+    }
+
+    #[test]
+    fn trivial_trailing_whitespace_only_between_sides() {
+        let hunk = test_hunk("let x = 1;  ", "let x = 1;\n");
+        assert_eq!(Resolution::trivial(&hunk).unwrap().content, "let x = 1;\n");
+
+        // Neither side is clearly the clean one
+        let hunk = test_hunk("let x = 1; ", "let x = 1;\t");
+        assert!(Resolution::trivial(&hunk).is_none());
+    }
+
+    #[test]
+    fn trivial_trailing_whitespace_change_on_one_side() {
+        let hunk = test_hunk_with_base("fn a() {}  ", "fn b() {}", "fn a() {}");
+        assert_eq!(Resolution::trivial(&hunk).unwrap().content, "fn b() {}");
+
+        let hunk = test_hunk_with_base("fn b() {}", "fn a() {}\r\n", "fn a() {}");
+        assert_eq!(Resolution::trivial(&hunk).unwrap().content, "fn b() {}");
+    }
+
+    #[test]
+    fn trivial_keeps_indentation_and_inner_whitespace_changes() {
+        // A reindent is a real change in Python or YAML
+        let hunk = test_hunk_with_base("    pass", "return", "pass");
+        assert!(Resolution::trivial(&hunk).is_none());
+
+        let hunk = test_hunk_with_base("s = 'a  b'", "t = 'a b'", "s = 'a b'");
+        assert!(Resolution::trivial(&hunk).is_none());
+
+        let hunk = test_hunk("a\n\nb", "a\nb");
+        assert!(Resolution::trivial(&hunk).is_none());
+    }
+
+    #[test]
+    fn trivial_real_conflict_is_none() {
+        assert!(Resolution::trivial(&test_hunk("left", "right")).is_none());
+        let hunk = test_hunk_with_base("left", "right", "base");
+        assert!(Resolution::trivial(&hunk).is_none());
+    }
 }
@@ -3,16 +3,22 @@
 //! All types in this module are **stable** and covered by semantic versioning.
 
 use std::collections::HashMap;
+use std::ops::RangeBounds;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::merge::line_ending;
 use crate::{
-    parse_conflict_markers, ApplyError, CompletionError, ConflictHunk, FileVersion, HunkId,
-    HunkState, LifecycleError, MergeInput, MergeResult, MergeSummary, ParseError, ParsedConflict,
-    Resolution, ResolutionError, Segment, ValidationError,
+    merge_three_way, parse_conflict_markers, ApplyError, CompletionError, ConflictHunk,
+    FileVersion, HunkContext, HunkId, HunkState, LifecycleError, MergeEngine, MergeInput,
+    MergeResult, MergeSummary, ParseError, ParsedConflict, Resolution, ResolutionError,
+    ResolutionStrategy, Segment, ValidationError,
 };
 
+/// Length of Git's default conflict markers (`<<<<<<<`).
+pub const DEFAULT_MARKER_SIZE: usize = 7;
+
 /// The state of a merge session.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum MergeState {
@@ -46,25 +52,64 @@ pub struct MergeSession {
     state: MergeState,
     /// Applied resolutions.
     resolutions: HashMap<HunkId, Resolution>,
+    /// Candidate resolutions per hunk, kept across resolve/clear cycles.
+    proposals: HashMap<HunkId, Vec<Resolution>>,
 }
 
 impl MergeSession {
-    /// Creates a new merge session from input.
+    /// Creates a new merge session by merging the input versions.
+    ///
+    /// Runs a line-based three-way merge (see [`merge_three_way`]) over
+    /// `left`, `right` and the optional `base`. Regions that merge cleanly
+    /// become [`Segment::Clean`]; overlapping changes become conflict hunks.
+    /// If nothing conflicts, the session starts in [`MergeState::Validated`].
     ///
-    /// Note: This constructor is intended for future 3-way merge generation.
     /// For parsing existing Git conflicts, use [`from_conflicted`](Self::from_conflicted).
     ///
     /// # Errors
     ///
-    /// Returns `ParseError` if conflict markers cannot be parsed.
+    /// Currently never fails; the `Result` is kept for API stability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use weavr_core::{FileVersion, MergeInput, MergeSession};
+    ///
+    /// let version = |content: &str| FileVersion {
+    ///     path: PathBuf::from("file.txt"),
+    ///     content: content.to_string(),
+    /// };
+    /// let input = MergeInput {
+    ///     left: version("a\nleft\nc"),
+    ///     right: version("a\nright\nc"),
+    ///     base: Some(version("a\nb\nc")),
+    /// };
+    ///
+    /// let session = MergeSession::new(input).unwrap();
+    /// assert_eq!(session.hunks().len(), 1);
+    /// assert_eq!(session.hunks()[0].base.as_ref().unwrap().text, "b");
+    /// ```
     pub fn new(input: MergeInput) -> Result<Self, ParseError> {
-        // Placeholder implementation - actual parsing will be implemented later
+        let ParsedConflict { hunks, segments } = merge_three_way(
+            &input.left.content,
+            &input.right.content,
+            input.base.as_ref().map(|base| base.content.as_str()),
+        );
+
+        let state = if hunks.is_empty() {
+            MergeState::Validated
+        } else {
+            MergeState::Parsed
+        };
+
         Ok(Self {
             input,
-            hunks: Vec::new(),
-            segments: Vec::new(),
-            state: MergeState::Parsed,
+            hunks,
+            segments,
+            state,
             resolutions: HashMap::new(),
+            proposals: HashMap::new(),
         })
     }
 
@@ -128,16 +173,55 @@ impl MergeSession {
             hunks,
             state,
             resolutions: HashMap::new(),
+            proposals: HashMap::new(),
             segments,
         })
     }
 
+    /// Fills in the base of hunks parsed without one, as with
+    /// `merge.conflictStyle=merge`, from a merge of the full file versions.
+    ///
+    /// The conflict markers stay the source of truth: a base is only taken
+    /// from the hunk at the same position in the merge of `input`, when
+    /// both merges found as many hunks and that hunk has the same left and
+    /// right text. Hunks edited since the markers were written keep no
+    /// base. Returns the number of hunks given a base.
+    pub fn fill_missing_bases(&mut self, input: &MergeInput) -> usize {
+        let merged = merge_three_way(
+            &input.left.content,
+            &input.right.content,
+            input.base.as_ref().map(|base| base.content.as_str()),
+        );
+        if merged.hunks.len() != self.hunks.len() {
+            return 0;
+        }
+
+        let mut filled = 0;
+        for (hunk, other) in self.hunks.iter_mut().zip(merged.hunks) {
+            if hunk.base.is_none()
+                && other.base.is_some()
+                && hunk.left == other.left
+                && hunk.right == other.right
+            {
+                hunk.base = other.base;
+                filled += 1;
+            }
+        }
+        filled
+    }
+
     /// Returns all conflict hunks.
     #[must_use]
     pub fn hunks(&self) -> &[ConflictHunk] {
         &self.hunks
     }
 
+    /// Returns the hunk with the given ID, if any.
+    #[must_use]
+    pub fn hunk(&self, id: HunkId) -> Option<&ConflictHunk> {
+        self.hunks.iter().find(|h| h.id == id)
+    }
+
     /// Returns the current session state.
     #[must_use]
     pub fn state(&self) -> MergeState {
@@ -306,7 +390,8 @@ impl MergeSession {
         Ok(())
     }
 
-    /// Clears the resolution for a hunk, returning it to `Unresolved` state.
+    /// Clears the resolution for a hunk, returning it to `Unresolved` state
+    /// (or `Proposed`, if candidate resolutions were recorded for it).
     ///
     /// This enables undo/retry workflows. State transitions happen automatically
     /// based on hunk status.
@@ -333,7 +418,10 @@ impl MergeSession {
             .find(|h| h.id == hunk_id)
             .ok_or(ResolutionError::HunkNotFound(hunk_id))?;
 
-        hunk.state = HunkState::Unresolved;
+        hunk.state = match self.proposals.get(&hunk_id) {
+            Some(proposals) => HunkState::Proposed(proposals.clone()),
+            None => HunkState::Unresolved,
+        };
         self.resolutions.remove(&hunk_id);
 
         // Update session state based on hunk status
@@ -342,6 +430,236 @@ impl MergeSession {
         Ok(())
     }
 
+    // --- Proposal Methods ---
+
+    /// Generates candidate resolutions for a hunk without modifying state.
+    ///
+    /// Strategies are consulted in the given order. Returns an empty list if
+    /// the hunk doesn't exist or no strategy applies.
+    #[must_use]
+    pub fn propose_resolutions(
+        &self,
+        hunk_id: HunkId,
+        strategies: &[&dyn ResolutionStrategy],
+    ) -> Vec<Resolution> {
+        let Some(hunk) = self.hunk(hunk_id) else {
+            return Vec::new();
+        };
+        strategies.iter().filter_map(|s| s.propose(hunk)).collect()
+    }
+
+    /// Returns the candidate resolutions recorded for a hunk.
+    #[must_use]
+    pub fn proposals(&self, hunk_id: HunkId) -> &[Resolution] {
+        self.proposals.get(&hunk_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Records candidate resolutions for a hunk.
+    ///
+    /// An unresolved hunk moves to `Proposed` (or back to `Unresolved` if
+    /// `proposals` is empty). A resolved hunk keeps its resolution — the
+    /// user's choice always wins — but the candidates are remembered and
+    /// offered again if the resolution is cleared.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResolutionError::HunkNotFound` if the hunk doesn't exist.
+    /// Returns `ResolutionError::InvalidResolution` if the session state doesn't allow resolution.
+    pub fn set_proposals(
+        &mut self,
+        hunk_id: HunkId,
+        proposals: Vec<Resolution>,
+    ) -> Result<(), ResolutionError> {
+        match self.state {
+            MergeState::Parsed | MergeState::Active | MergeState::FullyResolved => {}
+            state => {
+                return Err(ResolutionError::InvalidResolution(format!(
+                    "cannot propose resolutions in state {state:?}"
+                )));
+            }
+        }
+
+        let hunk = self
+            .hunks
+            .iter_mut()
+            .find(|h| h.id == hunk_id)
+            .ok_or(ResolutionError::HunkNotFound(hunk_id))?;
+
+        if !matches!(hunk.state, HunkState::Resolved(_)) {
+            hunk.state = if proposals.is_empty() {
+                HunkState::Unresolved
+            } else {
+                HunkState::Proposed(proposals.clone())
+            };
+        }
+
+        if proposals.is_empty() {
+            self.proposals.remove(&hunk_id);
+        } else {
+            self.proposals.insert(hunk_id, proposals);
+        }
+
+        Ok(())
+    }
+
+    /// Runs the engine over every hunk and records its proposals.
+    ///
+    /// Resolved hunks are left untouched (their candidates are still recorded).
+    /// Does nothing if the session state doesn't allow resolution.
+    ///
+    /// Returns the number of hunks that received at least one proposal.
+    pub fn populate_proposals(&mut self, engine: &MergeEngine) -> usize {
+        let proposals: Vec<(HunkId, Vec<Resolution>)> = self
+            .hunks
+            .iter()
+            .map(|hunk| (hunk.id, engine.propose(hunk)))
+            .collect();
+
+        proposals
+            .into_iter()
+            .filter(|(hunk_id, candidates)| {
+                !candidates.is_empty() && self.set_proposals(*hunk_id, candidates.clone()).is_ok()
+            })
+            .count()
+    }
+
+    /// Resolves a hunk with one of its recorded proposals.
+    ///
+    /// Returns the selected resolution so callers can record it (e.g. in
+    /// an [`ActionHistory`](crate::ActionHistory)).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResolutionError::HunkNotFound` if the hunk doesn't exist.
+    /// Returns `ResolutionError::InvalidResolution` if `index` is out of range
+    /// or the session state doesn't allow resolution.
+    pub fn select_proposal(
+        &mut self,
+        hunk_id: HunkId,
+        index: usize,
+    ) -> Result<Resolution, ResolutionError> {
+        if self.hunk(hunk_id).is_none() {
+            return Err(ResolutionError::HunkNotFound(hunk_id));
+        }
+        let resolution = self.proposals(hunk_id).get(index).cloned().ok_or_else(|| {
+            ResolutionError::InvalidResolution(format!(
+                "no proposal at index {index} for hunk {}",
+                hunk_id.0
+            ))
+        })?;
+
+        self.set_resolution(hunk_id, resolution.clone())?;
+        Ok(resolution)
+    }
+
+    /// Resolves every unresolved hunk that is trivially resolvable.
+    ///
+    /// Uses [`Resolution::trivial`]: one-sided changes, identical sides and
+    /// trailing-whitespace-only divergence. Hunks that already have a resolution are
+    /// left untouched. Does nothing if the session state doesn't allow resolution.
+    ///
+    /// Returns the IDs of the hunks that were resolved, in file order.
+    pub fn resolve_trivial(&mut self) -> Vec<HunkId> {
+        let trivial: Vec<(HunkId, Resolution)> = self
+            .hunks
+            .iter()
+            .filter(|h| !matches!(h.state, HunkState::Resolved(_)))
+            .filter_map(|h| Resolution::trivial(h).map(|r| (h.id, r)))
+            .collect();
+
+        trivial
+            .into_iter()
+            .filter(|(hunk_id, resolution)| {
+                self.set_resolution(*hunk_id, resolution.clone()).is_ok()
+            })
+            .map(|(hunk_id, _)| hunk_id)
+            .collect()
+    }
+
+    /// Renders the file with resolved hunks applied and conflict markers
+    /// around the hunks that remain unresolved.
+    ///
+    /// Unlike [`apply`](Self::apply), this works in any state, which makes it
+    /// suitable for writing back partially resolved files. Hunks with a base
+    /// are rendered in diff3 style.
+    #[must_use]
+    pub fn conflicted_output(&self) -> String {
+        self.conflicted_output_with_marker_size(DEFAULT_MARKER_SIZE)
+    }
+
+    /// Like [`conflicted_output`](Self::conflicted_output), with conflict
+    /// markers `marker_size` characters long (Git's `conflict-marker-size`
+    /// attribute).
+    #[must_use]
+    pub fn conflicted_output_with_marker_size(&self, marker_size: usize) -> String {
+        self.render_segments(.., marker_size)
+    }
+
+    /// Returns the context of hunk `id` widened to `lines` lines on either
+    /// side, or `None` if there is no such hunk.
+    ///
+    /// The lines are taken from the file as
+    /// [`conflicted_output`](Self::conflicted_output) renders it, so other
+    /// hunks appear resolved or with their conflict markers.
+    #[must_use]
+    pub fn context_window(&self, id: HunkId, lines: usize) -> Option<HunkContext> {
+        let index = self.hunks.iter().position(|h| h.id == id)?;
+        let position = self
+            .segments
+            .iter()
+            .position(|segment| *segment == Segment::Conflict(index))?;
+
+        let before = self.render_segments(..position, DEFAULT_MARKER_SIZE);
+        let after = self.render_segments(position + 1.., DEFAULT_MARKER_SIZE);
+        let before: Vec<String> = before.lines().map(String::from).collect();
+
+        Some(HunkContext {
+            before: before[before.len().saturating_sub(lines)..].to_vec(),
+            after: after.lines().take(lines).map(String::from).collect(),
+            ..self.hunks[index].context.clone()
+        })
+    }
+
+    /// Renders segments with resolved hunks applied and markers around the rest.
+    fn render_segments(&self, range: impl RangeBounds<usize>, marker_size: usize) -> String {
+        let mut output = String::new();
+        for (index, segment) in self.segments.iter().enumerate() {
+            if !range.contains(&index) {
+                continue;
+            }
+            match segment {
+                Segment::Clean(text) => output.push_str(text),
+                Segment::Conflict(hunk_index) => {
+                    let hunk = &self.hunks[*hunk_index];
+                    if let HunkState::Resolved(resolution) = &hunk.state {
+                        self.push_lines(&mut output, &resolution.content, index);
+                    } else {
+                        self.push_lines(&mut output, &render_markers(hunk, marker_size), index);
+                    }
+                }
+            }
+        }
+        output
+    }
+
+    /// Appends the lines of `text`, the content of segment `index`, each
+    /// ending in the file's line ending. At the end of the file the last line
+    /// only gets one if the file ended with a newline. Empty text adds
+    /// nothing, not even a blank line.
+    fn push_lines(&self, output: &mut String, text: &str, index: usize) {
+        let file = &self.input.left.content;
+        let line_ending = line_ending(file);
+        let at_end = index + 1 == self.segments.len();
+
+        let mut lines = text.lines().peekable();
+        while let Some(line) = lines.next() {
+            output.push_str(line);
+            if lines.peek().is_some() || !at_end || file.ends_with('\n') {
+                output.push_str(line_ending);
+            }
+        }
+    }
+
     // --- Lifecycle Methods ---
 
     /// Generates the merged output text from all resolutions.
@@ -460,33 +778,54 @@ impl MergeSession {
     }
 
     /// Internal helper to generate output from resolved hunks.
+    ///
+    /// Clean segments keep their bytes and resolved hunks are written with
+    /// the file's line ending, so a file keeps its line endings and final
+    /// newline.
     fn generate_output(&self) -> Result<String, ApplyError> {
-        let mut output = String::new();
-        let segment_count = self.segments.len();
-
-        for (i, segment) in self.segments.iter().enumerate() {
-            match segment {
-                Segment::Clean(text) => {
-                    output.push_str(text);
-                }
-                Segment::Conflict(hunk_index) => {
-                    let hunk = &self.hunks[*hunk_index];
-                    if let HunkState::Resolved(resolution) = &hunk.state {
-                        output.push_str(&resolution.content);
-                    } else {
-                        return Err(ApplyError::InternalError(format!(
-                            "hunk {hunk_index} not resolved"
-                        )));
-                    }
-                }
-            }
-            if i < segment_count - 1 {
-                output.push('\n');
-            }
+        if let Some(index) = self
+            .hunks
+            .iter()
+            .position(|hunk| !matches!(hunk.state, HunkState::Resolved(_)))
+        {
+            return Err(ApplyError::InternalError(format!(
+                "hunk {index} not resolved"
+            )));
         }
+        Ok(self.render_segments(.., DEFAULT_MARKER_SIZE))
+    }
+}
 
-        Ok(output)
+/// Renders a hunk with Git-style conflict markers.
+///
+/// The hunk's marker labels are written back when it has them, so a
+/// re-rendered file keeps `<<<<<<< HEAD` and the like; otherwise the
+/// markers are labelled `ours`, `base` and `theirs`.
+fn render_markers(hunk: &ConflictHunk, marker_size: usize) -> String {
+    let marker = |c: char| c.to_string().repeat(marker_size);
+    let labels = &hunk.labels;
+    let mut lines: Vec<String> = vec![format!(
+        "{} {}",
+        marker('<'),
+        labels.left.as_deref().unwrap_or("ours")
+    )];
+    lines.extend(hunk.left.text.lines().map(str::to_string));
+    if let Some(base) = &hunk.base {
+        lines.push(format!(
+            "{} {}",
+            marker('|'),
+            labels.base.as_deref().unwrap_or("base")
+        ));
+        lines.extend(base.text.lines().map(str::to_string));
     }
+    lines.push(marker('='));
+    lines.extend(hunk.right.text.lines().map(str::to_string));
+    lines.push(format!(
+        "{} {}",
+        marker('>'),
+        labels.right.as_deref().unwrap_or("theirs")
+    ));
+    lines.join("\n")
 }
 
 #[cfg(test)]
@@ -519,22 +858,105 @@ mod tests {
     fn session_creation() {
         let session = MergeSession::new(test_input()).expect("should create session");
         assert_eq!(session.state(), MergeState::Parsed);
-        assert!(session.hunks().is_empty());
+        assert_eq!(session.hunks().len(), 1);
+        assert_eq!(session.hunks()[0].left.text, "left content");
+        assert_eq!(session.hunks()[0].right.text, "right content");
+        assert!(session.hunks()[0].base.is_none());
     }
 
     #[test]
     fn session_fully_resolved_when_empty() {
-        let session = MergeSession::new(test_input()).expect("should create session");
+        let mut input = test_input();
+        input.right.content = input.left.content.clone();
+        let session = MergeSession::new(input).expect("should create session");
         // Empty hunks means fully resolved (vacuously true)
+        assert!(session.hunks().is_empty());
         assert!(session.is_fully_resolved());
+        assert_eq!(session.state(), MergeState::Validated);
     }
 
     #[test]
     fn session_unresolved_hunks_empty() {
-        let session = MergeSession::new(test_input()).expect("should create session");
+        let mut input = test_input();
+        input.right.content = input.left.content.clone();
+        let session = MergeSession::new(input).expect("should create session");
         assert!(session.unresolved_hunks().is_empty());
     }
 
+    #[test]
+    fn session_new_auto_merges_with_base() {
+        let mut input = test_input();
+        input.base = Some(FileVersion {
+            path: PathBuf::from("test.rs"),
+            content: String::from("a\nb\nc\nd\ne"),
+        });
+        input.left.content = String::from("a\nB\nc\nd\ne");
+        input.right.content = String::from("a\nb\nc\nD\ne");
+
+        let session = MergeSession::new(input).expect("should create session");
+        assert!(session.hunks().is_empty());
+
+        let result = session.complete().expect("should complete");
+        assert_eq!(result.content, "a\nB\nc\nD\ne");
+    }
+
+    #[test]
+    fn session_new_conflict_resolves_to_output() {
+        let mut input = test_input();
+        input.base = Some(FileVersion {
+            path: PathBuf::from("test.rs"),
+            content: String::from("start\nbase\nend"),
+        });
+        input.left.content = String::from("start\nleft\nend");
+        input.right.content = String::from("start\nright\nend");
+
+        let mut session = MergeSession::new(input).expect("should create session");
+        assert_eq!(session.hunks().len(), 1);
+        assert_eq!(session.hunks()[0].base.as_ref().unwrap().text, "base");
+        assert_eq!(session.hunks()[0].context.start_line_left, 2);
+
+        let hunk_id = session.hunks()[0].id;
+        let resolution = Resolution::accept_right(&session.hunks()[0]);
+        session.set_resolution(hunk_id, resolution).unwrap();
+        assert_eq!(session.apply().unwrap(), "start\nright\nend");
+    }
+
+    #[test]
+    fn empty_resolution_leaves_no_blank_line() {
+        let mut input = test_input();
+        input.base = Some(FileVersion {
+            path: PathBuf::from("test.rs"),
+            content: String::from("a\nb\nc\n"),
+        });
+        input.left.content = String::from("a\nc\n");
+        input.right.content = String::from("a\nB\nc\n");
+
+        let mut session = MergeSession::new(input).expect("should create session");
+        let resolution = Resolution::accept_left(&session.hunks()[0]);
+        session.set_resolution(HunkId(0), resolution).unwrap();
+        assert_eq!(session.apply().unwrap(), "a\nc\n");
+    }
+
+    #[test]
+    fn output_keeps_line_endings_and_final_newline() {
+        let content =
+            "a\r\n<<<<<<< HEAD\r\nleft\r\nmore\r\n=======\r\nright\r\n>>>>>>> feature\r\n";
+        let mut session =
+            MergeSession::from_conflicted(content, PathBuf::from("test.rs")).expect("should parse");
+        assert_eq!(session.conflicted_output(), content);
+
+        let resolution = Resolution::accept_left(&session.hunks()[0]);
+        session.set_resolution(HunkId(0), resolution).unwrap();
+        assert_eq!(session.apply().unwrap(), "a\r\nleft\r\nmore\r\n");
+
+        let content = "a\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature";
+        let mut session =
+            MergeSession::from_conflicted(content, PathBuf::from("test.rs")).expect("should parse");
+        let resolution = Resolution::accept_right(&session.hunks()[0]);
+        session.set_resolution(HunkId(0), resolution).unwrap();
+        assert_eq!(session.apply().unwrap(), "a\nright");
+    }
+
     #[test]
     fn session_input_accessible() {
         let input = test_input();
@@ -560,6 +982,30 @@ after";
         assert_eq!(session.state(), MergeState::Parsed);
     }
 
+    #[test]
+    fn fill_missing_bases_matches_hunks_by_sides() {
+        let content = "a\n<<<<<<< HEAD\nL1\n=======\nR1\n>>>>>>> feature\nc\n<<<<<<< HEAD\nedited\n=======\nR2\n>>>>>>> feature\ne\n";
+        let mut session =
+            MergeSession::from_conflicted(content, PathBuf::from("test.rs")).expect("should parse");
+        let version = |content: &str| FileVersion {
+            path: PathBuf::from("test.rs"),
+            content: content.to_string(),
+        };
+        let input = MergeInput {
+            left: version("a\nL1\nc\nL2\ne\n"),
+            right: version("a\nR1\nc\nR2\ne\n"),
+            base: Some(version("a\nb\nc\nd\ne\n")),
+        };
+
+        assert_eq!(session.fill_missing_bases(&input), 1);
+        assert_eq!(session.hunks()[0].base.as_ref().unwrap().text, "b");
+        assert!(session.hunks()[1].base.is_none());
+        assert_eq!(
+            session.conflicted_output().lines().count(),
+            content.lines().count() + 2
+        );
+    }
+
     #[test]
     fn from_conflicted_no_conflicts_returns_validated() {
         let content = "clean content\nno conflicts here";
@@ -907,6 +1353,267 @@ after";
         assert!(!result.content.contains("left"));
     }
 
+    // --- Proposal Tests ---
+
+    fn text_engine() -> MergeEngine {
+        MergeEngine::with_text_strategies(crate::AcceptBothOptions::default())
+    }
+
+    #[test]
+    fn propose_resolutions_does_not_modify_state() {
+        let session = session_with_conflict();
+        let hunk_id = session.hunks()[0].id;
+
+        let proposals = session.propose_resolutions(
+            hunk_id,
+            &[&crate::AcceptLeftStrategy, &crate::ManualStrategy],
+        );
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].content, "left");
+        assert_eq!(session.hunks()[0].state, HunkState::Unresolved);
+        assert!(session.proposals(hunk_id).is_empty());
+    }
+
+    #[test]
+    fn propose_resolutions_unknown_hunk_is_empty() {
+        let session = session_with_conflict();
+        assert!(session
+            .propose_resolutions(HunkId(99), &[&crate::AcceptLeftStrategy])
+            .is_empty());
+    }
+
+    #[test]
+    fn populate_proposals_marks_hunks_proposed() {
+        let mut session = session_with_multiple_conflicts();
+        let count = session.populate_proposals(&text_engine());
+
+        assert_eq!(count, 2);
+        for hunk in session.hunks() {
+            assert!(matches!(&hunk.state, HunkState::Proposed(p) if p.len() == 3));
+            assert_eq!(session.proposals(hunk.id).len(), 3);
+        }
+        // Proposals are not resolutions
+        assert_eq!(session.state(), MergeState::Parsed);
+        assert_eq!(session.unresolved_hunks().len(), 2);
+    }
+
+    #[test]
+    fn select_proposal_resolves_hunk() {
+        let mut session = session_with_conflict();
+        session.populate_proposals(&text_engine());
+        let hunk_id = session.hunks()[0].id;
+
+        let selected = session.select_proposal(hunk_id, 1).unwrap();
+        assert_eq!(selected.content, "right");
+        assert_eq!(session.hunks()[0].state, HunkState::Resolved(selected));
+        assert_eq!(session.state(), MergeState::FullyResolved);
+    }
+
+    #[test]
+    fn select_proposal_out_of_range() {
+        let mut session = session_with_conflict();
+        session.populate_proposals(&text_engine());
+        let hunk_id = session.hunks()[0].id;
+
+        let result = session.select_proposal(hunk_id, 3);
+        assert!(matches!(result, Err(ResolutionError::InvalidResolution(_))));
+        assert!(matches!(
+            session.select_proposal(HunkId(99), 0),
+            Err(ResolutionError::HunkNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn proposals_never_override_user_choice() {
+        let mut session = session_with_conflict();
+        let hunk_id = session.hunks()[0].id;
+        let manual = Resolution::manual("mine".to_string());
+        session.set_resolution(hunk_id, manual.clone()).unwrap();
+
+        session.populate_proposals(&text_engine());
+        assert_eq!(session.hunks()[0].state, HunkState::Resolved(manual));
+        assert_eq!(session.proposals(hunk_id).len(), 3);
+    }
+
+    #[test]
+    fn clear_resolution_returns_to_proposed() {
+        let mut session = session_with_conflict();
+        session.populate_proposals(&text_engine());
+        let hunk_id = session.hunks()[0].id;
+
+        session.select_proposal(hunk_id, 0).unwrap();
+        session.clear_resolution(hunk_id).unwrap();
+
+        assert!(matches!(session.hunks()[0].state, HunkState::Proposed(_)));
+        assert_eq!(session.state(), MergeState::Active);
+    }
+
+    #[test]
+    fn set_proposals_empty_returns_to_unresolved() {
+        let mut session = session_with_conflict();
+        let hunk_id = session.hunks()[0].id;
+        session.populate_proposals(&text_engine());
+
+        session.set_proposals(hunk_id, Vec::new()).unwrap();
+        assert_eq!(session.hunks()[0].state, HunkState::Unresolved);
+        assert!(session.proposals(hunk_id).is_empty());
+    }
+
+    #[test]
+    fn cannot_set_proposals_after_applied() {
+        let mut session = session_with_conflict();
+        let hunk_id = session.hunks()[0].id;
+        let resolution = Resolution::accept_left(&session.hunks()[0]);
+        session.set_resolution(hunk_id, resolution).unwrap();
+        let _ = session.apply().unwrap();
+
+        let result = session.set_proposals(hunk_id, Vec::new());
+        assert!(matches!(result, Err(ResolutionError::InvalidResolution(_))));
+        assert_eq!(session.populate_proposals(&text_engine()), 0);
+    }
+
+    #[test]
+    fn hunk_lookup_by_id() {
+        let session = session_with_multiple_conflicts();
+        assert_eq!(session.hunk(HunkId(1)).unwrap().left.text, "left2");
+        assert!(session.hunk(HunkId(5)).is_none());
+    }
+
+    // --- Trivial Resolution Tests ---
+
+    fn diff3_session() -> MergeSession {
+        let content = r"start
+<<<<<<< HEAD
+base
+||||||| base
+base
+=======
+theirs
+>>>>>>> feature
+middle
+<<<<<<< HEAD
+ours
+||||||| base
+base
+=======
+theirs
+>>>>>>> feature
+end";
+        MergeSession::from_conflicted(content, PathBuf::from("test.rs")).unwrap()
+    }
+
+    #[test]
+    fn resolve_trivial_resolves_one_sided_hunks() {
+        let mut session = diff3_session();
+        let resolved = session.resolve_trivial();
+
+        assert_eq!(resolved, vec![HunkId(0)]);
+        assert_eq!(session.resolutions()[&HunkId(0)].content, "theirs");
+        assert_eq!(
+            session.resolutions()[&HunkId(0)].kind,
+            crate::ResolutionStrategyKind::Trivial
+        );
+        assert_eq!(session.unresolved_hunks(), vec![HunkId(1)]);
+        assert_eq!(session.state(), MergeState::Active);
+    }
+
+    #[test]
+    fn resolve_trivial_keeps_existing_resolutions() {
+        let mut session = diff3_session();
+        let manual = Resolution::manual("mine".to_string());
+        session.set_resolution(HunkId(0), manual.clone()).unwrap();
+
+        assert!(session.resolve_trivial().is_empty());
+        assert_eq!(session.resolutions()[&HunkId(0)], manual);
+    }
+
+    #[test]
+    fn conflicted_output_renders_remaining_markers() {
+        let mut session = diff3_session();
+        session.resolve_trivial();
+
+        let output = session.conflicted_output();
+        assert_eq!(
+            output,
+            "start\ntheirs\nmiddle\n<<<<<<< HEAD\nours\n||||||| base\nbase\n=======\ntheirs\n>>>>>>> feature\nend"
+        );
+
+        // Round-trips through the parser
+        let reparsed = MergeSession::from_conflicted(&output, PathBuf::from("test.rs")).unwrap();
+        assert_eq!(reparsed.hunks().len(), 1);
+        assert_eq!(reparsed.hunks()[0].left.text, "ours");
+        assert_eq!(reparsed.hunks()[0].labels, session.hunks()[1].labels);
+    }
+
+    #[test]
+    fn conflicted_output_falls_back_to_side_names_without_labels() {
+        let session = MergeSession::new(MergeInput {
+            left: FileVersion {
+                path: PathBuf::from("test.rs"),
+                content: "a\nleft\nb".into(),
+            },
+            right: FileVersion {
+                path: PathBuf::from("test.rs"),
+                content: "a\nright\nb".into(),
+            },
+            base: None,
+        })
+        .unwrap();
+
+        assert_eq!(
+            session.conflicted_output(),
+            "a\n<<<<<<< ours\nleft\n=======\nright\n>>>>>>> theirs\nb"
+        );
+    }
+
+    #[test]
+    fn conflicted_output_honours_marker_size() {
+        let session = diff3_session();
+        let output = session.conflicted_output_with_marker_size(10);
+
+        assert!(output.contains("\n<<<<<<<<<< HEAD\n"));
+        assert!(output.contains("\n|||||||||| base\n"));
+        assert!(output.contains("\n==========\n"));
+        assert!(output.contains("\n>>>>>>>>>> feature\n"));
+        assert_eq!(
+            session.conflicted_output_with_marker_size(DEFAULT_MARKER_SIZE),
+            session.conflicted_output()
+        );
+    }
+
+    #[test]
+    fn context_window_spans_the_file() {
+        let content = "1\n2\n3\n4\n5\n<<<<<<< HEAD\nx\n=======\ny\n>>>>>>> other\n6\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\n7\n8\n9\n10";
+        let mut session = MergeSession::from_conflicted(content, PathBuf::from("f")).unwrap();
+        let second = session.hunks()[1].id;
+
+        let window = session.context_window(second, 4).unwrap();
+        assert_eq!(window.before, vec!["=======", "y", ">>>>>>> other", "6"]);
+        assert_eq!(window.after, vec!["7", "8", "9", "10"]);
+        assert_eq!(
+            window.start_line_left,
+            session.hunks()[1].context.start_line_left
+        );
+
+        let first = session.hunks()[0].clone();
+        session
+            .set_resolution(first.id, Resolution::accept_left(&first))
+            .unwrap();
+        let window = session.context_window(second, 100).unwrap();
+        assert_eq!(window.before, vec!["1", "2", "3", "4", "5", "x", "6"]);
+        assert!(session.context_window(HunkId(99), 3).is_none());
+    }
+
+    #[test]
+    fn conflicted_output_matches_apply_when_resolved() {
+        let mut session = session_with_conflict();
+        let resolution = Resolution::accept_left(&session.hunks()[0]);
+        session.set_resolution(HunkId(0), resolution).unwrap();
+
+        let partial = session.conflicted_output();
+        assert_eq!(partial, session.apply().unwrap());
+    }
+
     #[test]
     fn can_transition_valid_transitions() {
         // Test all valid transitions
@@ -0,0 +1,177 @@
+//! Snapshots of in-progress merge sessions.
+//!
+//! A [`SessionSnapshot`] captures the decisions made so far (resolutions and
+//! undo/redo history) so a session can be saved and resumed later. Each
+//! snapshot records a fingerprint of the merge input it was taken from, and
+//! is only restored onto a session with the same input.
+//!
+//! All types in this module are **stable** and covered by semantic versioning.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::Fnv1a;
+use crate::{ActionHistory, HunkId, MergeInput, MergeSession, Resolution, SnapshotError};
+
+/// Saved state of an in-progress merge session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// Fingerprint of the merge input (see [`MergeInput::fingerprint`]).
+    pub input_hash: String,
+    /// Resolutions by hunk, ordered by hunk ID.
+    pub resolutions: Vec<(HunkId, Resolution)>,
+    /// Undo/redo history.
+    pub history: ActionHistory,
+}
+
+impl MergeInput {
+    /// Returns a stable fingerprint of the input contents, as 16 hex digits.
+    ///
+    /// Paths are not included, so the same conflict gives the same
+    /// fingerprint wherever it is opened from. The hash (FNV-1a) is stable
+    /// across platforms and Rust versions.
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        let mut hash = Fnv1a::new();
+        hash.feed(self.left.content.as_bytes());
+        hash.feed(&[0xff]);
+        hash.feed(self.right.content.as_bytes());
+        match &self.base {
+            Some(base) => {
+                hash.feed(&[0xfe]);
+                hash.feed(base.content.as_bytes());
+            }
+            None => hash.feed(&[0xfd]),
+        }
+        hash.finish_hex()
+    }
+}
+
+impl MergeSession {
+    /// Captures the session's resolutions and the given undo/redo history.
+    #[must_use]
+    pub fn snapshot(&self, history: &ActionHistory) -> SessionSnapshot {
+        let mut resolutions: Vec<_> = self
+            .resolutions()
+            .iter()
+            .map(|(id, resolution)| (*id, resolution.clone()))
+            .collect();
+        resolutions.sort_by_key(|(id, _)| *id);
+
+        SessionSnapshot {
+            input_hash: self.input().fingerprint(),
+            resolutions,
+            history: history.clone(),
+        }
+    }
+
+    /// Restores resolutions from a snapshot and returns its history.
+    ///
+    /// The session is left unchanged if restoring fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SnapshotError::InputChanged`] if the snapshot was taken
+    /// from different input, [`SnapshotError::UnknownHunk`] if it refers to
+    /// a hunk this session does not have, or [`SnapshotError::Resolution`]
+    /// if a resolution cannot be set in the session's current state.
+    pub fn restore(&mut self, snapshot: SessionSnapshot) -> Result<ActionHistory, SnapshotError> {
+        if snapshot.input_hash != self.input().fingerprint() {
+            return Err(SnapshotError::InputChanged);
+        }
+
+        let referenced = snapshot
+            .resolutions
+            .iter()
+            .map(|(id, _)| *id)
+            .chain(snapshot.history.actions().map(crate::Action::hunk_id));
+        for id in referenced {
+            if self.hunk(id).is_none() {
+                return Err(SnapshotError::UnknownHunk(id));
+            }
+        }
+
+        let mut restored = self.clone();
+        for (id, resolution) in snapshot.resolutions {
+            restored.set_resolution(id, resolution)?;
+        }
+        *self = restored;
+
+        Ok(snapshot.history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::Action;
+
+    const CONFLICT: &str = "a\n<<<<<<< HEAD\none\n=======\nuno\n>>>>>>> b\nb\n<<<<<<< HEAD\ntwo\n=======\ndos\n>>>>>>> b\nc";
+
+    fn session(content: &str) -> MergeSession {
+        MergeSession::from_conflicted(content, PathBuf::from("file.txt")).unwrap()
+    }
+
+    #[test]
+    fn fingerprint_depends_on_content_only() {
+        let a = session(CONFLICT);
+        let b = MergeSession::from_conflicted(CONFLICT, PathBuf::from("other.txt")).unwrap();
+        assert_eq!(a.input().fingerprint(), b.input().fingerprint());
+        assert_eq!(a.input().fingerprint().len(), 16);
+
+        let changed = session(&CONFLICT.replace("dos", "zwei"));
+        assert_ne!(a.input().fingerprint(), changed.input().fingerprint());
+    }
+
+    #[test]
+    fn snapshot_round_trips_resolutions_and_history() {
+        let mut original = session(CONFLICT);
+        let hunk = original.hunks()[1].clone();
+        let resolution = Resolution::accept_right(&hunk);
+        original
+            .set_resolution(hunk.id, resolution.clone())
+            .unwrap();
+
+        let mut history = ActionHistory::new();
+        history.record(Action::SetResolution {
+            hunk_id: hunk.id,
+            old: None,
+            new: resolution.clone(),
+        });
+
+        let snapshot = original.snapshot(&history);
+        assert_eq!(snapshot.resolutions, vec![(hunk.id, resolution.clone())]);
+
+        let mut reopened = session(CONFLICT);
+        let restored_history = reopened.restore(snapshot).unwrap();
+        assert_eq!(reopened.resolutions().get(&hunk.id), Some(&resolution));
+        assert_eq!(restored_history.undo_count(), 1);
+    }
+
+    #[test]
+    fn restore_rejects_changed_input() {
+        let original = session(CONFLICT);
+        let snapshot = original.snapshot(&ActionHistory::new());
+
+        let mut changed = session(&CONFLICT.replace("uno", "eins"));
+        assert_eq!(changed.restore(snapshot), Err(SnapshotError::InputChanged));
+    }
+
+    #[test]
+    fn restore_rejects_unknown_hunks_without_changes() {
+        let mut current = session(CONFLICT);
+        let hunk = current.hunks()[0].clone();
+        let mut snapshot = current.snapshot(&ActionHistory::new());
+        snapshot.resolutions = vec![
+            (hunk.id, Resolution::accept_left(&hunk)),
+            (HunkId(7), Resolution::accept_left(&hunk)),
+        ];
+
+        assert_eq!(
+            current.restore(snapshot),
+            Err(SnapshotError::UnknownHunk(HunkId(7)))
+        );
+        assert!(current.resolutions().is_empty());
+    }
+}
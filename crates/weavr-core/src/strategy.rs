@@ -0,0 +1,416 @@
+//! Resolution strategies and the merge engine.
+//!
+//! A [`ResolutionStrategy`] proposes a candidate [`Resolution`] for a hunk;
+//! the [`MergeEngine`] runs a set of strategies in priority order and
+//! collects their proposals. Strategies never resolve a hunk themselves —
+//! frontends choose among the proposals.
+//!
+//! All types in this module are **stable** and covered by semantic versioning.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AcceptBothOptions, ConflictHunk, Resolution, ResolutionStrategyKind};
+
+/// Generates candidate resolutions for conflict hunks.
+pub trait ResolutionStrategy {
+    /// Proposes a resolution for the hunk, or `None` if the strategy does not apply.
+    fn propose(&self, hunk: &ConflictHunk) -> Option<Resolution>;
+
+    /// Describes the kind of resolution this strategy produces.
+    fn kind(&self) -> ResolutionStrategyKind;
+}
+
+/// Uses the left (ours) content verbatim.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AcceptLeftStrategy;
+
+impl ResolutionStrategy for AcceptLeftStrategy {
+    fn propose(&self, hunk: &ConflictHunk) -> Option<Resolution> {
+        Some(Resolution::accept_left(hunk))
+    }
+
+    fn kind(&self) -> ResolutionStrategyKind {
+        ResolutionStrategyKind::AcceptLeft
+    }
+}
+
+/// Uses the right (theirs) content verbatim.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AcceptRightStrategy;
+
+impl ResolutionStrategy for AcceptRightStrategy {
+    fn propose(&self, hunk: &ConflictHunk) -> Option<Resolution> {
+        Some(Resolution::accept_right(hunk))
+    }
+
+    fn kind(&self) -> ResolutionStrategyKind {
+        ResolutionStrategyKind::AcceptRight
+    }
+}
+
+/// Combines left and right content with configurable options.
+#[derive(Debug, Clone, Default)]
+pub struct AcceptBothStrategy {
+    /// How the two sides are combined.
+    pub options: AcceptBothOptions,
+}
+
+impl ResolutionStrategy for AcceptBothStrategy {
+    fn propose(&self, hunk: &ConflictHunk) -> Option<Resolution> {
+        Some(Resolution::accept_both(hunk, &self.options))
+    }
+
+    fn kind(&self) -> ResolutionStrategyKind {
+        ResolutionStrategyKind::AcceptBoth(self.options.clone())
+    }
+}
+
+/// Proposes a resolution only for trivially resolvable hunks.
+///
+/// See [`Resolution::trivial`] for the rules.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrivialStrategy;
+
+impl ResolutionStrategy for TrivialStrategy {
+    fn propose(&self, hunk: &ConflictHunk) -> Option<Resolution> {
+        Resolution::trivial(hunk)
+    }
+
+    fn kind(&self) -> ResolutionStrategyKind {
+        ResolutionStrategyKind::Trivial
+    }
+}
+
+/// Placeholder for user-provided content; never proposes anything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ManualStrategy;
+
+impl ResolutionStrategy for ManualStrategy {
+    fn propose(&self, _hunk: &ConflictHunk) -> Option<Resolution> {
+        None
+    }
+
+    fn kind(&self) -> ResolutionStrategyKind {
+        ResolutionStrategyKind::Manual
+    }
+}
+
+/// Priority tier of a strategy, derived from its [`ResolutionStrategyKind`].
+///
+/// The user's explicit choice sits above every tier: a resolved hunk is
+/// never replaced by proposals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StrategyTier {
+    /// Language-aware structural merges.
+    Ast,
+    /// AI-generated suggestions.
+    Ai,
+    /// Text-based strategies (trivial, accept left/right/both, manual, recorded).
+    Text,
+}
+
+impl StrategyTier {
+    /// The default priority order: AST, then AI, then text.
+    pub const DEFAULT_ORDER: [StrategyTier; 3] =
+        [StrategyTier::Ast, StrategyTier::Ai, StrategyTier::Text];
+
+    /// Returns the tier a resolution kind belongs to.
+    #[must_use]
+    pub fn of(kind: &ResolutionStrategyKind) -> Self {
+        match kind {
+            ResolutionStrategyKind::AstMerged { .. } => StrategyTier::Ast,
+            ResolutionStrategyKind::AiSuggested { .. } => StrategyTier::Ai,
+            ResolutionStrategyKind::AcceptLeft
+            | ResolutionStrategyKind::AcceptRight
+            | ResolutionStrategyKind::AcceptBoth(_)
+            | ResolutionStrategyKind::Manual
+            | ResolutionStrategyKind::Trivial
+            | ResolutionStrategyKind::Recorded => StrategyTier::Text,
+        }
+    }
+}
+
+/// Runs resolution strategies in priority order and collects proposals.
+///
+/// Strategies are grouped by [`StrategyTier`] according to the engine's
+/// priority order; within a tier they keep the order they were added in.
+///
+/// # Examples
+///
+/// ```
+/// use weavr_core::{AcceptLeftStrategy, AcceptRightStrategy, MergeEngine, StrategyTier};
+///
+/// let engine = MergeEngine::new()
+///     .with_strategy(AcceptLeftStrategy)
+///     .with_strategy(AcceptRightStrategy)
+///     .with_priority(&[StrategyTier::Text, StrategyTier::Ai]);
+///
+/// assert_eq!(engine.strategies.len(), 2);
+/// assert_eq!(engine.priority(), &[StrategyTier::Text, StrategyTier::Ai, StrategyTier::Ast]);
+/// ```
+pub struct MergeEngine {
+    /// Registered strategies, in insertion order.
+    pub strategies: Vec<Box<dyn ResolutionStrategy>>,
+    priority: Vec<StrategyTier>,
+}
+
+impl Default for MergeEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for MergeEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kinds: Vec<ResolutionStrategyKind> = self.strategies.iter().map(|s| s.kind()).collect();
+        f.debug_struct("MergeEngine")
+            .field("strategies", &kinds)
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
+impl MergeEngine {
+    /// Creates an engine with no strategies and the default priority order.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            strategies: Vec::new(),
+            priority: StrategyTier::DEFAULT_ORDER.to_vec(),
+        }
+    }
+
+    /// Creates an engine with the built-in text strategies:
+    /// accept left, accept right, and accept both with the given options.
+    #[must_use]
+    pub fn with_text_strategies(options: AcceptBothOptions) -> Self {
+        Self::new()
+            .with_strategy(AcceptLeftStrategy)
+            .with_strategy(AcceptRightStrategy)
+            .with_strategy(AcceptBothStrategy { options })
+    }
+
+    /// Adds a strategy to the engine.
+    #[must_use]
+    pub fn with_strategy(mut self, strategy: impl ResolutionStrategy + 'static) -> Self {
+        self.strategies.push(Box::new(strategy));
+        self
+    }
+
+    /// Sets the priority order of strategy tiers.
+    ///
+    /// Duplicates are ignored; tiers not listed keep their default relative
+    /// order after the listed ones.
+    #[must_use]
+    pub fn with_priority(mut self, order: &[StrategyTier]) -> Self {
+        let mut priority: Vec<StrategyTier> = Vec::new();
+        for tier in order.iter().chain(&StrategyTier::DEFAULT_ORDER) {
+            if !priority.contains(tier) {
+                priority.push(*tier);
+            }
+        }
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the tier priority order, highest first.
+    #[must_use]
+    pub fn priority(&self) -> &[StrategyTier] {
+        &self.priority
+    }
+
+    /// Collects proposals for a hunk from all strategies, highest priority first.
+    ///
+    /// Proposals whose content duplicates a higher-priority proposal are dropped.
+    #[must_use]
+    pub fn propose(&self, hunk: &ConflictHunk) -> Vec<Resolution> {
+        let mut proposals: Vec<Resolution> = Vec::new();
+        for tier in &self.priority {
+            for strategy in &self.strategies {
+                if StrategyTier::of(&strategy.kind()) != *tier {
+                    continue;
+                }
+                if let Some(resolution) = strategy.propose(hunk) {
+                    if !proposals.iter().any(|p| p.content == resolution.content) {
+                        proposals.push(resolution);
+                    }
+                }
+            }
+        }
+        proposals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BothOrder, HunkContent, HunkContext, HunkId, HunkState, MarkerLabels, ResolutionMetadata,
+        ResolutionSource,
+    };
+
+    fn test_hunk(left: &str, right: &str) -> ConflictHunk {
+        ConflictHunk {
+            id: HunkId(0),
+            left: HunkContent {
+                text: left.to_string(),
+            },
+            right: HunkContent {
+                text: right.to_string(),
+            },
+            base: None,
+            context: HunkContext::default(),
+            state: HunkState::Unresolved,
+            labels: MarkerLabels::default(),
+        }
+    }
+
+    /// A fake AI strategy that always proposes fixed content.
+    struct FixedAiStrategy(&'static str);
+
+    impl ResolutionStrategy for FixedAiStrategy {
+        fn propose(&self, _hunk: &ConflictHunk) -> Option<Resolution> {
+            Some(Resolution {
+                kind: self.kind(),
+                content: self.0.to_string(),
+                metadata: ResolutionMetadata {
+                    source: ResolutionSource::Ai,
+                    ..ResolutionMetadata::default()
+                },
+            })
+        }
+
+        fn kind(&self) -> ResolutionStrategyKind {
+            ResolutionStrategyKind::AiSuggested {
+                provider: "fake".to_string(),
+            }
+        }
+    }
+
+    #[test]
+    fn builtin_strategies_propose_expected_content() {
+        let hunk = test_hunk("left", "right");
+
+        assert_eq!(AcceptLeftStrategy.propose(&hunk).unwrap().content, "left");
+        assert_eq!(AcceptRightStrategy.propose(&hunk).unwrap().content, "right");
+        assert_eq!(
+            AcceptBothStrategy::default()
+                .propose(&hunk)
+                .unwrap()
+                .content,
+            "left\nright"
+        );
+        assert!(ManualStrategy.propose(&hunk).is_none());
+        assert!(TrivialStrategy.propose(&hunk).is_none());
+        assert_eq!(
+            TrivialStrategy
+                .propose(&test_hunk("same", "same"))
+                .unwrap()
+                .content,
+            "same"
+        );
+    }
+
+    #[test]
+    fn builtin_strategies_report_kind() {
+        let options = AcceptBothOptions {
+            order: BothOrder::RightThenLeft,
+            deduplicate: true,
+            trim_whitespace: false,
+        };
+        assert_eq!(
+            AcceptLeftStrategy.kind(),
+            ResolutionStrategyKind::AcceptLeft
+        );
+        assert_eq!(
+            AcceptRightStrategy.kind(),
+            ResolutionStrategyKind::AcceptRight
+        );
+        assert_eq!(
+            AcceptBothStrategy {
+                options: options.clone()
+            }
+            .kind(),
+            ResolutionStrategyKind::AcceptBoth(options)
+        );
+        assert_eq!(ManualStrategy.kind(), ResolutionStrategyKind::Manual);
+    }
+
+    #[test]
+    fn tier_of_kind() {
+        assert_eq!(
+            StrategyTier::of(&ResolutionStrategyKind::AcceptLeft),
+            StrategyTier::Text
+        );
+        assert_eq!(
+            StrategyTier::of(&ResolutionStrategyKind::AstMerged {
+                language: "rust".to_string()
+            }),
+            StrategyTier::Ast
+        );
+        assert_eq!(
+            StrategyTier::of(&ResolutionStrategyKind::AiSuggested {
+                provider: "claude".to_string()
+            }),
+            StrategyTier::Ai
+        );
+    }
+
+    #[test]
+    fn engine_default_priority() {
+        let engine = MergeEngine::new();
+        assert!(engine.strategies.is_empty());
+        assert_eq!(engine.priority(), &StrategyTier::DEFAULT_ORDER);
+    }
+
+    #[test]
+    fn engine_proposes_in_insertion_order_within_tier() {
+        let engine = MergeEngine::with_text_strategies(AcceptBothOptions::default());
+        let proposals = engine.propose(&test_hunk("left", "right"));
+
+        let contents: Vec<&str> = proposals.iter().map(|p| p.content.as_str()).collect();
+        assert_eq!(contents, vec!["left", "right", "left\nright"]);
+    }
+
+    #[test]
+    fn engine_orders_by_tier_priority() {
+        let engine = MergeEngine::new()
+            .with_strategy(AcceptLeftStrategy)
+            .with_strategy(FixedAiStrategy("ai"));
+
+        let proposals = engine.propose(&test_hunk("left", "right"));
+        assert_eq!(proposals[0].content, "ai");
+        assert_eq!(proposals[1].content, "left");
+
+        let engine = engine.with_priority(&[StrategyTier::Text]);
+        let proposals = engine.propose(&test_hunk("left", "right"));
+        assert_eq!(proposals[0].content, "left");
+        assert_eq!(proposals[1].content, "ai");
+    }
+
+    #[test]
+    fn engine_drops_duplicate_content() {
+        let engine = MergeEngine::new()
+            .with_strategy(FixedAiStrategy("same"))
+            .with_strategy(AcceptLeftStrategy)
+            .with_strategy(AcceptRightStrategy);
+
+        let proposals = engine.propose(&test_hunk("same", "same"));
+        assert_eq!(proposals.len(), 1);
+        assert!(matches!(
+            proposals[0].kind,
+            ResolutionStrategyKind::AiSuggested { .. }
+        ));
+    }
+
+    #[test]
+    fn with_priority_ignores_duplicates_and_fills_missing() {
+        let engine = MergeEngine::new().with_priority(&[StrategyTier::Ai, StrategyTier::Ai]);
+        assert_eq!(
+            engine.priority(),
+            &[StrategyTier::Ai, StrategyTier::Ast, StrategyTier::Text]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Writing Git configuration entries.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::GitError;
+
+/// Which Git configuration file to write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigScope {
+    /// The repository's `.git/config`.
+    Local,
+    /// The user's `~/.gitconfig`.
+    Global,
+}
+
+impl ConfigScope {
+    /// Returns the `git config` flag for this scope.
+    fn flag(self) -> &'static str {
+        match self {
+            ConfigScope::Local => "--local",
+            ConfigScope::Global => "--global",
+        }
+    }
+}
+
+/// Sets a Git configuration entry (`git config --local|--global <key> <value>`).
+///
+/// Git runs in `dir`, which must be inside a repository for
+/// [`ConfigScope::Local`].
+///
+/// # Errors
+///
+/// Returns `GitError::CommandFailed` if git cannot be run, or
+/// `GitError::CommandError` if it rejects the entry.
+pub fn set_config(dir: &Path, scope: ConfigScope, key: &str, value: &str) -> Result<(), GitError> {
+    let output = Command::new("git")
+        .args(["config", scope.flag(), key, value])
+        .current_dir(dir)
+        .output()
+        .map_err(GitError::CommandFailed)?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(GitError::CommandError {
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
@@ -4,6 +4,9 @@ use std::path::PathBuf;
 
 use thiserror::Error;
 
+use crate::stages::IndexStage;
+use crate::state::GitOperation;
+
 /// Git operation errors.
 #[derive(Debug, Error)]
 pub enum GitError {
@@ -30,6 +33,45 @@ pub enum GitError {
     #[error("failed to parse git output: {0}")]
     ParseError(String),
 
+    /// The path has no unmerged entries in the index.
+    #[error("{0} is not unmerged in the index")]
+    NotUnmerged(PathBuf),
+
+    /// A required index stage is missing (e.g. the file was deleted on one side).
+    #[error("{path} has no {stage} version in the index")]
+    MissingStage {
+        /// The unmerged path.
+        path: PathBuf,
+        /// The stage that is missing.
+        stage: IndexStage,
+    },
+
+    /// An index stage is not valid UTF-8 text.
+    #[error("{stage} version of {path} is not valid UTF-8")]
+    NonUtf8Content {
+        /// The unmerged path.
+        path: PathBuf,
+        /// The stage that could not be decoded.
+        stage: IndexStage,
+    },
+
+    /// No merge, rebase, cherry-pick or revert is in progress.
+    #[error("no merge, rebase, cherry-pick or revert in progress")]
+    NoOperation,
+
+    /// The operation does not support the requested action.
+    #[error("git {operation} has no {action}")]
+    UnsupportedAction {
+        /// The operation in progress.
+        operation: GitOperation,
+        /// The action that was requested, e.g. `--skip`.
+        action: &'static str,
+    },
+
+    /// The operation cannot continue while paths are still unmerged.
+    #[error("cannot continue: {0} path(s) still unmerged")]
+    StillUnmerged(usize),
+
     /// File operation failed.
     #[error("file operation failed on {path}: {source}")]
     FileError {
@@ -0,0 +1,96 @@
+//! File-level conflicts (delete/modify, both-deleted).
+//!
+//! These conflicts are about whether a file exists, not about its content,
+//! so they cannot be represented as hunks. They are resolved by keeping one
+//! side's version of the whole file or by deleting it.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::porcelain::ConflictType;
+
+/// How to resolve a file-level conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileResolution {
+    /// Keep our version of the file (deletes it if we deleted it).
+    KeepOurs,
+    /// Keep their version of the file (deletes it if they deleted it).
+    KeepTheirs,
+    /// Delete the file.
+    Delete,
+}
+
+impl fmt::Display for FileResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FileResolution::KeepOurs => "keep ours",
+            FileResolution::KeepTheirs => "keep theirs",
+            FileResolution::Delete => "delete",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A conflict over a whole file, read from the index stages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileConflict {
+    /// The path, relative to the repository root.
+    pub path: PathBuf,
+    /// The type of conflict.
+    pub conflict_type: ConflictType,
+    /// Our version of the file, if we kept it.
+    pub ours: Option<String>,
+    /// Their version of the file, if they kept it.
+    pub theirs: Option<String>,
+}
+
+impl FileConflict {
+    /// Returns the file content a resolution leaves, or `None` if it deletes the file.
+    #[must_use]
+    pub fn content_for(&self, resolution: FileResolution) -> Option<&str> {
+        match resolution {
+            FileResolution::KeepOurs => self.ours.as_deref(),
+            FileResolution::KeepTheirs => self.theirs.as_deref(),
+            FileResolution::Delete => None,
+        }
+    }
+
+    /// Returns true if the resolution deletes the file.
+    #[must_use]
+    pub fn deletes(&self, resolution: FileResolution) -> bool {
+        self.content_for(resolution).is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modify_delete() -> FileConflict {
+        FileConflict {
+            path: PathBuf::from("file.txt"),
+            conflict_type: ConflictType::AddedByUsDeletedByThem,
+            ours: Some("ours\n".into()),
+            theirs: None,
+        }
+    }
+
+    #[test]
+    fn content_for_each_resolution() {
+        let conflict = modify_delete();
+        assert_eq!(
+            conflict.content_for(FileResolution::KeepOurs),
+            Some("ours\n")
+        );
+        assert_eq!(conflict.content_for(FileResolution::KeepTheirs), None);
+        assert!(conflict.deletes(FileResolution::KeepTheirs));
+        assert!(conflict.deletes(FileResolution::Delete));
+        assert!(!conflict.deletes(FileResolution::KeepOurs));
+    }
+
+    #[test]
+    fn resolution_display() {
+        assert_eq!(FileResolution::KeepOurs.to_string(), "keep ours");
+        assert_eq!(FileResolution::Delete.to_string(), "delete");
+    }
+}
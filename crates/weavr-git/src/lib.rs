@@ -3,8 +3,14 @@
 //! This crate provides Git repository operations for weavr:
 //! - Discovering repositories from any subdirectory
 //! - Detecting conflicted files during merge/rebase/cherry-pick
+//! - Reading base/ours/theirs versions from the index stages
+//! - Resolving delete/modify conflicts by keeping a side or deleting the file
 //! - Staging resolved files
+//! - Writing Git configuration (e.g. registering weavr as a merge tool)
 //! - Detecting the current Git operation state
+//! - Continuing, skipping or aborting the operation in progress
+//! - Naming conflict sides by meaning (e.g. "upstream" during a rebase)
+//! - Reading resolutions recorded by `git rerere`
 //!
 //! # Example
 //!
@@ -24,12 +30,22 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+mod config;
 mod error;
+mod file_conflict;
 mod porcelain;
 mod repo;
+mod rerere;
+mod sides;
+mod stages;
 mod state;
 
+pub use config::{set_config, ConfigScope};
 pub use error::GitError;
+pub use file_conflict::{FileConflict, FileResolution};
 pub use porcelain::{ConflictEntry, ConflictType};
 pub use repo::GitRepo;
-pub use state::GitOperation;
+pub use rerere::RerereCache;
+pub use sides::{SideLabel, SideNames};
+pub use stages::{IndexStage, StageEntry};
+pub use state::{GitOperation, OperationStatus};
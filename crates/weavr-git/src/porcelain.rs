@@ -17,6 +17,29 @@ pub enum ConflictType {
     AddedByThemDeletedByUs,
 }
 
+impl ConflictType {
+    /// Returns a short human-readable label, e.g. `deleted by them`.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            ConflictType::BothModified => "both modified",
+            ConflictType::BothAdded => "both added",
+            ConflictType::BothDeleted => "both deleted",
+            ConflictType::AddedByUsDeletedByThem => "deleted by them",
+            ConflictType::AddedByThemDeletedByUs => "deleted by us",
+        }
+    }
+
+    /// Returns true if both sides have the file, so the conflict is in its content.
+    ///
+    /// Other conflict types are resolved as a whole file with a
+    /// [`FileResolution`](crate::FileResolution).
+    #[must_use]
+    pub fn is_content_conflict(self) -> bool {
+        matches!(self, ConflictType::BothModified | ConflictType::BothAdded)
+    }
+}
+
 /// A conflicted file entry from porcelain output.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ConflictEntry {
@@ -140,6 +163,27 @@ fn unquote_path(s: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn conflict_type_labels() {
+        assert_eq!(ConflictType::BothDeleted.label(), "both deleted");
+        assert_eq!(
+            ConflictType::AddedByUsDeletedByThem.label(),
+            "deleted by them"
+        );
+        assert_eq!(
+            ConflictType::AddedByThemDeletedByUs.label(),
+            "deleted by us"
+        );
+    }
+
+    #[test]
+    fn content_conflict_types() {
+        assert!(ConflictType::BothModified.is_content_conflict());
+        assert!(ConflictType::BothAdded.is_content_conflict());
+        assert!(!ConflictType::BothDeleted.is_content_conflict());
+        assert!(!ConflictType::AddedByThemDeletedByUs.is_content_conflict());
+    }
+
     #[test]
     fn parse_empty_output() {
         let entries = parse_porcelain_v1("");
@@ -3,9 +3,15 @@
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use weavr_core::{FileVersion, MergeInput};
+
 use crate::error::GitError;
-use crate::porcelain::{parse_porcelain_v1, ConflictEntry};
-use crate::state::GitOperation;
+use crate::file_conflict::{FileConflict, FileResolution};
+use crate::porcelain::{parse_porcelain_v1, ConflictEntry, ConflictType};
+use crate::rerere::RerereCache;
+use crate::sides::{subject_line, SideNames};
+use crate::stages::{parse_ls_files_unmerged, IndexStage, StageEntry};
+use crate::state::{GitOperation, OperationStatus};
 
 /// A handle to a Git repository.
 #[derive(Debug, Clone)]
@@ -96,6 +102,12 @@ impl GitRepo {
         &self.git_dir
     }
 
+    /// Returns the repository's `git rerere` cache.
+    #[must_use]
+    pub fn rerere_cache(&self) -> RerereCache {
+        RerereCache::for_git_dir(&self.git_dir)
+    }
+
     /// Returns a list of files with merge conflicts.
     ///
     /// Uses `git status --porcelain=v1` to detect unmerged paths.
@@ -120,6 +132,155 @@ impl GitRepo {
         Ok(parse_porcelain_v1(&output))
     }
 
+    /// Returns the unmerged index entries (stages 1-3) for a path.
+    ///
+    /// The path is relative to the repository root. The result is empty if
+    /// the path is not unmerged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GitError::CommandFailed` if the git command fails to execute.
+    /// Returns `GitError::CommandError` if git returns a non-zero exit status.
+    pub fn unmerged_stages(&self, path: &Path) -> Result<Vec<StageEntry>, GitError> {
+        let output = self.run_git(&["ls-files", "-u", "-z", "--", &path.to_string_lossy()])?;
+        Ok(parse_ls_files_unmerged(&output))
+    }
+
+    /// Reads the content of one index stage of an unmerged path.
+    ///
+    /// Returns `Ok(None)` if the stage does not exist, e.g. there is no base
+    /// for an add/add conflict, or one side deleted the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GitError::NonUtf8Content` if the blob is not valid UTF-8.
+    /// Returns `GitError::CommandFailed` or `GitError::CommandError` if git fails.
+    pub fn read_stage(&self, path: &Path, stage: IndexStage) -> Result<Option<String>, GitError> {
+        let entries = self.unmerged_stages(path)?;
+        self.read_stage_entry(path, &entries, stage)
+    }
+
+    /// Builds a [`MergeInput`] from the index stages of an unmerged path.
+    ///
+    /// Stage 2 becomes the left (ours) version, stage 3 the right (theirs)
+    /// version, and stage 1 the base when present. Unlike the working copy,
+    /// this gives full file versions and a real base regardless of
+    /// `merge.conflictStyle`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GitError::NotUnmerged` if the path has no unmerged entries.
+    /// Returns `GitError::MissingStage` if ours or theirs is missing (a delete conflict).
+    /// Returns `GitError::NonUtf8Content` if a stage is not valid UTF-8.
+    /// Returns `GitError::CommandFailed` or `GitError::CommandError` if git fails.
+    pub fn merge_input(&self, path: &Path) -> Result<MergeInput, GitError> {
+        let entries = self.unmerged_stages(path)?;
+        if entries.is_empty() {
+            return Err(GitError::NotUnmerged(path.to_path_buf()));
+        }
+
+        let version = |stage| -> Result<Option<FileVersion>, GitError> {
+            Ok(self
+                .read_stage_entry(path, &entries, stage)?
+                .map(|content| FileVersion {
+                    path: path.to_path_buf(),
+                    content,
+                }))
+        };
+        let required = |stage| {
+            version(stage)?.ok_or_else(|| GitError::MissingStage {
+                path: path.to_path_buf(),
+                stage,
+            })
+        };
+
+        Ok(MergeInput {
+            left: required(IndexStage::Ours)?,
+            right: required(IndexStage::Theirs)?,
+            base: version(IndexStage::Base)?,
+        })
+    }
+
+    /// Reads a file-level conflict (delete/modify or both-deleted) from the index.
+    ///
+    /// The conflict type is derived from which of stages 2 and 3 exist.
+    /// Stage content that is not valid UTF-8 is decoded lossily, since it is
+    /// only used for display.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GitError::NotUnmerged` if the path has no unmerged entries.
+    /// Returns `GitError::CommandFailed` or `GitError::CommandError` if git fails.
+    pub fn file_conflict(&self, path: &Path) -> Result<FileConflict, GitError> {
+        let entries = self.unmerged_stages(path)?;
+        if entries.is_empty() {
+            return Err(GitError::NotUnmerged(path.to_path_buf()));
+        }
+
+        let read = |stage| -> Result<Option<String>, GitError> {
+            let Some(entry) = entries.iter().find(|e| e.stage == stage) else {
+                return Ok(None);
+            };
+            let bytes = self.run_git_bytes(&["cat-file", "blob", &entry.object])?;
+            Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+        };
+        let ours = read(IndexStage::Ours)?;
+        let theirs = read(IndexStage::Theirs)?;
+
+        let conflict_type = match (&ours, &theirs) {
+            (Some(_), Some(_)) if entries.iter().any(|e| e.stage == IndexStage::Base) => {
+                ConflictType::BothModified
+            }
+            (Some(_), Some(_)) => ConflictType::BothAdded,
+            (Some(_), None) => ConflictType::AddedByUsDeletedByThem,
+            (None, Some(_)) => ConflictType::AddedByThemDeletedByUs,
+            (None, None) => ConflictType::BothDeleted,
+        };
+
+        Ok(FileConflict {
+            path: path.to_path_buf(),
+            conflict_type,
+            ours,
+            theirs,
+        })
+    }
+
+    /// Resolves an unmerged path as a whole file.
+    ///
+    /// Keeping a side that has the file checks out that version
+    /// (`git checkout --ours`/`--theirs`) and stages it with `git add`.
+    /// Keeping a side that deleted the file, or [`FileResolution::Delete`],
+    /// removes it from the index and working tree with `git rm`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GitError::NotUnmerged` if the path has no unmerged entries.
+    /// Returns `GitError::CommandFailed` or `GitError::CommandError` if git fails.
+    pub fn resolve_file(&self, path: &Path, resolution: FileResolution) -> Result<(), GitError> {
+        let entries = self.unmerged_stages(path)?;
+        if entries.is_empty() {
+            return Err(GitError::NotUnmerged(path.to_path_buf()));
+        }
+
+        let path_arg = path.to_string_lossy();
+        let (stage, flag) = match resolution {
+            FileResolution::KeepOurs => (IndexStage::Ours, "--ours"),
+            FileResolution::KeepTheirs => (IndexStage::Theirs, "--theirs"),
+            FileResolution::Delete => {
+                self.run_git(&["rm", "-q", "--", &path_arg])?;
+                return Ok(());
+            }
+        };
+
+        if entries.iter().any(|e| e.stage == stage) {
+            self.run_git(&["checkout", flag, "--", &path_arg])?;
+            self.run_git(&["add", "--", &path_arg])?;
+        } else {
+            self.run_git(&["rm", "-q", "--", &path_arg])?;
+        }
+        Ok(())
+    }
+
     /// Stages a resolved file.
     ///
     /// # Errors
@@ -171,9 +332,157 @@ impl GitRepo {
         }
     }
 
+    /// Continues the operation in progress (`git <operation> --continue`).
+    ///
+    /// Git's editor is bypassed, so commit messages are kept as prepared.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GitError::NoOperation` if nothing is in progress,
+    /// `GitError::StillUnmerged` if conflicted paths remain, and
+    /// `GitError::CommandError` if git fails without stopping on conflicts.
+    pub fn continue_operation(&self) -> Result<OperationStatus, GitError> {
+        let unmerged = self.conflicted_entries()?.len();
+        if unmerged > 0 {
+            return Err(GitError::StillUnmerged(unmerged));
+        }
+        self.step_operation("--continue")
+    }
+
+    /// Skips the current commit of a rebase, cherry-pick or revert
+    /// (`git <operation> --skip`), discarding its changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GitError::NoOperation` if nothing is in progress,
+    /// `GitError::UnsupportedAction` for a merge, and
+    /// `GitError::CommandError` if git fails without stopping on conflicts.
+    pub fn skip_operation(&self) -> Result<OperationStatus, GitError> {
+        let operation = self.current_operation();
+        if operation.has_conflicts() && !operation.can_skip() {
+            return Err(GitError::UnsupportedAction {
+                operation,
+                action: "--skip",
+            });
+        }
+        self.step_operation("--skip")
+    }
+
+    /// Aborts the operation in progress (`git <operation> --abort`),
+    /// restoring the state from before it started.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GitError::NoOperation` if nothing is in progress, or
+    /// `GitError::CommandError` if git fails.
+    pub fn abort_operation(&self) -> Result<(), GitError> {
+        let command = self
+            .current_operation()
+            .command()
+            .ok_or(GitError::NoOperation)?;
+        self.run_git(&[command, "--abort"])?;
+        Ok(())
+    }
+
+    /// Returns what "ours" and "theirs" stand for in the current operation.
+    ///
+    /// Commit subjects are filled in where git records them: `HEAD` for
+    /// ours, and `MERGE_HEAD`, `CHERRY_PICK_HEAD`, `REVERT_HEAD` or the
+    /// rebase state (`rebase-merge/message`, then `REBASE_HEAD`) for theirs.
+    #[must_use]
+    pub fn side_names(&self) -> SideNames {
+        let operation = self.current_operation();
+        let mut names = SideNames::for_operation(operation);
+        if operation == GitOperation::None {
+            return names;
+        }
+
+        names.ours.subject = self.commit_subject("HEAD");
+        names.theirs.subject = match operation {
+            GitOperation::Merge => self.commit_subject("MERGE_HEAD"),
+            GitOperation::Rebase => {
+                std::fs::read_to_string(self.git_dir.join("rebase-merge").join("message"))
+                    .ok()
+                    .and_then(|message| subject_line(&message))
+                    .or_else(|| self.commit_subject("REBASE_HEAD"))
+            }
+            GitOperation::CherryPick => self.commit_subject("CHERRY_PICK_HEAD"),
+            GitOperation::Revert => self.commit_subject("REVERT_HEAD"),
+            GitOperation::None => None,
+        };
+        names
+    }
+
+    /// Runs `git <operation> <flag>` and reports where the operation stands.
+    ///
+    /// A failing command is expected when the next step conflicts, so the
+    /// exit status only matters if the operation neither finished nor
+    /// stopped on conflicts.
+    fn step_operation(&self, flag: &str) -> Result<OperationStatus, GitError> {
+        let command = self
+            .current_operation()
+            .command()
+            .ok_or(GitError::NoOperation)?;
+        let output = Command::new("git")
+            .args([command, flag])
+            .env("GIT_EDITOR", "true")
+            .current_dir(&self.root)
+            .output()
+            .map_err(GitError::CommandFailed)?;
+
+        if !self.current_operation().has_conflicts() {
+            Ok(OperationStatus::Finished)
+        } else if !self.conflicted_entries()?.is_empty() {
+            Ok(OperationStatus::Conflicted)
+        } else if output.status.success() {
+            Ok(OperationStatus::Stopped)
+        } else {
+            Err(GitError::CommandError {
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            })
+        }
+    }
+
+    /// Returns the subject line of a commit, or `None` if it cannot be read.
+    fn commit_subject(&self, rev: &str) -> Option<String> {
+        self.run_git(&["log", "-1", "--format=%s", rev, "--"])
+            .ok()
+            .and_then(|output| subject_line(&output))
+    }
+
+    /// Reads the blob for `stage` from already-listed entries.
+    fn read_stage_entry(
+        &self,
+        path: &Path,
+        entries: &[StageEntry],
+        stage: IndexStage,
+    ) -> Result<Option<String>, GitError> {
+        let Some(entry) = entries.iter().find(|e| e.stage == stage) else {
+            return Ok(None);
+        };
+
+        let bytes = self.run_git_bytes(&["cat-file", "blob", &entry.object])?;
+        String::from_utf8(bytes)
+            .map(Some)
+            .map_err(|_| GitError::NonUtf8Content {
+                path: path.to_path_buf(),
+                stage,
+            })
+    }
+
     /// Runs a git command and returns stdout as a string.
     fn run_git(&self, args: &[&str]) -> Result<String, GitError> {
+        let stdout = self.run_git_bytes(args)?;
+        Ok(String::from_utf8_lossy(&stdout).into_owned())
+    }
+
+    /// Runs a git command and returns raw stdout.
+    ///
+    /// Pathspecs are taken literally, so file names like `[id].tsx` are not
+    /// read as globs.
+    fn run_git_bytes(&self, args: &[&str]) -> Result<Vec<u8>, GitError> {
         let output = Command::new("git")
+            .arg("--literal-pathspecs")
             .args(args)
             .current_dir(&self.root)
             .output()
@@ -185,6 +494,6 @@ impl GitRepo {
             });
         }
 
-        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        Ok(output.stdout)
     }
 }
@@ -0,0 +1,237 @@
+//! Reading resolutions recorded by `git rerere`.
+//!
+//! With `rerere.enabled`, git keeps each conflicted file it has seen in
+//! `<git-dir>/rr-cache/<id>/preimage` (markers normalized, sides sorted,
+//! base dropped) and the file as the user resolved it in `postimage`.
+//! Further variants of the same conflict are kept as `preimage.N` and
+//! `postimage.N`. [`RerereCache`] splits each pair into one
+//! [`RecordedResolution`] per conflict, so the history can be replayed
+//! like weavr's own recorded resolutions.
+
+use std::path::{Path, PathBuf};
+
+use weavr_core::{parse_conflict_markers, ParsedConflict, RecordedResolution, Segment};
+
+use crate::error::GitError;
+
+/// The `git rerere` cache of a repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RerereCache {
+    dir: PathBuf,
+}
+
+impl RerereCache {
+    /// Creates a reader for the rerere cache in `dir`.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Creates the reader for a repository, in `<git-dir>/rr-cache`.
+    #[must_use]
+    pub fn for_git_dir(git_dir: &Path) -> Self {
+        Self::new(git_dir.join("rr-cache"))
+    }
+
+    /// Returns the cache directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns one recorded resolution per conflict resolved in the cache.
+    ///
+    /// A missing cache gives an empty list. Entries without a postimage
+    /// (conflicts git saw but that were never resolved), unreadable entries,
+    /// and postimages that cannot be lined up with their preimage are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::FileError`] if the cache directory exists but
+    /// cannot be read.
+    pub fn resolutions(&self) -> Result<Vec<RecordedResolution>, GitError> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => {
+                return Err(GitError::FileError {
+                    path: self.dir.clone(),
+                    source,
+                })
+            }
+        };
+
+        let mut dirs: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        dirs.sort();
+
+        Ok(dirs.iter().flat_map(|dir| read_entry(dir)).collect())
+    }
+}
+
+/// Reads every preimage/postimage pair in one cache entry.
+fn read_entry(dir: &Path) -> Vec<RecordedResolution> {
+    let Ok(files) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut suffixes: Vec<String> = files
+        .filter_map(Result::ok)
+        .filter_map(|file| {
+            let name = file.file_name().to_str()?.to_string();
+            name.strip_prefix("preimage").map(str::to_string)
+        })
+        .collect();
+    suffixes.sort();
+
+    let mut resolutions = Vec::new();
+    for suffix in suffixes {
+        let read = |name: &str| std::fs::read_to_string(dir.join(format!("{name}{suffix}"))).ok();
+        let (Some(preimage), Some(postimage)) = (read("preimage"), read("postimage")) else {
+            continue;
+        };
+        let Ok(parsed) = parse_conflict_markers(&preimage) else {
+            continue;
+        };
+        let Some(contents) = split_postimage(&parsed, &postimage) else {
+            continue;
+        };
+        resolutions.extend(
+            parsed
+                .hunks
+                .iter()
+                .zip(contents)
+                .map(|(hunk, content)| RecordedResolution::new(hunk, content, dir.to_path_buf())),
+        );
+    }
+    resolutions
+}
+
+/// Splits a postimage into the resolved content of each preimage hunk.
+///
+/// The clean text around the conflicts is found in the postimage in order;
+/// whatever lies between is the resolution. Returns `None` if the clean
+/// text cannot be found, or two conflicts are adjacent so the split would
+/// be ambiguous.
+fn split_postimage(parsed: &ParsedConflict, postimage: &str) -> Option<Vec<String>> {
+    let post: Vec<&str> = postimage.lines().collect();
+    let mut contents: Vec<Option<String>> = vec![None; parsed.hunks.len()];
+    let mut pos = 0;
+    let mut pending: Option<usize> = None;
+
+    for segment in &parsed.segments {
+        match segment {
+            Segment::Clean(text) => {
+                let lines: Vec<&str> = text.lines().collect();
+                if lines.is_empty() {
+                    continue;
+                }
+                let start = match pending.take() {
+                    Some(hunk) => {
+                        let start = find_lines(&post, &lines, pos)?;
+                        contents[hunk] = Some(post[pos..start].join("\n"));
+                        start
+                    }
+                    None if post[pos..].starts_with(&lines) => pos,
+                    None => return None,
+                };
+                pos = start + lines.len();
+            }
+            Segment::Conflict(hunk) => {
+                if pending.replace(*hunk).is_some() {
+                    return None;
+                }
+            }
+        }
+    }
+
+    match pending {
+        Some(hunk) => contents[hunk] = Some(post[pos..].join("\n")),
+        None if pos != post.len() => return None,
+        None => {}
+    }
+    contents.into_iter().collect()
+}
+
+/// Returns the first index at or after `from` where `needle` starts.
+fn find_lines(haystack: &[&str], needle: &[&str], from: usize) -> Option<usize> {
+    (from..=haystack.len().checked_sub(needle.len())?)
+        .find(|&start| haystack[start..].starts_with(needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PREIMAGE: &str = "fn a() {}\n<<<<<<<\nleft one\n=======\nright one\n>>>>>>>\nfn b() {}\n<<<<<<<\nleft two\n=======\nright two\n>>>>>>>\nfn c() {}\n";
+
+    #[test]
+    fn splits_postimage_between_clean_text() {
+        let parsed = parse_conflict_markers(PREIMAGE).unwrap();
+        let postimage = "fn a() {}\nmerged one\nextra\nfn b() {}\nright two\nfn c() {}\n";
+
+        assert_eq!(
+            split_postimage(&parsed, postimage).unwrap(),
+            vec!["merged one\nextra".to_string(), "right two".to_string()]
+        );
+    }
+
+    #[test]
+    fn conflict_at_either_end_takes_the_rest() {
+        let parsed = parse_conflict_markers(
+            "<<<<<<<\nl\n=======\nr\n>>>>>>>\nmiddle\n<<<<<<<\nx\n=======\ny\n>>>>>>>",
+        )
+        .unwrap();
+        assert_eq!(
+            split_postimage(&parsed, "l\nr\nmiddle\n").unwrap(),
+            vec!["l\nr".to_string(), String::new()]
+        );
+    }
+
+    #[test]
+    fn mismatched_postimage_is_skipped() {
+        let parsed = parse_conflict_markers(PREIMAGE).unwrap();
+        assert!(split_postimage(&parsed, "something else entirely\n").is_none());
+        assert!(split_postimage(&parsed, "fn a() {}\nmerged\nfn c() {}\n").is_none());
+    }
+
+    #[test]
+    fn reads_resolved_entries_and_variants() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = dir.path().join("0123abcd");
+        std::fs::create_dir(&entry).unwrap();
+        std::fs::write(entry.join("preimage"), PREIMAGE).unwrap();
+        std::fs::write(
+            entry.join("postimage"),
+            "fn a() {}\nleft one\nfn b() {}\nleft two\nright two\nfn c() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            entry.join("preimage.1"),
+            "a\n<<<<<<<\nold\n=======\nnew\n>>>>>>>\nb\n",
+        )
+        .unwrap();
+        std::fs::write(entry.join("postimage.1"), "a\nnew\nb\n").unwrap();
+
+        // Seen but never resolved
+        let unresolved = dir.path().join("4567ef");
+        std::fs::create_dir(&unresolved).unwrap();
+        std::fs::write(unresolved.join("preimage"), PREIMAGE).unwrap();
+
+        let resolutions = RerereCache::new(dir.path()).resolutions().unwrap();
+        let contents: Vec<&str> = resolutions.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, vec!["left one", "left two\nright two", "new"]);
+        assert_eq!(resolutions[0].path, entry);
+        assert_eq!(resolutions[2].left, "old");
+    }
+
+    #[test]
+    fn missing_cache_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RerereCache::for_git_dir(dir.path());
+        assert!(cache.resolutions().unwrap().is_empty());
+    }
+}
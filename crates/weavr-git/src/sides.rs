@@ -0,0 +1,119 @@
+//! What "ours" and "theirs" mean in the current operation.
+//!
+//! Git's side names follow the mechanics of each operation, not the user's
+//! intent. During a rebase, "ours" is the upstream being rebased onto and
+//! "theirs" is the user's own commit being replayed. [`SideNames`] names
+//! each side by what it stands for.
+
+use std::fmt;
+
+use crate::state::GitOperation;
+
+/// What one side of a conflict stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideLabel {
+    /// The side's meaning, e.g. `upstream` or `your commit`.
+    pub role: &'static str,
+    /// Subject line of the commit the side comes from, if known.
+    pub subject: Option<String>,
+}
+
+impl SideLabel {
+    fn new(role: &'static str) -> Self {
+        Self {
+            role,
+            subject: None,
+        }
+    }
+}
+
+impl fmt::Display for SideLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.subject {
+            Some(subject) => write!(f, "{}: {subject}", self.role),
+            None => f.write_str(self.role),
+        }
+    }
+}
+
+/// Names of both conflict sides for an operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideNames {
+    /// The operation the names were derived from.
+    pub operation: GitOperation,
+    /// What "ours" (index stage 2) stands for.
+    pub ours: SideLabel,
+    /// What "theirs" (index stage 3) stands for.
+    pub theirs: SideLabel,
+}
+
+impl SideNames {
+    /// Returns the side names for an operation, without commit subjects.
+    #[must_use]
+    pub fn for_operation(operation: GitOperation) -> Self {
+        let (ours, theirs) = match operation {
+            GitOperation::None => ("ours", "theirs"),
+            GitOperation::Merge => ("your branch", "merged branch"),
+            GitOperation::Rebase => ("upstream", "your commit being replayed"),
+            GitOperation::CherryPick => ("your branch", "picked commit"),
+            GitOperation::Revert => ("your branch", "reverted commit"),
+        };
+        Self {
+            operation,
+            ours: SideLabel::new(ours),
+            theirs: SideLabel::new(theirs),
+        }
+    }
+
+    /// Returns true if the user's own change is "theirs".
+    ///
+    /// This is the case during a rebase, where the user's commits are
+    /// replayed onto the upstream.
+    #[must_use]
+    pub fn own_change_is_theirs(&self) -> bool {
+        self.operation == GitOperation::Rebase
+    }
+}
+
+/// Returns the first non-empty line of a commit message.
+pub(crate) fn subject_line(message: &str) -> Option<String> {
+    message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebase_swaps_meaning() {
+        let names = SideNames::for_operation(GitOperation::Rebase);
+        assert_eq!(names.ours.role, "upstream");
+        assert_eq!(names.theirs.role, "your commit being replayed");
+        assert!(names.own_change_is_theirs());
+
+        let merge = SideNames::for_operation(GitOperation::Merge);
+        assert_eq!(merge.ours.role, "your branch");
+        assert!(!merge.own_change_is_theirs());
+    }
+
+    #[test]
+    fn label_display_includes_subject() {
+        let mut label = SideLabel::new("upstream");
+        assert_eq!(label.to_string(), "upstream");
+        label.subject = Some("Add retries".into());
+        assert_eq!(label.to_string(), "upstream: Add retries");
+    }
+
+    #[test]
+    fn subject_line_skips_blank_lines() {
+        assert_eq!(
+            subject_line("\n  Fix typo  \n\nBody text\n"),
+            Some("Fix typo".into())
+        );
+        assert_eq!(subject_line("\n \n"), None);
+    }
+}
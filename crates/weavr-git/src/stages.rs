@@ -0,0 +1,144 @@
+//! Parser for `git ls-files -u -z` output (unmerged index stages).
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// An index stage of an unmerged path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexStage {
+    /// Stage 1: the common ancestor (merge base).
+    Base,
+    /// Stage 2: our version (`HEAD`).
+    Ours,
+    /// Stage 3: their version (`MERGE_HEAD`).
+    Theirs,
+}
+
+impl IndexStage {
+    /// Returns the stage number as used by Git (`1`, `2` or `3`).
+    #[must_use]
+    pub fn number(self) -> u8 {
+        match self {
+            IndexStage::Base => 1,
+            IndexStage::Ours => 2,
+            IndexStage::Theirs => 3,
+        }
+    }
+
+    /// Returns the stage for a Git stage number, if it is an unmerged stage.
+    #[must_use]
+    pub fn from_number(n: u8) -> Option<Self> {
+        match n {
+            1 => Some(IndexStage::Base),
+            2 => Some(IndexStage::Ours),
+            3 => Some(IndexStage::Theirs),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for IndexStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IndexStage::Base => "base",
+            IndexStage::Ours => "ours",
+            IndexStage::Theirs => "theirs",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One stage of an unmerged path, as listed by `git ls-files -u`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageEntry {
+    /// The file mode (e.g. `100644`).
+    pub mode: String,
+    /// The blob object ID.
+    pub object: String,
+    /// Which stage this entry is.
+    pub stage: IndexStage,
+    /// The path, relative to the repository root.
+    pub path: PathBuf,
+}
+
+/// Parses `git ls-files -u -z` output.
+///
+/// Each NUL-terminated record has the form `<mode> SP <object> SP <stage> TAB <path>`.
+/// With `-z`, paths are not quoted. Malformed records are skipped.
+#[must_use]
+pub fn parse_ls_files_unmerged(output: &str) -> Vec<StageEntry> {
+    output
+        .split('\0')
+        .filter_map(|record| {
+            let (meta, path) = record.split_once('\t')?;
+            let mut fields = meta.split(' ');
+            let mode = fields.next()?;
+            let object = fields.next()?;
+            let stage = IndexStage::from_number(fields.next()?.parse().ok()?)?;
+
+            Some(StageEntry {
+                mode: mode.to_string(),
+                object: object.to_string(),
+                stage,
+                path: PathBuf::from(path),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_empty_output() {
+        assert!(parse_ls_files_unmerged("").is_empty());
+    }
+
+    #[test]
+    fn parse_all_three_stages() {
+        let output =
+            "100644 aaa 1\tsrc/lib.rs\x00100644 bbb 2\tsrc/lib.rs\x00100644 ccc 3\tsrc/lib.rs\0";
+        let entries = parse_ls_files_unmerged(output);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].stage, IndexStage::Base);
+        assert_eq!(entries[0].object, "aaa");
+        assert_eq!(entries[1].stage, IndexStage::Ours);
+        assert_eq!(entries[2].stage, IndexStage::Theirs);
+        assert_eq!(entries[2].path, PathBuf::from("src/lib.rs"));
+        assert_eq!(entries[2].mode, "100644");
+    }
+
+    #[test]
+    fn parse_missing_base() {
+        let output = "100644 bbb 2\tadded.rs\x00100644 ccc 3\tadded.rs\0";
+        let entries = parse_ls_files_unmerged(output);
+
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.stage != IndexStage::Base));
+    }
+
+    #[test]
+    fn parse_path_with_spaces_and_tabs() {
+        let output = "100644 aaa 2\tdir with space/a\tb.rs\0";
+        let entries = parse_ls_files_unmerged(output);
+        assert_eq!(entries[0].path, PathBuf::from("dir with space/a\tb.rs"));
+    }
+
+    #[test]
+    fn parse_skips_malformed_records() {
+        let output = "garbage\x00100644 aaa 0\tmerged.rs\x00100644 bbb 2\tok.rs\0";
+        let entries = parse_ls_files_unmerged(output);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, PathBuf::from("ok.rs"));
+    }
+
+    #[test]
+    fn stage_numbers_round_trip() {
+        for stage in [IndexStage::Base, IndexStage::Ours, IndexStage::Theirs] {
+            assert_eq!(IndexStage::from_number(stage.number()), Some(stage));
+        }
+        assert_eq!(IndexStage::from_number(0), None);
+    }
+}
@@ -1,5 +1,7 @@
 //! Git repository state detection.
 
+use std::fmt;
+
 /// The type of Git operation currently in progress.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GitOperation {
@@ -21,6 +23,47 @@ impl GitOperation {
     pub fn has_conflicts(&self) -> bool {
         !matches!(self, GitOperation::None)
     }
+
+    /// Returns the git subcommand that drives the operation, e.g. `rebase`.
+    #[must_use]
+    pub fn command(&self) -> Option<&'static str> {
+        match self {
+            GitOperation::None => None,
+            GitOperation::Merge => Some("merge"),
+            GitOperation::Rebase => Some("rebase"),
+            GitOperation::CherryPick => Some("cherry-pick"),
+            GitOperation::Revert => Some("revert"),
+        }
+    }
+
+    /// Returns true if the operation can skip the current commit (`--skip`).
+    ///
+    /// A merge has a single step, so it can only be continued or aborted.
+    #[must_use]
+    pub fn can_skip(&self) -> bool {
+        matches!(
+            self,
+            GitOperation::Rebase | GitOperation::CherryPick | GitOperation::Revert
+        )
+    }
+}
+
+impl fmt::Display for GitOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.command().unwrap_or("no operation"))
+    }
+}
+
+/// Where an operation stands after it was continued or skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationStatus {
+    /// The operation completed; nothing is in progress any more.
+    Finished,
+    /// The operation stopped at a later step with new conflicts.
+    Conflicted,
+    /// The operation stopped without conflicts (e.g. an `edit` step of an
+    /// interactive rebase).
+    Stopped,
 }
 
 #[cfg(test)]
@@ -51,4 +94,14 @@ mod tests {
     fn revert_has_conflicts() {
         assert!(GitOperation::Revert.has_conflicts());
     }
+
+    #[test]
+    fn commands_and_skip_support() {
+        assert_eq!(GitOperation::None.command(), None);
+        assert_eq!(GitOperation::CherryPick.command(), Some("cherry-pick"));
+        assert_eq!(GitOperation::Rebase.to_string(), "rebase");
+        assert!(!GitOperation::Merge.can_skip());
+        assert!(GitOperation::Rebase.can_skip());
+        assert!(!GitOperation::None.can_skip());
+    }
 }
@@ -4,7 +4,10 @@ use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 use tempfile::TempDir;
-use weavr_git::{GitOperation, GitRepo};
+use weavr_git::{
+    set_config, ConfigScope, ConflictType, FileResolution, GitError, GitOperation, GitRepo,
+    IndexStage, OperationStatus,
+};
 
 /// Helper to create a Git repository in a temp directory.
 fn setup_git_repo() -> TempDir {
@@ -154,8 +157,8 @@ fn detect_merge_conflict() {
     assert_eq!(conflicts[0], PathBuf::from("file.txt"));
 }
 
-#[test]
-fn detect_rebase_conflict() {
+/// Helper to stop a rebase of `feature` onto `main` on a conflict.
+fn setup_rebase_conflict() -> TempDir {
     let dir = setup_git_repo();
 
     // Create initial commit
@@ -196,6 +199,12 @@ fn detect_rebase_conflict() {
         "rebase should have conflicted"
     );
 
+    dir
+}
+
+#[test]
+fn detect_rebase_conflict() {
+    let dir = setup_rebase_conflict();
     let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
 
     assert!(repo.is_in_rebase());
@@ -205,6 +214,32 @@ fn detect_rebase_conflict() {
     assert_eq!(conflicts.len(), 1);
 }
 
+#[test]
+fn side_names_during_rebase() {
+    let dir = setup_rebase_conflict();
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+
+    let names = repo.side_names();
+    assert!(names.own_change_is_theirs());
+    assert_eq!(names.ours.to_string(), "upstream: Main commit");
+    assert_eq!(
+        names.theirs.to_string(),
+        "your commit being replayed: Feature commit"
+    );
+}
+
+#[test]
+fn side_names_without_operation() {
+    let dir = setup_git_repo();
+    commit_file(&dir, "file.txt", "initial", "Initial commit");
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+
+    let names = repo.side_names();
+    assert_eq!(names.operation, GitOperation::None);
+    assert_eq!(names.ours.to_string(), "ours");
+    assert_eq!(names.theirs.subject, None);
+}
+
 #[test]
 fn detect_cherry_pick_conflict() {
     let dir = setup_git_repo();
@@ -343,3 +378,392 @@ fn conflicted_entries_returns_conflict_types() {
         weavr_git::ConflictType::BothModified
     );
 }
+
+#[test]
+fn merge_input_reads_index_stages() {
+    let dir = setup_git_repo();
+
+    // Use the default conflict style so the working copy has no base section
+    Command::new("git")
+        .args(["config", "merge.conflictStyle", "merge"])
+        .current_dir(dir.path())
+        .output()
+        .expect("git config conflictStyle");
+
+    commit_file(&dir, "file.txt", "a\nbase\nb\n", "Initial commit");
+
+    Command::new("git")
+        .args(["checkout", "-b", "feature"])
+        .current_dir(dir.path())
+        .output()
+        .expect("create branch");
+    commit_file(&dir, "file.txt", "a\ntheirs\nb\n", "Feature commit");
+
+    Command::new("git")
+        .args(["checkout", "main"])
+        .current_dir(dir.path())
+        .output()
+        .expect("checkout main");
+    commit_file(&dir, "file.txt", "a\nours\nb\n", "Main commit");
+
+    Command::new("git")
+        .args(["merge", "feature"])
+        .current_dir(dir.path())
+        .output()
+        .ok();
+
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+    let path = PathBuf::from("file.txt");
+
+    let stages: Vec<_> = repo
+        .unmerged_stages(&path)
+        .expect("list stages")
+        .into_iter()
+        .map(|e| e.stage)
+        .collect();
+    assert_eq!(
+        stages,
+        vec![IndexStage::Base, IndexStage::Ours, IndexStage::Theirs]
+    );
+
+    let input = repo.merge_input(&path).expect("merge input");
+    assert_eq!(input.left.content, "a\nours\nb\n");
+    assert_eq!(input.right.content, "a\ntheirs\nb\n");
+    assert_eq!(input.base.expect("base").content, "a\nbase\nb\n");
+    assert_eq!(input.left.path, path);
+
+    let working = fs::read_to_string(dir.path().join("file.txt")).expect("read working copy");
+    assert!(!working.contains("|||||||"));
+}
+
+#[test]
+fn paths_are_not_read_as_globs() {
+    let dir = setup_git_repo();
+    commit_file(&dir, "[id].tsx", "base\n", "Initial commit");
+    commit_file(&dir, "i.tsx", "base\n", "Add i.tsx");
+
+    git(&dir, &["checkout", "-b", "feature"]);
+    commit_file(&dir, "[id].tsx", "theirs\n", "Feature commit");
+    commit_file(&dir, "i.tsx", "theirs\n", "Feature commit");
+    git(&dir, &["checkout", "main"]);
+    commit_file(&dir, "[id].tsx", "ours\n", "Main commit");
+    commit_file(&dir, "i.tsx", "ours\n", "Main commit");
+    git(&dir, &["merge", "feature"]);
+
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+    let path = PathBuf::from("[id].tsx");
+    assert_eq!(repo.unmerged_stages(&path).expect("list stages").len(), 3);
+    assert_eq!(
+        repo.merge_input(&path).expect("merge input").left.content,
+        "ours\n"
+    );
+
+    repo.resolve_file(&path, FileResolution::KeepTheirs)
+        .expect("keep theirs");
+    assert_eq!(
+        repo.conflicted_files().expect("conflicts"),
+        vec![PathBuf::from("i.tsx")]
+    );
+}
+
+#[test]
+fn merge_input_add_add_has_no_base() {
+    let dir = setup_git_repo();
+    commit_file(&dir, "other.txt", "initial", "Initial commit");
+
+    Command::new("git")
+        .args(["checkout", "-b", "feature"])
+        .current_dir(dir.path())
+        .output()
+        .expect("create branch");
+    commit_file(&dir, "new.txt", "theirs\n", "Feature adds file");
+
+    Command::new("git")
+        .args(["checkout", "main"])
+        .current_dir(dir.path())
+        .output()
+        .expect("checkout main");
+    commit_file(&dir, "new.txt", "ours\n", "Main adds file");
+
+    Command::new("git")
+        .args(["merge", "feature"])
+        .current_dir(dir.path())
+        .output()
+        .ok();
+
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+    let path = PathBuf::from("new.txt");
+
+    assert_eq!(
+        repo.read_stage(&path, IndexStage::Base).expect("read"),
+        None
+    );
+    let input = repo.merge_input(&path).expect("merge input");
+    assert_eq!(input.left.content, "ours\n");
+    assert_eq!(input.right.content, "theirs\n");
+    assert!(input.base.is_none());
+}
+
+#[test]
+fn merge_input_rejects_merged_path() {
+    let dir = setup_git_repo();
+    commit_file(&dir, "file.txt", "content", "Initial commit");
+
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+    let result = repo.merge_input(&PathBuf::from("file.txt"));
+    assert!(matches!(result, Err(GitError::NotUnmerged(_))));
+}
+
+/// Creates a modify/delete conflict: `kept.txt` is modified on main and
+/// deleted on feature; `gone.txt` is deleted on main and modified on feature.
+fn setup_modify_delete_conflict() -> TempDir {
+    let dir = setup_git_repo();
+    commit_file(&dir, "kept.txt", "base\n", "Initial commit");
+    commit_file(&dir, "gone.txt", "base\n", "Add second file");
+
+    Command::new("git")
+        .args(["checkout", "-b", "feature"])
+        .current_dir(dir.path())
+        .output()
+        .expect("create branch");
+    Command::new("git")
+        .args(["rm", "-q", "kept.txt"])
+        .current_dir(dir.path())
+        .output()
+        .expect("git rm");
+    commit_file(&dir, "gone.txt", "theirs\n", "Feature commit");
+
+    Command::new("git")
+        .args(["checkout", "main"])
+        .current_dir(dir.path())
+        .output()
+        .expect("checkout main");
+    Command::new("git")
+        .args(["rm", "-q", "gone.txt"])
+        .current_dir(dir.path())
+        .output()
+        .expect("git rm");
+    commit_file(&dir, "kept.txt", "ours\n", "Main commit");
+
+    Command::new("git")
+        .args(["merge", "feature"])
+        .current_dir(dir.path())
+        .output()
+        .ok();
+
+    dir
+}
+
+#[test]
+fn file_conflict_reads_modify_delete() {
+    let dir = setup_modify_delete_conflict();
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+
+    let kept = repo
+        .file_conflict(&PathBuf::from("kept.txt"))
+        .expect("file conflict");
+    assert_eq!(kept.conflict_type, ConflictType::AddedByUsDeletedByThem);
+    assert_eq!(kept.ours.as_deref(), Some("ours\n"));
+    assert_eq!(kept.theirs, None);
+
+    let gone = repo
+        .file_conflict(&PathBuf::from("gone.txt"))
+        .expect("file conflict");
+    assert_eq!(gone.conflict_type, ConflictType::AddedByThemDeletedByUs);
+    assert_eq!(gone.theirs.as_deref(), Some("theirs\n"));
+
+    let entries = repo.conflicted_entries().expect("get entries");
+    assert!(entries
+        .iter()
+        .all(|e| !e.conflict_type.is_content_conflict()));
+}
+
+#[test]
+fn resolve_file_keeps_or_deletes() {
+    let dir = setup_modify_delete_conflict();
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+
+    repo.resolve_file(&PathBuf::from("kept.txt"), FileResolution::KeepOurs)
+        .expect("keep ours");
+    // Keeping ours for a file we deleted removes it
+    repo.resolve_file(&PathBuf::from("gone.txt"), FileResolution::KeepOurs)
+        .expect("keep ours");
+
+    assert!(repo.conflicted_files().expect("get conflicts").is_empty());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("kept.txt")).expect("read"),
+        "ours\n"
+    );
+    assert!(!dir.path().join("gone.txt").exists());
+}
+
+#[test]
+fn resolve_file_delete_and_keep_theirs() {
+    let dir = setup_modify_delete_conflict();
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+
+    repo.resolve_file(&PathBuf::from("kept.txt"), FileResolution::Delete)
+        .expect("delete");
+    repo.resolve_file(&PathBuf::from("gone.txt"), FileResolution::KeepTheirs)
+        .expect("keep theirs");
+
+    assert!(repo.conflicted_files().expect("get conflicts").is_empty());
+    assert!(!dir.path().join("kept.txt").exists());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("gone.txt")).expect("read"),
+        "theirs\n"
+    );
+
+    let result = repo.resolve_file(&PathBuf::from("gone.txt"), FileResolution::Delete);
+    assert!(matches!(result, Err(GitError::NotUnmerged(_))));
+}
+
+/// Helper to run a git command in the repository.
+fn git(dir: &TempDir, args: &[&str]) -> std::process::Output {
+    Command::new("git")
+        .args(args)
+        .current_dir(dir.path())
+        .output()
+        .expect("git command")
+}
+
+#[test]
+fn continue_rebase_stops_at_each_conflict_until_finished() {
+    let dir = setup_git_repo();
+    commit_file(&dir, "file.txt", "initial", "Initial commit");
+    git(&dir, &["checkout", "-b", "feature"]);
+    commit_file(&dir, "file.txt", "feature one", "Feature one");
+    commit_file(&dir, "file.txt", "feature two", "Feature two");
+    git(&dir, &["checkout", "main"]);
+    commit_file(&dir, "file.txt", "main change", "Main commit");
+    git(&dir, &["checkout", "feature"]);
+    assert!(!git(&dir, &["rebase", "main"]).status.success());
+
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+    assert!(matches!(
+        repo.continue_operation(),
+        Err(GitError::StillUnmerged(1))
+    ));
+
+    fs::write(dir.path().join("file.txt"), "resolved one").unwrap();
+    repo.stage_file(std::path::Path::new("file.txt")).unwrap();
+    assert_eq!(
+        repo.continue_operation().unwrap(),
+        OperationStatus::Conflicted
+    );
+    assert_eq!(
+        repo.side_names().theirs.subject.as_deref(),
+        Some("Feature two")
+    );
+
+    fs::write(dir.path().join("file.txt"), "resolved two").unwrap();
+    repo.stage_file(std::path::Path::new("file.txt")).unwrap();
+    assert_eq!(
+        repo.continue_operation().unwrap(),
+        OperationStatus::Finished
+    );
+    assert_eq!(repo.current_operation(), GitOperation::None);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+        "resolved two"
+    );
+}
+
+#[test]
+fn skip_and_abort_operations() {
+    let dir = setup_rebase_conflict();
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+
+    // Skipping the only commit finishes the rebase without it
+    assert_eq!(repo.skip_operation().unwrap(), OperationStatus::Finished);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+        "main change"
+    );
+    assert!(matches!(repo.abort_operation(), Err(GitError::NoOperation)));
+}
+
+#[test]
+fn merge_cannot_skip_but_can_abort() {
+    let dir = setup_git_repo();
+    commit_file(&dir, "file.txt", "initial", "Initial commit");
+    git(&dir, &["checkout", "-b", "feature"]);
+    commit_file(&dir, "file.txt", "feature change", "Feature commit");
+    git(&dir, &["checkout", "main"]);
+    commit_file(&dir, "file.txt", "main change", "Main commit");
+    assert!(!git(&dir, &["merge", "feature"]).status.success());
+
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+    assert!(matches!(
+        repo.skip_operation(),
+        Err(GitError::UnsupportedAction { .. })
+    ));
+
+    repo.abort_operation().unwrap();
+    assert_eq!(repo.current_operation(), GitOperation::None);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+        "main change"
+    );
+}
+
+#[test]
+fn set_local_config_entry() {
+    let dir = setup_git_repo();
+    set_config(
+        dir.path(),
+        ConfigScope::Local,
+        "mergetool.weavr.cmd",
+        "weavr mergetool \"$BASE\"",
+    )
+    .unwrap();
+
+    let output = git(&dir, &["config", "--local", "mergetool.weavr.cmd"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "weavr mergetool \"$BASE\""
+    );
+
+    let outside = TempDir::new().expect("create temp dir");
+    assert!(set_config(outside.path(), ConfigScope::Local, "a.b", "c").is_err());
+}
+
+#[test]
+fn rerere_resolutions_match_the_conflict_again() {
+    let dir = setup_git_repo();
+    git(&dir, &["config", "rerere.enabled", "true"]);
+    commit_file(&dir, "file.txt", "a\nbase\nz\n", "Initial commit");
+    git(&dir, &["checkout", "-b", "feature"]);
+    commit_file(&dir, "file.txt", "a\nfeature\nz\n", "Feature commit");
+    git(&dir, &["checkout", "main"]);
+    commit_file(&dir, "file.txt", "a\nmain\nz\n", "Main commit");
+
+    // Resolve once so rerere records the resolution, then undo the merge
+    assert!(!git(&dir, &["merge", "feature"]).status.success());
+    fs::write(dir.path().join("file.txt"), "a\nmain and feature\nz\n").unwrap();
+    git(&dir, &["add", "file.txt"]);
+    git(&dir, &["commit", "--no-edit"]);
+    git(&dir, &["reset", "--hard", "HEAD~1"]);
+
+    // Hit the same conflict again, without letting rerere resolve it
+    git(&dir, &["config", "rerere.enabled", "false"]);
+    assert!(!git(&dir, &["merge", "feature"]).status.success());
+
+    let repo = GitRepo::discover_from(dir.path()).expect("discover repo");
+    let recorded: weavr_core::RecordedResolutions = repo
+        .rerere_cache()
+        .resolutions()
+        .expect("read rr-cache")
+        .into_iter()
+        .collect();
+    assert_eq!(recorded.len(), 1);
+
+    let content = fs::read_to_string(dir.path().join("file.txt")).unwrap();
+    let mut session =
+        weavr_core::MergeSession::from_conflicted(&content, PathBuf::from("file.txt")).unwrap();
+    assert_eq!(session.apply_recorded(&recorded).len(), 1);
+    assert_eq!(
+        session.resolutions().values().next().unwrap().content,
+        "main and feature"
+    );
+}
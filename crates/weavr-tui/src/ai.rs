@@ -9,10 +9,13 @@
 //! constructs an [`AiHandle`] and passes it to [`App`].
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::mpsc;
 
 use weavr_core::{ConflictHunk, HunkId, HunkState, Resolution};
+use weavr_git::{SideLabel, SideNames};
 
+use crate::candidate;
 use crate::input::{Dialog, InputMode};
 use crate::resolution;
 use crate::App;
@@ -21,7 +24,24 @@ use crate::App;
 // Channel types
 // ---------------------------------------------------------------------------
 
+/// Default number of file lines sent on either side of a hunk.
+pub const DEFAULT_CONTEXT_LINES: usize = 20;
+
+/// What the AI worker is told about the file a hunk comes from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AiFileContext {
+    /// Path of the file being resolved.
+    pub path: PathBuf,
+    /// Subject of the commit "ours" comes from, if known.
+    pub ours_subject: Option<String>,
+    /// Subject of the commit "theirs" comes from, if known.
+    pub theirs_subject: Option<String>,
+}
+
 /// Command sent from TUI to AI background worker.
+///
+/// Hunks are sent with their context widened to the handle's
+/// [`context_lines`](AiHandle::context_lines) of surrounding file content.
 pub enum AiCommand {
     /// Request a suggestion for a single hunk.
     Suggest {
@@ -29,11 +49,15 @@ pub enum AiCommand {
         hunk_id: HunkId,
         /// A clone of the conflict hunk data.
         hunk: ConflictHunk,
+        /// The file the hunk comes from.
+        file: AiFileContext,
     },
     /// Request suggestions for multiple hunks.
     SuggestAll {
         /// Pairs of hunk ID and hunk data.
         hunks: Vec<(HunkId, ConflictHunk)>,
+        /// The file the hunks come from.
+        file: AiFileContext,
     },
     /// Request an explanation for a hunk's conflict.
     Explain {
@@ -41,6 +65,8 @@ pub enum AiCommand {
         hunk_id: HunkId,
         /// A clone of the conflict hunk data.
         hunk: ConflictHunk,
+        /// The file the hunk comes from.
+        file: AiFileContext,
     },
     /// Cancel any in-flight request for this hunk.
     Cancel {
@@ -61,6 +87,8 @@ pub enum AiEvent {
         resolution: Resolution,
         /// Confidence score (0-100), if available.
         confidence: Option<u8>,
+        /// Problems found when checking the suggestion against the conflict.
+        warnings: Vec<String>,
     },
     /// No suggestion available.
     NoSuggestion {
@@ -83,6 +111,13 @@ pub enum AiEvent {
         /// Error description.
         message: String,
     },
+    /// A hunk of the batch finished, with or without a suggestion.
+    BatchProgress {
+        /// Number of hunks finished so far.
+        done: usize,
+        /// Number of hunks in the batch.
+        total: usize,
+    },
     /// Batch suggestion processing is complete.
     BatchComplete,
 }
@@ -98,13 +133,31 @@ pub enum AiEvent {
 pub struct AiHandle {
     sender: mpsc::Sender<AiCommand>,
     receiver: mpsc::Receiver<AiEvent>,
+    context_lines: usize,
 }
 
 impl AiHandle {
     /// Creates a new handle from channel endpoints.
     #[must_use]
     pub fn new(sender: mpsc::Sender<AiCommand>, receiver: mpsc::Receiver<AiEvent>) -> Self {
-        Self { sender, receiver }
+        Self {
+            sender,
+            receiver,
+            context_lines: DEFAULT_CONTEXT_LINES,
+        }
+    }
+
+    /// Sets how many lines of file content to send on either side of a hunk.
+    #[must_use]
+    pub fn with_context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+
+    /// Returns how many lines of file content are sent on either side of a hunk.
+    #[must_use]
+    pub fn context_lines(&self) -> usize {
+        self.context_lines
     }
 
     /// Sends a command to the AI worker.
@@ -137,6 +190,9 @@ pub struct AiSuggestion {
     pub resolution: Resolution,
     /// Confidence score (0-100).
     pub confidence: Option<u8>,
+    /// Problems found when checking the suggestion against the conflict,
+    /// shown before it is accepted.
+    pub warnings: Vec<String>,
 }
 
 /// Tracks the current AI suggestion state for UI rendering.
@@ -146,6 +202,8 @@ pub struct AiState {
     pub pending_hunk: Option<HunkId>,
     /// Whether a batch request is in progress.
     pub pending_batch: bool,
+    /// Hunks finished and total in the batch in progress.
+    pub batch_progress: Option<(usize, usize)>,
     /// Suggestions keyed by hunk ID.
     pub suggestions: HashMap<HunkId, AiSuggestion>,
     /// An explanation for the current hunk.
@@ -179,6 +237,18 @@ impl AiState {
         self.explanation = None;
     }
 
+    /// Returns the spinner, followed by the batch progress (e.g. `| 12/30`)
+    /// while a batch is in progress.
+    #[must_use]
+    pub fn loading_indicator(&self) -> String {
+        match self.batch_progress {
+            Some((done, total)) if self.pending_batch => {
+                format!("{} {done}/{total}", self.spinner_char())
+            }
+            _ => self.spinner_char().to_string(),
+        }
+    }
+
     /// Advances the spinner animation tick.
     pub fn tick_spinner(&mut self) {
         self.spinner_tick = self.spinner_tick.wrapping_add(1);
@@ -211,9 +281,11 @@ pub fn request_suggestion(app: &mut App) {
         return;
     }
     app.ai_state.pending_hunk = Some(hunk.id);
+    let hunk = with_context_window(app, hunk);
     let send_result = app.ai_handle.as_ref().unwrap().send(AiCommand::Suggest {
         hunk_id: hunk.id,
         hunk,
+        file: file_context(app),
     });
     if send_result.is_err() {
         app.ai_state.pending_hunk = None;
@@ -237,8 +309,8 @@ pub fn request_all_suggestions(app: &mut App) {
     let hunks: Vec<_> = session
         .hunks()
         .iter()
-        .filter(|h| matches!(h.state, HunkState::Unresolved))
-        .map(|h| (h.id, h.clone()))
+        .filter(|h| !matches!(h.state, HunkState::Resolved(_)))
+        .map(|h| (h.id, with_context_window(app, h.clone())))
         .collect();
     if hunks.is_empty() {
         app.set_status_message("No unresolved hunks");
@@ -246,14 +318,19 @@ pub fn request_all_suggestions(app: &mut App) {
     }
     let count = hunks.len();
     app.ai_state.pending_batch = true;
+    app.ai_state.batch_progress = Some((0, count));
     if app
         .ai_handle
         .as_ref()
         .unwrap()
-        .send(AiCommand::SuggestAll { hunks })
+        .send(AiCommand::SuggestAll {
+            hunks,
+            file: file_context(app),
+        })
         .is_err()
     {
         app.ai_state.pending_batch = false;
+        app.ai_state.batch_progress = None;
         app.ai_handle = None;
         app.set_status_message("AI worker disconnected");
         return;
@@ -298,6 +375,7 @@ pub fn request_explanation(app: &mut App) {
         return;
     };
     app.ai_state.pending_hunk = Some(hunk.id);
+    let hunk = with_context_window(app, hunk);
     if app
         .ai_handle
         .as_ref()
@@ -305,6 +383,7 @@ pub fn request_explanation(app: &mut App) {
         .send(AiCommand::Explain {
             hunk_id: hunk.id,
             hunk,
+            file: file_context(app),
         })
         .is_err()
     {
@@ -316,6 +395,40 @@ pub fn request_explanation(app: &mut App) {
     app.set_status_message("Requesting AI explanation...");
 }
 
+/// Widens the hunk's context to the handle's window of file content.
+fn with_context_window(app: &App, mut hunk: ConflictHunk) -> ConflictHunk {
+    let lines = app
+        .ai_handle
+        .as_ref()
+        .map_or(DEFAULT_CONTEXT_LINES, AiHandle::context_lines);
+    if let Some(context) = app
+        .session
+        .as_ref()
+        .and_then(|session| session.context_window(hunk.id, lines))
+    {
+        hunk.context = context;
+    }
+    hunk
+}
+
+/// Describes the current file for the AI worker.
+fn file_context(app: &App) -> AiFileContext {
+    let subject = |side: fn(&SideNames) -> &SideLabel| {
+        app.side_names
+            .as_ref()
+            .and_then(|names| side(names).subject.clone())
+    };
+    AiFileContext {
+        path: app
+            .session
+            .as_ref()
+            .map(|session| session.input().left.path.clone())
+            .unwrap_or_default(),
+        ours_subject: subject(|names| &names.ours),
+        theirs_subject: subject(|names| &names.theirs),
+    }
+}
+
 /// Polls for AI events and updates state. Called each tick in the event loop.
 pub fn poll_ai_events(app: &mut App) {
     // Collect events first to avoid borrow conflict (ai_handle borrows app)
@@ -337,6 +450,7 @@ pub fn poll_ai_events(app: &mut App) {
                 hunk_id,
                 resolution,
                 confidence,
+                warnings,
             } => {
                 // Only accept if we're still interested in this hunk
                 let interested = app.ai_state.pending_hunk == Some(hunk_id)
@@ -346,23 +460,30 @@ pub fn poll_ai_events(app: &mut App) {
                     if app.ai_state.pending_hunk == Some(hunk_id) {
                         app.ai_state.pending_hunk = None;
                     }
-                    app.ai_state.suggestions.insert(
-                        hunk_id,
-                        AiSuggestion {
-                            hunk_id,
-                            resolution,
-                            confidence,
-                        },
-                    );
+                    candidate::add_ai_candidate(app, hunk_id, resolution.clone());
                     // Only show status message if this is for the current hunk
                     if app.current_hunk().is_some_and(|h| h.id == hunk_id) {
                         let conf_str = confidence
                             .map(|c| format!(" ({c}% confidence)"))
                             .unwrap_or_default();
+                        let warn_str = if warnings.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" - warning: {}", warnings.join(", "))
+                        };
                         app.set_status_message(&format!(
-                            "AI suggestion ready{conf_str} - Enter to accept, Esc to dismiss"
+                            "AI suggestion ready{conf_str}{warn_str} - Enter to accept, Esc to dismiss"
                         ));
                     }
+                    app.ai_state.suggestions.insert(
+                        hunk_id,
+                        AiSuggestion {
+                            hunk_id,
+                            resolution,
+                            confidence,
+                            warnings,
+                        },
+                    );
                 }
             }
             AiEvent::NoSuggestion { reason, .. } => {
@@ -381,12 +502,16 @@ pub fn poll_ai_events(app: &mut App) {
                 }
             }
             AiEvent::Error { message, .. } => {
+                // A failed hunk does not end a batch; BatchComplete does
                 app.ai_state.pending_hunk = None;
-                app.ai_state.pending_batch = false;
                 app.set_status_message(&format!("AI error: {message}"));
             }
+            AiEvent::BatchProgress { done, total } => {
+                app.ai_state.batch_progress = Some((done, total));
+            }
             AiEvent::BatchComplete => {
                 app.ai_state.pending_batch = false;
+                app.ai_state.batch_progress = None;
                 let count = app.ai_state.suggestions.len();
                 app.set_status_message(&format!("AI batch complete: {count} suggestion(s) ready"));
             }
@@ -439,6 +564,7 @@ mod tests {
                 hunk_id,
                 resolution: Resolution::manual("test".into()),
                 confidence: Some(85),
+                warnings: Vec::new(),
             },
         );
         assert!(state.has_suggestion_for(hunk_id));
@@ -454,6 +580,7 @@ mod tests {
                 hunk_id: HunkId(1),
                 resolution: Resolution::manual("test".into()),
                 confidence: None,
+                warnings: Vec::new(),
             },
         );
         state.explanation = Some("explanation".into());
@@ -512,6 +639,7 @@ mod tests {
                 hunk_id: HunkId(1),
                 resolution: Resolution::manual("test".into()),
                 confidence: Some(90),
+                warnings: Vec::new(),
             },
         );
         dismiss_suggestion(&mut app);
@@ -569,6 +697,104 @@ mod tests {
         assert!(event.is_some());
     }
 
+    #[test]
+    fn requests_carry_file_context() {
+        let content = "1\n2\n3\n4\n5\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\n6\n7";
+        let mut app = App::new();
+        app.set_session(
+            weavr_core::MergeSession::from_conflicted(content, "src/lib.rs".into()).unwrap(),
+        );
+        let mut names = SideNames::for_operation(weavr_git::GitOperation::Merge);
+        names.theirs.subject = Some("Add feature".into());
+        app.set_side_names(names, false);
+
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (_evt_tx, evt_rx) = mpsc::channel();
+        app.set_ai_handle(AiHandle::new(cmd_tx, evt_rx).with_context_lines(4));
+
+        request_explanation(&mut app);
+        let Ok(AiCommand::Explain { hunk, file, .. }) = cmd_rx.try_recv() else {
+            panic!("expected an explain command");
+        };
+        assert_eq!(hunk.context.before, vec!["2", "3", "4", "5"]);
+        assert_eq!(hunk.context.after, vec!["6", "7"]);
+        assert_eq!(
+            file,
+            AiFileContext {
+                path: "src/lib.rs".into(),
+                ours_subject: None,
+                theirs_subject: Some("Add feature".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn batch_progress_survives_failed_hunks() {
+        let (cmd_tx, _cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        let mut app = App::new();
+        app.set_ai_handle(AiHandle::new(cmd_tx, evt_rx));
+        app.ai_state.pending_batch = true;
+        app.ai_state.batch_progress = Some((0, 30));
+
+        evt_tx
+            .send(AiEvent::Error {
+                hunk_id: HunkId(3),
+                message: "rate limit exceeded".into(),
+            })
+            .unwrap();
+        evt_tx
+            .send(AiEvent::BatchProgress {
+                done: 12,
+                total: 30,
+            })
+            .unwrap();
+        poll_ai_events(&mut app);
+        assert!(app.ai_state.pending_batch);
+        assert!(app.ai_state.loading_indicator().ends_with(" 12/30"));
+
+        evt_tx.send(AiEvent::BatchComplete).unwrap();
+        poll_ai_events(&mut app);
+        assert!(!app.ai_state.is_loading());
+        assert!(app.ai_state.batch_progress.is_none());
+        assert_eq!(
+            app.ai_state.loading_indicator(),
+            app.ai_state.spinner_char().to_string()
+        );
+    }
+
+    #[test]
+    fn suggestion_warnings_are_shown() {
+        let content = "<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\n";
+        let mut app = App::new();
+        app.set_session(
+            weavr_core::MergeSession::from_conflicted(content, "file.txt".into()).unwrap(),
+        );
+        let hunk_id = app.current_hunk().unwrap().id;
+        let (cmd_tx, _cmd_rx) = mpsc::channel();
+        let (evt_tx, evt_rx) = mpsc::channel();
+        app.set_ai_handle(AiHandle::new(cmd_tx, evt_rx));
+
+        evt_tx
+            .send(AiEvent::Suggestion {
+                hunk_id,
+                resolution: Resolution::manual("left".into()),
+                confidence: Some(80),
+                warnings: vec!["drops the right side's change".into()],
+            })
+            .unwrap();
+        poll_ai_events(&mut app);
+
+        assert_eq!(
+            app.ai_state.suggestion_for(hunk_id).unwrap().warnings,
+            vec!["drops the right side's change"]
+        );
+        assert!(app
+            .status_message()
+            .is_some_and(|(msg, _)| msg
+                .contains("(80% confidence) - warning: drops the right side's change")));
+    }
+
     #[test]
     fn ai_handle_try_recv_returns_none_when_empty() {
         let (cmd_tx, _cmd_rx) = mpsc::channel::<AiCommand>();
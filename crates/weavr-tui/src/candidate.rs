@@ -0,0 +1,331 @@
+//! Candidate resolutions for conflict hunks.
+//!
+//! This module handles:
+//! - Populating per-hunk candidates from the built-in text strategies
+//! - Recording AI suggestions as candidates
+//! - Offering resolutions recorded in earlier merges as candidates
+//! - Cycling through and committing the current hunk's candidates
+//!
+//! Candidates live in the session (`HunkState::Proposed`); the TUI only
+//! tracks which candidate is shown for each hunk.
+
+use weavr_core::{
+    AcceptBothOptions, AcceptBothStrategy, BothOrder, HunkId, MergeEngine, RecordedResolutions,
+    Resolution, ResolutionStrategyKind,
+};
+
+use crate::resolution;
+use crate::{files, App};
+
+/// Builds the engine used to propose candidates when a session is loaded.
+///
+/// Offers ours, theirs, both, and both with duplicate lines removed.
+#[must_use]
+pub fn default_engine() -> MergeEngine {
+    MergeEngine::with_text_strategies(AcceptBothOptions::default()).with_strategy(
+        AcceptBothStrategy {
+            options: AcceptBothOptions {
+                deduplicate: true,
+                ..AcceptBothOptions::default()
+            },
+        },
+    )
+}
+
+/// Records candidates from [`default_engine`] for every hunk in the session.
+pub fn populate_candidates(app: &mut App) {
+    if let Some(session) = app.session.as_mut() {
+        session.populate_proposals(&default_engine());
+    }
+    app.candidate_index.clear();
+}
+
+/// Returns the current hunk's candidates along with the selected index.
+#[must_use]
+pub fn current_candidates(app: &App) -> Option<(usize, &[Resolution])> {
+    let session = app.session.as_ref()?;
+    let hunk = session.hunks().get(app.current_hunk_index)?;
+    let candidates = session.proposals(hunk.id);
+    if candidates.is_empty() {
+        return None;
+    }
+    let index = app
+        .candidate_index
+        .get(&hunk.id)
+        .copied()
+        .unwrap_or(0)
+        .min(candidates.len() - 1);
+    Some((index, candidates))
+}
+
+/// Shows the next candidate for the current hunk, wrapping around.
+pub fn next_candidate(app: &mut App) {
+    step_candidate(app, true);
+}
+
+/// Shows the previous candidate for the current hunk, wrapping around.
+pub fn prev_candidate(app: &mut App) {
+    step_candidate(app, false);
+}
+
+fn step_candidate(app: &mut App, forward: bool) {
+    let Some(hunk_id) = app.current_hunk().map(|h| h.id) else {
+        return;
+    };
+    let Some((index, candidates)) = current_candidates(app) else {
+        app.set_status_message("No candidates for this hunk");
+        return;
+    };
+    let total = candidates.len();
+    let next = if forward {
+        (index + 1) % total
+    } else {
+        (index + total - 1) % total
+    };
+    let label = candidate_label(&candidates[next].kind);
+
+    app.candidate_index.insert(hunk_id, next);
+    app.set_status_message(&format!("Candidate {}/{total}: {label}", next + 1));
+}
+
+/// Commits the currently shown candidate as the hunk's resolution.
+///
+/// The change is recorded in the action history like any other resolution.
+pub fn accept_candidate(app: &mut App) {
+    let Some((index, candidates)) = current_candidates(app) else {
+        app.set_status_message("No candidates for this hunk");
+        return;
+    };
+    let candidate = candidates[index].clone();
+    let action = format!("Accept candidate: {}", candidate_label(&candidate.kind));
+    resolution::apply_resolution(app, &action, |_hunk| candidate);
+}
+
+/// Records an AI suggestion as the first candidate for a hunk.
+///
+/// An existing candidate with the same content is replaced, so repeated
+/// suggestions don't pile up.
+pub fn add_ai_candidate(app: &mut App, hunk_id: HunkId, resolution: Resolution) {
+    let Some(session) = app.session.as_mut() else {
+        return;
+    };
+    let mut candidates: Vec<Resolution> = session
+        .proposals(hunk_id)
+        .iter()
+        .filter(|c| c.content != resolution.content)
+        .cloned()
+        .collect();
+    candidates.insert(0, resolution);
+    if session.set_proposals(hunk_id, candidates).is_ok() {
+        app.candidate_index.insert(hunk_id, 0);
+    }
+}
+
+/// Offers resolutions recorded in earlier merges as the first candidate
+/// for every matching hunk, in all loaded files.
+pub fn add_recorded_candidates(app: &mut App, recorded: &RecordedResolutions) {
+    let count: usize = files::sessions_mut(app)
+        .map(|session| session.propose_recorded(recorded).len())
+        .sum();
+    app.candidate_index.clear();
+    if count > 0 {
+        app.set_status_message(&format!("Recorded resolutions found for {count} hunks"));
+    }
+}
+
+/// Returns a short display label for a candidate's kind,
+/// e.g. `AcceptBoth(dedupe)` or `AiSuggested(claude)`.
+#[must_use]
+pub fn candidate_label(kind: &ResolutionStrategyKind) -> String {
+    match kind {
+        ResolutionStrategyKind::AcceptLeft => "AcceptLeft".to_string(),
+        ResolutionStrategyKind::AcceptRight => "AcceptRight".to_string(),
+        ResolutionStrategyKind::AcceptBoth(options) => {
+            let mut flags = Vec::new();
+            if options.order == BothOrder::RightThenLeft {
+                flags.push("right-first");
+            }
+            if options.deduplicate {
+                flags.push("dedupe");
+            }
+            if options.trim_whitespace {
+                flags.push("trim");
+            }
+            if flags.is_empty() {
+                "AcceptBoth".to_string()
+            } else {
+                format!("AcceptBoth({})", flags.join(", "))
+            }
+        }
+        ResolutionStrategyKind::Trivial => "Trivial".to_string(),
+        ResolutionStrategyKind::Recorded => "Recorded".to_string(),
+        ResolutionStrategyKind::Manual => "Manual".to_string(),
+        ResolutionStrategyKind::AstMerged { language } => format!("AstMerged({language})"),
+        ResolutionStrategyKind::AiSuggested { provider } => format!("AiSuggested({provider})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use weavr_core::{HunkState, MergeSession, ResolutionMetadata, ResolutionSource};
+
+    use super::*;
+
+    fn app_with_conflict() -> App {
+        let content =
+            "before\n<<<<<<< HEAD\nshared\nleft\n=======\nshared\nright\n>>>>>>> feature\nafter";
+        let session = MergeSession::from_conflicted(content, PathBuf::from("test.rs")).unwrap();
+        let mut app = App::new();
+        app.set_session(session);
+        app
+    }
+
+    #[test]
+    fn set_session_populates_candidates() {
+        let app = app_with_conflict();
+        let (index, candidates) = current_candidates(&app).unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(candidates.len(), 4);
+        assert!(matches!(
+            app.current_hunk().unwrap().state,
+            HunkState::Proposed(_)
+        ));
+    }
+
+    #[test]
+    fn next_and_prev_candidate_wrap() {
+        let mut app = app_with_conflict();
+
+        next_candidate(&mut app);
+        assert_eq!(current_candidates(&app).unwrap().0, 1);
+
+        prev_candidate(&mut app);
+        prev_candidate(&mut app);
+        assert_eq!(current_candidates(&app).unwrap().0, 3);
+
+        next_candidate(&mut app);
+        assert_eq!(current_candidates(&app).unwrap().0, 0);
+    }
+
+    #[test]
+    fn accept_candidate_resolves_and_records_history() {
+        let mut app = app_with_conflict();
+        next_candidate(&mut app);
+        next_candidate(&mut app);
+        next_candidate(&mut app);
+
+        accept_candidate(&mut app);
+
+        let hunk = app.current_hunk().unwrap();
+        let HunkState::Resolved(resolution) = &hunk.state else {
+            panic!("hunk should be resolved");
+        };
+        assert_eq!(resolution.content, "shared\nleft\nright");
+        assert!(app.can_undo());
+
+        app.undo();
+        assert!(matches!(
+            app.current_hunk().unwrap().state,
+            HunkState::Proposed(_)
+        ));
+    }
+
+    #[test]
+    fn cycling_without_session_is_noop() {
+        let mut app = App::new();
+        next_candidate(&mut app);
+        accept_candidate(&mut app);
+        assert!(!app.can_undo());
+    }
+
+    #[test]
+    fn ai_candidate_goes_first() {
+        let mut app = app_with_conflict();
+        let hunk_id = app.current_hunk().unwrap().id;
+        let suggestion = Resolution {
+            kind: ResolutionStrategyKind::AiSuggested {
+                provider: "claude".to_string(),
+            },
+            content: "merged".to_string(),
+            metadata: ResolutionMetadata {
+                source: ResolutionSource::Ai,
+                ..ResolutionMetadata::default()
+            },
+        };
+
+        add_ai_candidate(&mut app, hunk_id, suggestion.clone());
+        add_ai_candidate(&mut app, hunk_id, suggestion);
+
+        let (index, candidates) = current_candidates(&app).unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(candidates.len(), 5);
+        assert_eq!(candidates[0].content, "merged");
+    }
+
+    #[test]
+    fn recorded_candidate_goes_first_in_every_file() {
+        let content =
+            "before\n<<<<<<< HEAD\nshared\nleft\n=======\nshared\nright\n>>>>>>> feature\nafter";
+        let mut earlier = MergeSession::from_conflicted(content, PathBuf::from("a.rs")).unwrap();
+        let hunk = earlier.hunks()[0].clone();
+        earlier
+            .set_resolution(hunk.id, Resolution::accept_right(&hunk))
+            .unwrap();
+        let recorded = earlier.recorded_resolutions().into_iter().collect();
+
+        let mut app = App::new();
+        app.set_files(vec![
+            crate::files::FileEntry::new(
+                MergeSession::from_conflicted(content, PathBuf::from("a.rs")).unwrap(),
+            ),
+            crate::files::FileEntry::new(
+                MergeSession::from_conflicted(content, PathBuf::from("b.rs")).unwrap(),
+            ),
+        ]);
+        app.add_recorded_candidates(&recorded);
+
+        let (index, candidates) = current_candidates(&app).unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(candidates[0].kind, ResolutionStrategyKind::Recorded);
+        assert_eq!(candidates[0].content, "shared\nright");
+        assert_eq!(candidates.len(), 4);
+        assert_eq!(
+            app.status_message().map(|(m, _)| m.as_str()),
+            Some("Recorded resolutions found for 2 hunks")
+        );
+
+        app.next_file();
+        let (_, candidates) = current_candidates(&app).unwrap();
+        assert_eq!(candidates[0].kind, ResolutionStrategyKind::Recorded);
+    }
+
+    #[test]
+    fn labels_describe_kind() {
+        assert_eq!(
+            candidate_label(&ResolutionStrategyKind::AcceptLeft),
+            "AcceptLeft"
+        );
+        assert_eq!(
+            candidate_label(&ResolutionStrategyKind::AcceptBoth(
+                AcceptBothOptions::default()
+            )),
+            "AcceptBoth"
+        );
+        assert_eq!(
+            candidate_label(&ResolutionStrategyKind::AcceptBoth(AcceptBothOptions {
+                order: BothOrder::RightThenLeft,
+                deduplicate: true,
+                trim_whitespace: false,
+            })),
+            "AcceptBoth(right-first, dedupe)"
+        );
+        assert_eq!(
+            candidate_label(&ResolutionStrategyKind::AiSuggested {
+                provider: "claude".to_string()
+            }),
+            "AiSuggested(claude)"
+        );
+    }
+}
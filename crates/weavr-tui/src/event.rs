@@ -64,6 +64,10 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
         return;
     }
 
+    if handle_file_sequence(app, key) {
+        return;
+    }
+
     // Clear pending key for any other keypress
     app.key_sequence.clear();
 
@@ -97,6 +101,7 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
         }
 
         // Dismiss AI suggestion
+        #[allow(clippy::collapsible_match)] // Esc is only for dismissing, even without a suggestion
         KeyCode::Esc => {
             if app
                 .current_hunk()
@@ -115,6 +120,7 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
             let last = app.total_hunks().saturating_sub(1);
             app.go_to_hunk(last);
         }
+        KeyCode::Char('F') => app.toggle_file_list(),
 
         // Scrolling (half page = 10 lines)
         KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -132,12 +138,18 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Char('b') => app.resolve_both(),
         KeyCode::Char('B') => app.show_accept_both_dialog(), // Shift-B for options
         KeyCode::Char('x') => app.clear_current_resolution(),
+        KeyCode::Char('D') => app.delete_file(),
         KeyCode::Char('u') if !key.modifiers.contains(KeyModifiers::CONTROL) => app.undo(),
         KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => app.redo(),
         KeyCode::Char('e') => {
             app.prepare_editor();
         }
 
+        // Candidates
+        KeyCode::Char('c') => app.next_candidate(),
+        KeyCode::Char('C') => app.prev_candidate(),
+        KeyCode::Char('a') => app.accept_candidate(),
+
         // AI suggestions
         KeyCode::Char('s') => ai::request_suggestion(app),
         KeyCode::Char('S') => ai::request_all_suggestions(app),
@@ -161,6 +173,35 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
     }
 }
 
+/// Handles the `]f` / `[f` file navigation sequences.
+///
+/// Returns true if the key was consumed.
+fn handle_file_sequence(app: &mut App, key: KeyEvent) -> bool {
+    if key.code == KeyCode::Char('f') {
+        if app
+            .key_sequence
+            .check(KeyCode::Char(']'), KEY_SEQUENCE_TIMEOUT)
+        {
+            app.key_sequence.clear();
+            app.next_file();
+            return true;
+        }
+        if app
+            .key_sequence
+            .check(KeyCode::Char('['), KEY_SEQUENCE_TIMEOUT)
+        {
+            app.key_sequence.clear();
+            app.prev_file();
+            return true;
+        }
+    }
+    if let KeyCode::Char(']' | '[') = key.code {
+        app.key_sequence.set(key.code);
+        return true;
+    }
+    false
+}
+
 /// Handles key events in command mode.
 fn handle_command_mode(app: &mut App, key: KeyEvent) {
     match key.code {
@@ -381,6 +422,40 @@ mod tests {
         // Should not have gone to first hunk
     }
 
+    #[test]
+    fn bracket_f_sequences_switch_files() {
+        use std::path::PathBuf;
+
+        use weavr_core::MergeSession;
+
+        use crate::files::FileEntry;
+
+        let session = |name: &str| {
+            let content = "<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature";
+            FileEntry::new(MergeSession::from_conflicted(content, PathBuf::from(name)).unwrap())
+        };
+        let mut app = App::new();
+        app.set_files(vec![session("a.rs"), session("b.rs"), session("c.rs")]);
+        let press = |app: &mut App, c: char| {
+            let event = Event::Key(make_key_event(KeyCode::Char(c), KeyModifiers::NONE));
+            handle_event(app, &event);
+        };
+
+        press(&mut app, ']');
+        press(&mut app, 'f');
+        assert_eq!(app.current_file_index(), 1);
+
+        press(&mut app, '[');
+        press(&mut app, 'f');
+        press(&mut app, '[');
+        press(&mut app, 'f');
+        assert_eq!(app.current_file_index(), 2);
+
+        // A lone 'f' does nothing
+        press(&mut app, 'f');
+        assert_eq!(app.current_file_index(), 2);
+    }
+
     #[test]
     fn shift_g_goes_to_last_hunk() {
         let mut app = App::new();
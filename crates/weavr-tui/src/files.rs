@@ -0,0 +1,493 @@
+//! Multi-file sessions.
+//!
+//! This module handles:
+//! - Holding one merge session per conflicted file
+//! - Switching the active file (`]f` / `[f`) with per-file view state
+//! - Per-file progress for the file list sidebar
+//! - File-level conflicts (delete/modify, both-deleted), which have no
+//!   session and are resolved by choosing a [`FileResolution`]
+//!
+//! The active file's session lives in `App::session`, so the rest of the
+//! TUI works on it unchanged. Inactive files keep their session and view
+//! state (hunk position, scroll, undo history, candidates) in their
+//! [`FileEntry`].
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use weavr_core::{ActionHistory, HunkId, HunkState, MergeSession};
+use weavr_git::{ConflictType, FileConflict, FileResolution};
+
+use crate::ai::AiState;
+use crate::candidate;
+use crate::App;
+
+/// A file loaded in the TUI.
+#[derive(Debug)]
+pub struct FileEntry {
+    path: PathBuf,
+    conflict_type: Option<ConflictType>,
+    /// The session; `None` while the file is active (held by `App`).
+    session: Option<MergeSession>,
+    /// The conflict, for files resolved as a whole rather than by hunk.
+    file_conflict: Option<FileConflict>,
+    /// The chosen resolution for a file-level conflict.
+    file_resolution: Option<FileResolution>,
+    view: FileView,
+}
+
+/// Per-file view state saved while another file is active.
+#[derive(Debug, Default)]
+struct FileView {
+    current_hunk_index: usize,
+    left_right_scroll: u16,
+    result_scroll: u16,
+    action_history: ActionHistory,
+    candidate_index: HashMap<HunkId, usize>,
+    ai_state: AiState,
+}
+
+impl FileEntry {
+    /// Creates an entry for a session. The path is taken from the session input.
+    #[must_use]
+    pub fn new(session: MergeSession) -> Self {
+        Self {
+            path: session.input().left.path.clone(),
+            conflict_type: None,
+            session: Some(session),
+            file_conflict: None,
+            file_resolution: None,
+            view: FileView::default(),
+        }
+    }
+
+    /// Creates an entry for a file-level conflict, shown under `path`.
+    #[must_use]
+    pub fn file_level(path: PathBuf, conflict: FileConflict) -> Self {
+        Self {
+            path,
+            conflict_type: Some(conflict.conflict_type),
+            session: None,
+            file_conflict: Some(conflict),
+            file_resolution: None,
+            view: FileView::default(),
+        }
+    }
+
+    /// Sets the Git conflict type shown in the file list.
+    #[must_use]
+    pub fn with_conflict_type(mut self, conflict_type: ConflictType) -> Self {
+        self.conflict_type = Some(conflict_type);
+        self
+    }
+
+    /// Sets the undo/redo history, e.g. when resuming a saved session.
+    #[must_use]
+    pub fn with_history(mut self, history: ActionHistory) -> Self {
+        self.view.action_history = history;
+        self
+    }
+
+    /// Returns the file path.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the Git conflict type, if known.
+    #[must_use]
+    pub fn conflict_type(&self) -> Option<ConflictType> {
+        self.conflict_type
+    }
+
+    /// Returns the file-level conflict, if this file has no content conflict.
+    #[must_use]
+    pub fn file_conflict(&self) -> Option<&FileConflict> {
+        self.file_conflict.as_ref()
+    }
+
+    /// Returns the chosen resolution for a file-level conflict.
+    #[must_use]
+    pub fn file_resolution(&self) -> Option<FileResolution> {
+        self.file_resolution
+    }
+
+    /// Consumes the entry and returns its session.
+    ///
+    /// Returns `None` only if the session was taken while the file was active.
+    #[must_use]
+    pub fn into_session(self) -> Option<MergeSession> {
+        self.session
+    }
+}
+
+/// Replaces the loaded files and activates the first one.
+///
+/// Every session receives candidates from [`candidate::default_engine`].
+pub fn set_files(app: &mut App, files: Vec<FileEntry>) {
+    let engine = candidate::default_engine();
+    app.files = files;
+    for entry in &mut app.files {
+        if let Some(session) = entry.session.as_mut() {
+            session.populate_proposals(&engine);
+        }
+    }
+    app.current_file = 0;
+    activate(app, 0);
+}
+
+/// Returns every loaded session, including the active file's.
+pub(crate) fn sessions_mut(app: &mut App) -> impl Iterator<Item = &mut MergeSession> {
+    app.session.iter_mut().chain(
+        app.files
+            .iter_mut()
+            .filter_map(|entry| entry.session.as_mut()),
+    )
+}
+
+/// Returns the loaded files.
+///
+/// The active file's session is held by the app, not its entry.
+#[must_use]
+pub fn files(app: &App) -> &[FileEntry] {
+    &app.files
+}
+
+/// Moves to the next file, wrapping around.
+pub fn next_file(app: &mut App) {
+    let count = app.files.len();
+    if count > 1 {
+        go_to_file(app, (app.current_file + 1) % count);
+    }
+}
+
+/// Moves to the previous file, wrapping around.
+pub fn prev_file(app: &mut App) {
+    let count = app.files.len();
+    if count > 1 {
+        go_to_file(app, (app.current_file + count - 1) % count);
+    }
+}
+
+/// Makes the file at `index` active, saving the current file's view state.
+pub fn go_to_file(app: &mut App, index: usize) {
+    if index >= app.files.len() || index == app.current_file {
+        return;
+    }
+    if app.ai_state.is_loading() {
+        app.set_status_message("AI request in progress - wait before switching files");
+        return;
+    }
+
+    stash_active(app);
+    app.current_file = index;
+    activate(app, index);
+
+    let (resolved, total) = file_progress(app, index);
+    let message = format!(
+        "File {}/{}: {} ({resolved}/{total} resolved)",
+        index + 1,
+        app.files.len(),
+        app.files[index].path.display()
+    );
+    app.set_status_message(&message);
+}
+
+/// Returns `(resolved, total)` hunk counts for the file at `index`.
+///
+/// A file-level conflict counts as a single hunk.
+#[must_use]
+pub fn file_progress(app: &App, index: usize) -> (usize, usize) {
+    if let Some(entry) = app.files.get(index).filter(|e| e.file_conflict.is_some()) {
+        return (usize::from(entry.file_resolution.is_some()), 1);
+    }
+    let session = if index == app.current_file {
+        app.session.as_ref()
+    } else {
+        app.files.get(index).and_then(|e| e.session.as_ref())
+    };
+    session.map_or((0, 0), |s| {
+        let resolved = s
+            .hunks()
+            .iter()
+            .filter(|h| matches!(h.state, HunkState::Resolved(_)))
+            .count();
+        (resolved, s.hunks().len())
+    })
+}
+
+/// Returns the number of unresolved hunks across all files.
+#[must_use]
+pub fn total_unresolved(app: &App) -> usize {
+    let active = app
+        .session
+        .as_ref()
+        .map_or(0, |s| s.unresolved_hunks().len());
+    let inactive: usize = app
+        .files
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != app.current_file)
+        .filter_map(|(_, e)| e.session.as_ref())
+        .map(|s| s.unresolved_hunks().len())
+        .sum();
+    let file_level = app
+        .files
+        .iter()
+        .filter(|e| e.file_conflict.is_some() && e.file_resolution.is_none())
+        .count();
+    active + inactive + file_level
+}
+
+/// Returns the active file's file-level conflict and its chosen resolution.
+#[must_use]
+pub fn active_file_conflict(app: &App) -> Option<(&FileConflict, Option<FileResolution>)> {
+    let entry = app.files.get(app.current_file)?;
+    entry
+        .file_conflict
+        .as_ref()
+        .map(|conflict| (conflict, entry.file_resolution))
+}
+
+/// Chooses (or with `None`, clears) the resolution of the active file-level conflict.
+///
+/// Returns false if the active file is not a file-level conflict.
+pub fn choose_file_resolution(app: &mut App, resolution: Option<FileResolution>) -> bool {
+    let Some(entry) = app
+        .files
+        .get_mut(app.current_file)
+        .filter(|e| e.file_conflict.is_some())
+    else {
+        return false;
+    };
+    entry.file_resolution = resolution;
+
+    let deletes = entry
+        .file_conflict
+        .as_ref()
+        .zip(resolution)
+        .is_some_and(|(conflict, r)| conflict.deletes(r));
+    let message = match resolution {
+        None => "Cleared file resolution".to_string(),
+        Some(FileResolution::Delete) => "Delete file".to_string(),
+        Some(r) if deletes => format!("{} (deletes file)", capitalize(&r.to_string())),
+        Some(r) => capitalize(&r.to_string()),
+    };
+    app.set_status_message(&message);
+    true
+}
+
+/// Uppercases the first letter of a status message.
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// Returns all files with their sessions, leaving the app empty.
+///
+/// Use this after the TUI exits to write or report every file.
+pub fn take_files(app: &mut App) -> Vec<FileEntry> {
+    stash_active(app);
+    app.current_file = 0;
+    std::mem::take(&mut app.files)
+}
+
+/// Shows or hides the file list sidebar.
+pub fn toggle_file_list(app: &mut App) {
+    app.show_file_list = !app.show_file_list;
+}
+
+/// Returns true if the file list sidebar should be drawn.
+#[must_use]
+pub fn file_list_visible(app: &App) -> bool {
+    app.show_file_list && app.files.len() > 1
+}
+
+/// Moves the active session and view state back into its entry.
+fn stash_active(app: &mut App) {
+    let Some(entry) = app.files.get_mut(app.current_file) else {
+        return;
+    };
+    entry.session = app.session.take();
+    entry.view = FileView {
+        current_hunk_index: app.current_hunk_index,
+        left_right_scroll: app.left_right_scroll,
+        result_scroll: app.result_scroll,
+        action_history: std::mem::take(&mut app.action_history),
+        candidate_index: std::mem::take(&mut app.candidate_index),
+        ai_state: std::mem::take(&mut app.ai_state),
+    };
+}
+
+/// Moves the entry's session and view state into the app.
+fn activate(app: &mut App, index: usize) {
+    let Some(entry) = app.files.get_mut(index) else {
+        app.session = None;
+        return;
+    };
+    let view = std::mem::take(&mut entry.view);
+    app.session = entry.session.take();
+    app.current_hunk_index = view.current_hunk_index;
+    app.left_right_scroll = view.left_right_scroll;
+    app.result_scroll = view.result_scroll;
+    app.action_history = view.action_history;
+    app.candidate_index = view.candidate_index;
+    app.ai_state = view.ai_state;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, hunks: usize) -> FileEntry {
+        let conflict = "<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\n";
+        let content = format!("start\n{}end", conflict.repeat(hunks));
+        let session = MergeSession::from_conflicted(&content, PathBuf::from(name)).unwrap();
+        FileEntry::new(session)
+    }
+
+    fn app_with_files() -> App {
+        let mut app = App::new();
+        set_files(
+            &mut app,
+            vec![
+                entry("a.rs", 2).with_conflict_type(ConflictType::BothModified),
+                entry("b.rs", 1).with_conflict_type(ConflictType::BothAdded),
+            ],
+        );
+        app
+    }
+
+    #[test]
+    fn set_files_activates_first_file() {
+        let app = app_with_files();
+        assert_eq!(app.current_file, 0);
+        assert_eq!(app.total_hunks(), 2);
+        assert_eq!(files(&app)[1].path(), Path::new("b.rs"));
+        assert_eq!(
+            files(&app)[1].conflict_type(),
+            Some(ConflictType::BothAdded)
+        );
+        assert!(file_list_visible(&app));
+    }
+
+    #[test]
+    fn switching_files_keeps_view_state() {
+        let mut app = app_with_files();
+        app.next_hunk();
+        app.resolve_left();
+
+        next_file(&mut app);
+        assert_eq!(app.current_file, 1);
+        assert_eq!(app.current_hunk_index(), 0);
+        assert_eq!(app.total_hunks(), 1);
+        assert!(!app.can_undo());
+
+        prev_file(&mut app);
+        assert_eq!(app.current_file, 0);
+        assert_eq!(app.current_hunk_index(), 1);
+        assert!(app.can_undo());
+        assert_eq!(file_progress(&app, 0), (1, 2));
+    }
+
+    #[test]
+    fn navigation_wraps() {
+        let mut app = app_with_files();
+        prev_file(&mut app);
+        assert_eq!(app.current_file, 1);
+        next_file(&mut app);
+        assert_eq!(app.current_file, 0);
+    }
+
+    #[test]
+    fn progress_and_unresolved_cover_all_files() {
+        let mut app = app_with_files();
+        assert_eq!(total_unresolved(&app), 3);
+
+        next_file(&mut app);
+        app.resolve_right();
+        assert_eq!(file_progress(&app, 1), (1, 1));
+        assert_eq!(file_progress(&app, 0), (0, 2));
+        assert_eq!(total_unresolved(&app), 2);
+    }
+
+    #[test]
+    fn switching_is_blocked_while_ai_loading() {
+        let mut app = app_with_files();
+        app.ai_state.pending_batch = true;
+        next_file(&mut app);
+        assert_eq!(app.current_file, 0);
+    }
+
+    #[test]
+    fn take_files_returns_every_session() {
+        let mut app = app_with_files();
+        next_file(&mut app);
+
+        let files = take_files(&mut app);
+        assert!(app.session().is_none());
+        assert_eq!(files.len(), 2);
+        let sessions: Vec<_> = files
+            .into_iter()
+            .map(|f| f.into_session().unwrap())
+            .collect();
+        assert_eq!(sessions[0].hunks().len(), 2);
+        assert_eq!(sessions[1].hunks().len(), 1);
+    }
+
+    fn modify_delete() -> FileEntry {
+        let conflict = FileConflict {
+            path: PathBuf::from("gone.rs"),
+            conflict_type: ConflictType::AddedByUsDeletedByThem,
+            ours: Some("ours\n".into()),
+            theirs: None,
+        };
+        FileEntry::file_level(PathBuf::from("gone.rs"), conflict)
+    }
+
+    #[test]
+    fn file_level_conflicts_count_as_one_hunk() {
+        let mut app = App::new();
+        set_files(&mut app, vec![entry("a.rs", 1), modify_delete()]);
+        assert_eq!(total_unresolved(&app), 2);
+        assert!(active_file_conflict(&app).is_none());
+        assert!(!choose_file_resolution(
+            &mut app,
+            Some(FileResolution::Delete)
+        ));
+
+        next_file(&mut app);
+        assert!(app.session().is_none());
+        assert_eq!(
+            files(&app)[1].conflict_type(),
+            Some(ConflictType::AddedByUsDeletedByThem)
+        );
+        assert_eq!(file_progress(&app, 1), (0, 1));
+
+        assert!(choose_file_resolution(
+            &mut app,
+            Some(FileResolution::KeepTheirs)
+        ));
+        assert_eq!(
+            app.status_message().unwrap().0,
+            "Keep theirs (deletes file)"
+        );
+        assert_eq!(file_progress(&app, 1), (1, 1));
+        assert_eq!(total_unresolved(&app), 1);
+
+        let files = take_files(&mut app);
+        assert_eq!(files[1].file_resolution(), Some(FileResolution::KeepTheirs));
+        assert!(files[1].file_conflict().is_some());
+    }
+
+    #[test]
+    fn single_file_hides_file_list() {
+        let mut app = App::new();
+        set_files(&mut app, vec![entry("a.rs", 1)]);
+        assert!(!file_list_visible(&app));
+        next_file(&mut app);
+        assert_eq!(app.current_file, 0);
+    }
+}
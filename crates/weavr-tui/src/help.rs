@@ -31,11 +31,11 @@ fn build_help_sections() -> Vec<HelpSection> {
             bindings: &[
                 HelpBinding {
                     key: "o",
-                    description: "Accept ours (left)",
+                    description: "Accept ours",
                 },
                 HelpBinding {
                     key: "t",
-                    description: "Accept theirs (right)",
+                    description: "Accept theirs",
                 },
                 HelpBinding {
                     key: "b",
@@ -45,6 +45,14 @@ fn build_help_sections() -> Vec<HelpSection> {
                     key: "B",
                     description: "Accept both (options)",
                 },
+                HelpBinding {
+                    key: "c/C",
+                    description: "Next/prev candidate",
+                },
+                HelpBinding {
+                    key: "a",
+                    description: "Accept shown candidate",
+                },
                 HelpBinding {
                     key: "e",
                     description: "Edit in $EDITOR",
@@ -53,6 +61,10 @@ fn build_help_sections() -> Vec<HelpSection> {
                     key: "x",
                     description: "Clear resolution",
                 },
+                HelpBinding {
+                    key: "D",
+                    description: "Delete file (delete conflict)",
+                },
                 HelpBinding {
                     key: "u",
                     description: "Undo last action",
@@ -78,6 +90,14 @@ fn build_help_sections() -> Vec<HelpSection> {
                     key: "gg/G",
                     description: "First/last hunk",
                 },
+                HelpBinding {
+                    key: "]f/[f",
+                    description: "Next/prev file",
+                },
+                HelpBinding {
+                    key: "F",
+                    description: "Toggle file list",
+                },
                 HelpBinding {
                     key: "Tab",
                     description: "Cycle panes",
@@ -153,6 +173,14 @@ fn build_help_sections() -> Vec<HelpSection> {
                     key: ":q!",
                     description: "Force quit",
                 },
+                HelpBinding {
+                    key: ":auto",
+                    description: "Resolve all trivial hunks",
+                },
+                HelpBinding {
+                    key: ":swap",
+                    description: "Swap left and right panes",
+                },
                 HelpBinding {
                     key: ":help",
                     description: "Show this help",
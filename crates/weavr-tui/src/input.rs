@@ -73,6 +73,10 @@ pub enum Command {
     ForceQuit,
     /// Show help (`:help`).
     Help,
+    /// Resolve all trivial hunks (`:auto`).
+    AutoResolve,
+    /// Swap the left and right panes (`:swap`).
+    Swap,
     /// Unknown or invalid command.
     Unknown(String),
 }
@@ -89,6 +93,8 @@ impl Command {
             "wq" | "x" => Self::WriteQuit,
             "q!" => Self::ForceQuit,
             "help" => Self::Help,
+            "auto" => Self::AutoResolve,
+            "swap" => Self::Swap,
             other => Self::Unknown(other.to_string()),
         }
     }
@@ -102,6 +108,8 @@ impl Command {
             Self::WriteQuit => "write and quit",
             Self::ForceQuit => "force quit",
             Self::Help => "help",
+            Self::AutoResolve => "auto-resolve",
+            Self::Swap => "swap sides",
             Self::Unknown(_) => "unknown command",
         }
     }
@@ -177,6 +185,16 @@ mod tests {
         assert_eq!(Command::parse("  help  "), Command::Help);
     }
 
+    #[test]
+    fn parse_auto() {
+        assert_eq!(Command::parse("auto"), Command::AutoResolve);
+    }
+
+    #[test]
+    fn parse_swap() {
+        assert_eq!(Command::parse("swap"), Command::Swap);
+    }
+
     #[test]
     fn parse_unknown() {
         assert_eq!(Command::parse("foo"), Command::Unknown("foo".to_string()));
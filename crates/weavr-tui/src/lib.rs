@@ -4,8 +4,11 @@
 //!
 //! Key features:
 //! - Three-pane layout (left, right, result)
+//! - Multi-file sessions with a file list sidebar
 //! - Keyboard-first navigation
 //! - Hunk-based conflict resolution
+//! - Autosave of in-progress sessions for resuming later
+//! - Side names by meaning during rebases, cherry-picks and reverts
 //! - Theming support
 //!
 //! The TUI is a thin wrapper around weavr-core. It displays state and
@@ -14,22 +17,30 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
-use weavr_core::{ConflictHunk, MergeSession};
+use weavr_core::{ConflictHunk, HunkId, MergeSession, SessionSnapshot};
+use weavr_git::SideNames;
 
 /// Timeout for multi-key sequences like 'gg'.
 const KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(500);
 
 pub mod ai;
+pub mod candidate;
 pub mod dialog;
 pub mod diff;
 pub mod editor;
 pub mod event;
+pub mod files;
 pub mod help;
 pub mod input;
 pub mod navigation;
+pub mod persist;
 pub mod resolution;
+pub mod save;
+pub mod sides;
 pub mod theme;
 pub mod ui;
 use input::{Command, Dialog, InputMode, KeySequence};
@@ -102,6 +113,22 @@ pub struct App {
     pub(crate) ai_handle: Option<ai::AiHandle>,
     /// AI suggestion state for UI rendering.
     pub(crate) ai_state: ai::AiState,
+    /// Which candidate is shown for each hunk with proposals.
+    pub(crate) candidate_index: HashMap<HunkId, usize>,
+    /// All loaded files; the active one's session is held in `session`.
+    pub(crate) files: Vec<files::FileEntry>,
+    /// Index of the active file in `files`.
+    pub(crate) current_file: usize,
+    /// Whether the file list sidebar is shown (when there are several files).
+    pub(crate) show_file_list: bool,
+    /// Where in-progress sessions are autosaved (set by CLI).
+    pub(crate) session_store: Option<persist::SessionStore>,
+    /// The last snapshot saved for each file, to skip unchanged saves.
+    pub(crate) saved_snapshots: HashMap<PathBuf, SessionSnapshot>,
+    /// What each side stands for in the Git operation (set by CLI).
+    pub(crate) side_names: Option<SideNames>,
+    /// Whether the left pane shows "theirs" and the right pane "ours".
+    pub(crate) swap_sides: bool,
 }
 
 impl App {
@@ -127,6 +154,14 @@ impl App {
             diff_config: diff::DiffConfig::default(),
             ai_handle: None,
             ai_state: ai::AiState::default(),
+            candidate_index: HashMap::new(),
+            files: Vec::new(),
+            current_file: 0,
+            show_file_list: true,
+            session_store: None,
+            saved_snapshots: HashMap::new(),
+            side_names: None,
+            swap_sides: false,
         }
     }
 
@@ -152,12 +187,89 @@ impl App {
             diff_config: diff::DiffConfig::default(),
             ai_handle: None,
             ai_state: ai::AiState::default(),
+            candidate_index: HashMap::new(),
+            files: Vec::new(),
+            current_file: 0,
+            show_file_list: true,
+            session_store: None,
+            saved_snapshots: HashMap::new(),
+            side_names: None,
+            swap_sides: false,
         }
     }
 
     /// Sets the merge session to display.
+    ///
+    /// Unresolved hunks receive candidate resolutions from the built-in
+    /// text strategies (see [`candidate::default_engine`]).
     pub fn set_session(&mut self, session: MergeSession) {
-        self.session = Some(session);
+        files::set_files(self, vec![files::FileEntry::new(session)]);
+    }
+
+    /// Loads several files and activates the first one.
+    pub fn set_files(&mut self, entries: Vec<files::FileEntry>) {
+        files::set_files(self, entries);
+    }
+
+    /// Returns all files with their sessions, leaving the app empty.
+    pub fn take_files(&mut self) -> Vec<files::FileEntry> {
+        files::take_files(self)
+    }
+
+    /// Returns the loaded files.
+    #[must_use]
+    pub fn files(&self) -> &[files::FileEntry] {
+        files::files(self)
+    }
+
+    /// Returns the index of the active file.
+    #[must_use]
+    pub fn current_file_index(&self) -> usize {
+        self.current_file
+    }
+
+    /// Moves to the next file, wrapping around.
+    pub fn next_file(&mut self) {
+        files::next_file(self);
+    }
+
+    /// Moves to the previous file, wrapping around.
+    pub fn prev_file(&mut self) {
+        files::prev_file(self);
+    }
+
+    /// Moves to the file at `index`.
+    pub fn go_to_file(&mut self, index: usize) {
+        files::go_to_file(self, index);
+    }
+
+    /// Shows or hides the file list sidebar.
+    pub fn toggle_file_list(&mut self) {
+        files::toggle_file_list(self);
+    }
+
+    /// Enables autosaving sessions to `store` after each change.
+    pub fn set_session_store(&mut self, store: persist::SessionStore) {
+        self.session_store = Some(store);
+    }
+
+    /// Offers resolutions recorded in earlier merges as candidates in all
+    /// loaded files.
+    pub fn add_recorded_candidates(&mut self, recorded: &weavr_core::RecordedResolutions) {
+        candidate::add_recorded_candidates(self, recorded);
+    }
+
+    /// Names the sides for the Git operation in progress.
+    ///
+    /// With `own_change_left`, the panes are swapped when the user's own
+    /// change is "theirs", as during a rebase.
+    pub fn set_side_names(&mut self, names: SideNames, own_change_left: bool) {
+        sides::set_side_names(self, names, own_change_left);
+    }
+
+    /// Swaps which side is shown in the left and right panes.
+    pub fn toggle_swap(&mut self) {
+        sides::toggle_swap(self);
     }
 
     /// Returns a reference to the current session, if any.
@@ -262,12 +374,14 @@ impl App {
         navigation::prev_unresolved_hunk(self);
     }
 
-    /// Resolves the current hunk by accepting the left (ours) content.
+    /// Resolves the current hunk by accepting the left (ours) content,
+    /// even when the panes are swapped.
     pub fn resolve_left(&mut self) {
         resolution::resolve_left(self);
     }
 
-    /// Resolves the current hunk by accepting the right (theirs) content.
+    /// Resolves the current hunk by accepting the right (theirs) content,
+    /// even when the panes are swapped.
     pub fn resolve_right(&mut self) {
         resolution::resolve_right(self);
     }
@@ -277,6 +391,31 @@ impl App {
         resolution::resolve_both(self);
     }
 
+    /// Deletes the file of the active file-level conflict (delete/modify).
+    pub fn delete_file(&mut self) {
+        resolution::delete_file(self);
+    }
+
+    /// Resolves every trivial hunk in the session.
+    pub fn resolve_trivial(&mut self) {
+        resolution::resolve_trivial(self);
+    }
+
+    /// Shows the next candidate resolution for the current hunk.
+    pub fn next_candidate(&mut self) {
+        candidate::next_candidate(self);
+    }
+
+    /// Shows the previous candidate resolution for the current hunk.
+    pub fn prev_candidate(&mut self) {
+        candidate::prev_candidate(self);
+    }
+
+    /// Commits the currently shown candidate as the hunk's resolution.
+    pub fn accept_candidate(&mut self) {
+        candidate::accept_candidate(self);
+    }
+
     /// Clears the resolution for the current hunk, returning it to unresolved state.
     pub fn clear_current_resolution(&mut self) {
         resolution::clear_current_resolution(self);
@@ -400,14 +539,20 @@ impl App {
     pub fn execute_command(&mut self) {
         let cmd = Command::parse(&self.command_buffer);
         match cmd {
-            Command::Write => self.write_file(),
+            Command::Write => {
+                self.write_file();
+            }
             Command::Quit => self.try_quit(),
             Command::WriteQuit => {
-                // TODO: Implement :wq when file writing is implemented
-                self.set_status_message(":wq not yet implemented - use :q! to force quit");
+                // Only the current file is written; others may be unresolved
+                if self.write_file() {
+                    self.try_quit();
+                }
             }
             Command::ForceQuit => self.quit(),
             Command::Help => self.show_help(),
+            Command::AutoResolve => self.resolve_trivial(),
+            Command::Swap => self.toggle_swap(),
             Command::Unknown(s) => {
                 if !s.is_empty() {
                     self.set_status_message(&format!("Unknown command: {s}"));
@@ -417,39 +562,24 @@ impl App {
         self.exit_command_mode();
     }
 
-    /// Writes the resolved file. Currently a placeholder.
-    fn write_file(&mut self) {
-        if self.has_unresolved_hunks() {
-            let count = self.unresolved_count();
-            self.set_status_message(&format!("Cannot save: {count} unresolved hunks"));
-        } else {
-            // TODO: Implement actual file writing in Phase 7
-            self.set_status_message("File saved (not yet implemented)");
-        }
+    /// Writes the resolved file to disk.
+    ///
+    /// Returns true if the file was written; failures are shown in the
+    /// status bar.
+    pub fn write_file(&mut self) -> bool {
+        save::write_file(self)
     }
 
-    /// Attempts to quit, showing a warning if there are unresolved hunks.
+    /// Attempts to quit, showing a warning if any file has unresolved hunks.
     fn try_quit(&mut self) {
-        if self.has_unresolved_hunks() {
-            let count = self.unresolved_count();
+        let count = files::total_unresolved(self);
+        if count > 0 {
             self.set_status_message(&format!("{count} unresolved hunks. Use :q! to force quit"));
         } else {
             self.quit();
         }
     }
 
-    /// Returns true if there are unresolved hunks.
-    fn has_unresolved_hunks(&self) -> bool {
-        self.unresolved_count() > 0
-    }
-
-    /// Returns the number of unresolved hunks.
-    fn unresolved_count(&self) -> usize {
-        self.session
-            .as_ref()
-            .map_or(0, |s| s.unresolved_hunks().len())
-    }
-
     /// Shows the help dialog.
     pub fn show_help(&mut self) {
         dialog::show_help(self);
@@ -574,6 +704,7 @@ fn run_event_loop(terminal: &mut ratatui::DefaultTerminal, app: &mut App) -> std
             // Apply result if editor succeeded
             if let Some(new_content) = result {
                 app.apply_editor_result(&new_content);
+                persist::autosave(app);
             } else {
                 app.set_status_message("Editor cancelled");
             }
@@ -587,6 +718,7 @@ fn run_event_loop(terminal: &mut ratatui::DefaultTerminal, app: &mut App) -> std
 
         if let Some(evt) = event::poll_event(Duration::from_millis(100))? {
             event::handle_event(app, &evt);
+            persist::autosave(app);
         }
     }
 
@@ -663,6 +795,39 @@ mod tests {
         assert!(app.should_quit());
     }
 
+    #[test]
+    fn write_quit_refuses_while_other_files_are_unresolved() {
+        let dir = tempfile::tempdir().unwrap();
+        let conflict = "a\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\nb\n";
+        let entry = |name: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, conflict).unwrap();
+            files::FileEntry::new(MergeSession::from_conflicted(conflict, path).unwrap())
+        };
+        let mut app = App::new();
+        app.set_files(vec![entry("a.rs"), entry("b.rs")]);
+        app.resolve_left();
+
+        app.enter_command_mode();
+        app.command_buffer = "wq".to_string();
+        app.execute_command();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a.rs")).unwrap(),
+            "a\nleft\nb\n"
+        );
+        assert!(!app.should_quit());
+        assert_eq!(
+            app.status_message().unwrap().0,
+            "1 unresolved hunks. Use :q! to force quit"
+        );
+
+        app.enter_command_mode();
+        app.command_buffer = "q!".to_string();
+        app.execute_command();
+        assert!(app.should_quit());
+    }
+
     #[test]
     fn app_set_session() {
         use std::path::PathBuf;
@@ -901,4 +1066,35 @@ mod tests {
         // Second call returns None
         assert!(app.take_editor_pending().is_none());
     }
+
+    #[test]
+    fn auto_command_resolves_trivial_hunks() {
+        use std::path::PathBuf;
+
+        let version = |content: &str| weavr_core::FileVersion {
+            path: PathBuf::from("test.rs"),
+            content: content.to_string(),
+        };
+        let input = weavr_core::MergeInput {
+            left: version("a\ny  \nb\nours\nc"),
+            right: version("a\ny\nb\ntheirs\nc"),
+            base: Some(version("a\nx\nb\nbase\nc")),
+        };
+        let mut app = App::new();
+        app.set_session(weavr_core::MergeSession::new(input).unwrap());
+        assert_eq!(files::total_unresolved(&app), 2);
+
+        app.enter_command_mode();
+        app.command_buffer = "auto".to_string();
+        app.execute_command();
+
+        assert_eq!(files::total_unresolved(&app), 1);
+        assert_eq!(
+            app.status_message().unwrap().0,
+            "Auto-resolved 1 trivial hunk"
+        );
+
+        app.undo();
+        assert_eq!(files::total_unresolved(&app), 2);
+    }
 }
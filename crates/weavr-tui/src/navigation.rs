@@ -74,7 +74,7 @@ pub fn next_unresolved_hunk(app: &mut App) {
         // Search forward from current position
         for i in 1..=total {
             let idx = (app.current_hunk_index + i) % total;
-            if matches!(hunks[idx].state, HunkState::Unresolved) {
+            if !matches!(hunks[idx].state, HunkState::Resolved(_)) {
                 app.current_hunk_index = idx;
                 reset_scroll(app);
                 return;
@@ -95,7 +95,7 @@ pub fn prev_unresolved_hunk(app: &mut App) {
         // Search backward from current position
         for i in 1..=total {
             let idx = (app.current_hunk_index + total - i) % total;
-            if matches!(hunks[idx].state, HunkState::Unresolved) {
+            if !matches!(hunks[idx].state, HunkState::Resolved(_)) {
                 app.current_hunk_index = idx;
                 reset_scroll(app);
                 return;
@@ -0,0 +1,351 @@
+//! Saving in-progress sessions so they can be resumed.
+//!
+//! This module handles:
+//! - A [`JsonStore`] directory of JSON files, shared with the recorded
+//!   resolutions kept by the CLI
+//! - Storing one [`SessionSnapshot`] per file as JSON (under `.git/weavr/sessions/`)
+//! - Autosaving the active file after each change
+//! - Removing a file's snapshot once it is resolved
+//!
+//! Snapshots record a fingerprint of the conflicted input; restoring one
+//! onto a changed file is refused by [`MergeSession::restore`](weavr_core::MergeSession::restore).
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use weavr_core::{Fnv1a, SessionSnapshot};
+
+use crate::save::write_atomic;
+use crate::App;
+
+/// A directory of JSON files, one value per file, named `<name>.json`.
+///
+/// Names are used as given; callers make sure they cannot leave the
+/// directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonStore {
+    dir: PathBuf,
+}
+
+impl JsonStore {
+    /// Creates a store that keeps its files in `dir`.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the directory holding the files.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the file for `name`.
+    #[must_use]
+    pub fn file(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+
+    /// Loads the value stored as `name`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or
+    /// [`io::ErrorKind::InvalidData`] if it does not hold a valid value.
+    pub fn load<T: DeserializeOwned>(&self, name: &str) -> io::Result<Option<T>> {
+        match std::fs::read_to_string(self.file(name)) {
+            Ok(content) => parse(&content).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Stores `value` as `name`, replacing any previous value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or file cannot be written.
+    pub fn save<T: Serialize>(&self, name: &str, value: &T) -> io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string_pretty(value)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        write_atomic(&self.file(name), &json)
+    }
+
+    /// Returns every stored value, in no particular order.
+    ///
+    /// A missing directory gives an empty list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read, or
+    /// [`io::ErrorKind::InvalidData`] if it does not hold a valid value.
+    pub fn list<T: DeserializeOwned>(&self) -> io::Result<Vec<T>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut values = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                values.push(parse(&std::fs::read_to_string(&path)?)?);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Removes the value stored as `name`. Returns `false` if there was none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be removed.
+    pub fn remove(&self, name: &str) -> io::Result<bool> {
+        match std::fs::remove_file(self.file(name)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Parses a stored value, reporting bad JSON as [`io::ErrorKind::InvalidData`].
+fn parse<T: DeserializeOwned>(content: &str) -> io::Result<T> {
+    serde_json::from_str(content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// A snapshot as stored on disk, with the file it belongs to.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredSnapshot {
+    /// Absolute path of the conflicted file.
+    path: PathBuf,
+    #[serde(flatten)]
+    snapshot: SessionSnapshot,
+}
+
+/// On-disk store of session snapshots, one JSON file per conflicted file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionStore {
+    store: JsonStore,
+}
+
+impl SessionStore {
+    /// Creates a store that keeps snapshots in `dir`.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            store: JsonStore::new(dir),
+        }
+    }
+
+    /// Creates the store for a repository, in `<git-dir>/weavr/sessions`.
+    #[must_use]
+    pub fn for_git_dir(git_dir: &Path) -> Self {
+        Self::new(git_dir.join("weavr").join("sessions"))
+    }
+
+    /// Returns the directory holding the snapshots.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        self.store.dir()
+    }
+
+    /// Loads the snapshot saved for `path`, if any.
+    ///
+    /// A snapshot stored for a different file under the same name is
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot file cannot be read, or
+    /// [`io::ErrorKind::InvalidData`] if it is not a valid snapshot.
+    pub fn load(&self, path: &Path) -> io::Result<Option<SessionSnapshot>> {
+        let absolute = absolute(path);
+        let stored: Option<StoredSnapshot> = self.store.load(&name_for(&absolute))?;
+        Ok(stored
+            .filter(|stored| stored.path == absolute)
+            .map(|stored| stored.snapshot))
+    }
+
+    /// Saves the snapshot for `path`, replacing any previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store directory or snapshot file cannot be written.
+    pub fn save(&self, path: &Path, snapshot: &SessionSnapshot) -> io::Result<()> {
+        let stored = StoredSnapshot {
+            path: absolute(path),
+            snapshot: snapshot.clone(),
+        };
+        self.store.save(&name_for(&stored.path), &stored)
+    }
+
+    /// Removes the snapshot for `path`. Missing snapshots are not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot file exists but cannot be removed.
+    pub fn remove(&self, path: &Path) -> io::Result<()> {
+        let absolute = absolute(path);
+        // Leave another file's snapshot stored under the same name alone
+        if let Ok(None) = self.load(&absolute) {
+            return Ok(());
+        }
+        self.store.remove(&name_for(&absolute)).map(drop)
+    }
+
+    /// Returns the snapshot file for the absolute path `absolute`.
+    #[cfg(test)]
+    fn file_for(&self, absolute: &Path) -> PathBuf {
+        self.store.file(&name_for(absolute))
+    }
+}
+
+/// Returns the name of the snapshot for the absolute path `absolute`.
+///
+/// The name is a hash of the path, so it stays short however deep the file
+/// is. The path itself is stored in the snapshot.
+fn name_for(absolute: &Path) -> String {
+    let mut hash = Fnv1a::new();
+    hash.feed(absolute.as_os_str().as_encoded_bytes());
+    hash.finish_hex()
+}
+
+/// Returns `path` made absolute, so the same file maps to the same
+/// snapshot whichever directory weavr runs from.
+fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Saves the active file's snapshot if it changed since the last save.
+///
+/// Does nothing without a store. Files with no decisions yet are not
+/// saved. Failures are shown in the status bar.
+pub fn autosave(app: &mut App) {
+    let (Some(store), Some(session)) = (app.session_store.as_ref(), app.session.as_ref()) else {
+        return;
+    };
+    let path = session.input().left.path.clone();
+    let snapshot = session.snapshot(&app.action_history);
+
+    let previous = app.saved_snapshots.get(&path);
+    if previous == Some(&snapshot) {
+        return;
+    }
+    if previous.is_none() && snapshot.resolutions.is_empty() && !snapshot.history.can_undo() {
+        return;
+    }
+
+    match store.save(&path, &snapshot) {
+        Ok(()) => {
+            app.saved_snapshots.insert(path, snapshot);
+        }
+        Err(e) => app.set_status_message(&format!("Autosave failed: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weavr_core::MergeSession;
+
+    use super::*;
+
+    fn app_with_store(dir: &Path) -> (App, PathBuf) {
+        let path = dir.join("file.txt");
+        let content = "a\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\nb";
+        std::fs::write(&path, content).unwrap();
+
+        let mut app = App::new();
+        app.set_session(MergeSession::from_conflicted(content, path.clone()).unwrap());
+        app.set_session_store(SessionStore::new(dir.join("sessions")));
+        (app, path)
+    }
+
+    #[test]
+    fn store_round_trips_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut app, path) = app_with_store(dir.path());
+        app.resolve_right();
+        let snapshot = app.session().unwrap().snapshot(&app.action_history);
+
+        let store = SessionStore::new(dir.path().join("sessions"));
+        assert_eq!(store.load(&path).unwrap(), None);
+        store.save(&path, &snapshot).unwrap();
+        assert_eq!(store.load(&path).unwrap(), Some(snapshot));
+
+        store.remove(&path).unwrap();
+        assert_eq!(store.load(&path).unwrap(), None);
+        store.remove(&path).unwrap();
+    }
+
+    #[test]
+    fn load_reports_corrupt_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SessionStore::new(dir.path());
+        let path = dir.path().join("file.txt");
+        std::fs::write(store.file_for(&absolute(&path)), "not json").unwrap();
+
+        let err = store.load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        store.remove(&path).unwrap();
+        assert_eq!(store.load(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn snapshots_are_named_by_hash_and_record_their_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let deep = dir.path().join("d".repeat(200)).join("f".repeat(200));
+        std::fs::create_dir_all(&deep).unwrap();
+        let (mut app, path) = app_with_store(&deep);
+        app.resolve_right();
+        let snapshot = app.session().unwrap().snapshot(&app.action_history);
+
+        let store = SessionStore::new(dir.path().join("sessions"));
+        store.save(&path, &snapshot).unwrap();
+        assert_eq!(store.load(&path).unwrap(), Some(snapshot.clone()));
+
+        let file = store.file_for(&absolute(&path));
+        assert_eq!(
+            file.file_name().unwrap().len(),
+            "0123456789abcdef.json".len()
+        );
+        let stored: StoredSnapshot =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(stored.path, absolute(&path));
+
+        // A snapshot for another file under the same name is not used or removed
+        let other = dir.path().join("other.txt");
+        std::fs::copy(&file, store.file_for(&absolute(&other))).unwrap();
+        assert_eq!(store.load(&other).unwrap(), None);
+        store.remove(&other).unwrap();
+        assert!(store.file_for(&absolute(&other)).exists());
+    }
+
+    #[test]
+    fn autosave_writes_after_changes_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut app, path) = app_with_store(dir.path());
+        let store = SessionStore::new(dir.path().join("sessions"));
+
+        autosave(&mut app);
+        assert_eq!(store.load(&path).unwrap(), None);
+
+        app.resolve_left();
+        autosave(&mut app);
+        let saved = store.load(&path).unwrap().unwrap();
+        assert_eq!(saved.resolutions.len(), 1);
+        assert_eq!(saved.history.undo_count(), 1);
+
+        // Undoing everything is still saved, so the cleared state resumes too
+        app.undo();
+        autosave(&mut app);
+        let saved = store.load(&path).unwrap().unwrap();
+        assert!(saved.resolutions.is_empty());
+        assert_eq!(saved.history.redo_count(), 1);
+    }
+}
@@ -2,32 +2,93 @@
 //!
 //! This module handles:
 //! - Applying resolutions (left, right, both, manual)
+//! - Resolving trivial hunks in bulk
 //! - Clearing resolutions
 //! - Undo/redo support
 
 use weavr_core::{AcceptBothOptions, Action, ConflictHunk, Resolution};
+use weavr_git::FileResolution;
 
-use crate::App;
+use crate::files::{active_file_conflict, choose_file_resolution};
+use crate::{sides, App};
 
-/// Resolves the current hunk by accepting the left (ours) content.
+/// Resolves the current hunk by accepting the left (ours) content, even
+/// when the panes are swapped.
+///
+/// For a file-level conflict, keeps our version of the file.
 pub fn resolve_left(app: &mut App) {
-    apply_resolution(app, "Accept ours", Resolution::accept_left);
+    if choose_file_resolution(app, Some(FileResolution::KeepOurs)) {
+        return;
+    }
+    let action = format!("Accept {}", sides::ours_role(app));
+    apply_resolution(app, &action, Resolution::accept_left);
 }
 
-/// Resolves the current hunk by accepting the right (theirs) content.
+/// Resolves the current hunk by accepting the right (theirs) content, even
+/// when the panes are swapped.
+///
+/// For a file-level conflict, keeps their version of the file.
 pub fn resolve_right(app: &mut App) {
-    apply_resolution(app, "Accept theirs", Resolution::accept_right);
+    if choose_file_resolution(app, Some(FileResolution::KeepTheirs)) {
+        return;
+    }
+    let action = format!("Accept {}", sides::theirs_role(app));
+    apply_resolution(app, &action, Resolution::accept_right);
 }
 
 /// Resolves the current hunk by accepting both sides (left then right).
 pub fn resolve_both(app: &mut App) {
+    if active_file_conflict(app).is_some() {
+        app.set_status_message(
+            "File conflict: choose o (keep ours), t (keep theirs) or D (delete)",
+        );
+        return;
+    }
     apply_resolution(app, "Accept both", |hunk| {
         Resolution::accept_both(hunk, &AcceptBothOptions::default())
     });
 }
 
+/// Resolves every trivial hunk (identical sides, one-sided change,
+/// trailing-whitespace-only divergence) in the session.
+///
+/// Each resolved hunk is recorded in the action history, so the pass can be
+/// undone hunk by hunk.
+pub fn resolve_trivial(app: &mut App) {
+    let Some(session) = app.session.as_mut() else {
+        return;
+    };
+
+    let resolved = session.resolve_trivial();
+    for hunk_id in &resolved {
+        if let Some(resolution) = session.resolutions().get(hunk_id) {
+            app.action_history.record(Action::SetResolution {
+                hunk_id: *hunk_id,
+                old: None,
+                new: resolution.clone(),
+            });
+        }
+    }
+
+    match resolved.len() {
+        0 => app.set_status_message("No trivial hunks to resolve"),
+        1 => app.set_status_message("Auto-resolved 1 trivial hunk"),
+        n => app.set_status_message(&format!("Auto-resolved {n} trivial hunks")),
+    }
+}
+
+/// Deletes the file of the active file-level conflict.
+pub fn delete_file(app: &mut App) {
+    if !choose_file_resolution(app, Some(FileResolution::Delete)) {
+        app.set_status_message("Only files with a delete conflict can be deleted");
+    }
+}
+
 /// Clears the resolution for the current hunk, returning it to unresolved state.
 pub fn clear_current_resolution(app: &mut App) {
+    if choose_file_resolution(app, None) {
+        return;
+    }
     // Get hunk info and current resolution for undo
     let Some((hunk_id, prev)) = app.session.as_ref().and_then(|session| {
         session
@@ -0,0 +1,171 @@
+//! Saving the resolved file.
+//!
+//! This module handles:
+//! - Running the session through apply and validate
+//! - Writing the output atomically (temp file + rename)
+//! - Reporting failures in the status bar
+
+use std::io::Write;
+use std::path::Path;
+
+use crate::files::active_file_conflict;
+use crate::App;
+
+/// Writes the resolved file to the session's path.
+///
+/// The session is applied and validated on a copy, so the user can keep
+/// editing after saving. Returns true if the file was written; on failure
+/// the reason is shown in the status bar.
+///
+/// File-level conflicts are not written here: their resolution is carried
+/// out with git after the TUI exits, so this only checks one was chosen.
+pub fn write_file(app: &mut App) -> bool {
+    if let Some((_, choice)) = active_file_conflict(app) {
+        if choice.is_some() {
+            app.set_status_message("File resolution is applied with git on exit");
+            return true;
+        }
+        app.set_status_message("Cannot save: choose o (keep ours), t (keep theirs) or D (delete)");
+        return false;
+    }
+
+    let Some(session) = app.session.as_ref() else {
+        app.set_status_message("No file to save");
+        return false;
+    };
+
+    let unresolved = session.unresolved_hunks().len();
+    if unresolved > 0 {
+        app.set_status_message(&format!("Cannot save: {unresolved} unresolved hunks"));
+        return false;
+    }
+
+    let path = session.input().left.path.clone();
+    let mut validated = session.clone();
+    let content = match validated.apply() {
+        Ok(content) => content,
+        Err(e) => {
+            app.set_status_message(&format!("Cannot save: {e}"));
+            return false;
+        }
+    };
+    if let Err(e) = validated.validate() {
+        app.set_status_message(&format!("Cannot save: {e}"));
+        return false;
+    }
+
+    match write_atomic(&path, &content) {
+        Ok(()) => {
+            app.set_status_message(&format!("Wrote {}", path.display()));
+            true
+        }
+        Err(e) => {
+            app.set_status_message(&format!("Write failed: {}: {e}", path.display()));
+            false
+        }
+    }
+}
+
+/// Writes `content` to `path` atomically.
+///
+/// The content goes to a temporary file in the same directory, which is
+/// then renamed over the target. An existing file's permissions are kept.
+///
+/// # Errors
+///
+/// Returns an error if the temporary file cannot be created, written,
+/// or renamed into place.
+pub fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    tmp.write_all(content.as_bytes())?;
+    tmp.as_file().sync_all()?;
+
+    if let Ok(metadata) = std::fs::metadata(path) {
+        std::fs::set_permissions(tmp.path(), metadata.permissions())?;
+    }
+
+    tmp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use weavr_core::MergeSession;
+
+    use super::*;
+
+    fn app_for(path: PathBuf) -> App {
+        let content = "a\n<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature\nb";
+        std::fs::write(&path, content).unwrap();
+        let session = MergeSession::from_conflicted(content, path).unwrap();
+        let mut app = App::new();
+        app.set_session(session);
+        app
+    }
+
+    #[test]
+    fn write_refuses_unresolved_hunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        let mut app = app_for(path.clone());
+
+        assert!(!write_file(&mut app));
+        assert_eq!(
+            app.status_message().unwrap().0,
+            "Cannot save: 1 unresolved hunks"
+        );
+        assert!(std::fs::read_to_string(&path).unwrap().contains("<<<<<<<"));
+    }
+
+    #[test]
+    fn write_saves_resolved_content_and_keeps_session_editable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        let mut app = app_for(path.clone());
+        app.resolve_left();
+
+        assert!(write_file(&mut app));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\nleft\nb");
+
+        // The session is still editable after saving
+        app.resolve_right();
+        assert!(write_file(&mut app));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\nright\nb");
+    }
+
+    #[test]
+    fn write_reports_io_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        let mut app = app_for(path);
+        app.resolve_left();
+        drop(dir);
+
+        assert!(!write_file(&mut app));
+        assert!(app.status_message().unwrap().0.starts_with("Write failed:"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("script.sh");
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        write_atomic(&path, "new").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+}
@@ -0,0 +1,135 @@
+//! Naming and arranging the conflict sides.
+//!
+//! This module handles:
+//! - Naming the sides by meaning for the Git operation in progress
+//!   (e.g. "upstream" and "your commit being replayed" during a rebase)
+//! - Swapping the panes so the user's own change is shown on the left
+//!
+//! Swapping only moves the panes: `o` still accepts ours and `t` theirs,
+//! and `b` still puts ours first, whichever pane shows them.
+
+use weavr_git::SideNames;
+
+use crate::App;
+
+/// Sets the side names for the operation in progress.
+///
+/// With `own_change_left`, the panes are swapped when the user's own
+/// change is "theirs" (during a rebase). Names for no operation are
+/// ignored, so marker labels are shown instead.
+pub fn set_side_names(app: &mut App, names: SideNames, own_change_left: bool) {
+    if !names.operation.has_conflicts() {
+        return;
+    }
+    app.swap_sides = own_change_left && names.own_change_is_theirs();
+    app.side_names = Some(names);
+}
+
+/// Swaps which side is shown in the left and right panes.
+pub fn toggle_swap(app: &mut App) {
+    app.swap_sides = !app.swap_sides;
+    let left = if app.swap_sides {
+        theirs_role(app)
+    } else {
+        ours_role(app)
+    };
+    app.set_status_message(&format!("Showing {left} on the left"));
+}
+
+/// Returns what "ours" stands for, e.g. `upstream`.
+pub(crate) fn ours_role(app: &App) -> &'static str {
+    app.side_names
+        .as_ref()
+        .map_or("ours", |names| names.ours.role)
+}
+
+/// Returns what "theirs" stands for, e.g. `your commit being replayed`.
+pub(crate) fn theirs_role(app: &App) -> &'static str {
+    app.side_names
+        .as_ref()
+        .map_or("theirs", |names| names.theirs.role)
+}
+
+#[cfg(test)]
+mod tests {
+    use weavr_core::MergeSession;
+    use weavr_git::GitOperation;
+
+    use super::*;
+
+    fn app() -> App {
+        let content = "a\n<<<<<<< HEAD\nupstream\n=======\nmine\n>>>>>>> abc123\nb";
+        let mut app = App::new();
+        app.set_session(MergeSession::from_conflicted(content, "file.txt".into()).unwrap());
+        app
+    }
+
+    #[test]
+    fn own_change_left_swaps_only_during_rebase() {
+        let mut app = app();
+        set_side_names(
+            &mut app,
+            SideNames::for_operation(GitOperation::Merge),
+            true,
+        );
+        assert!(!app.swap_sides);
+
+        set_side_names(
+            &mut app,
+            SideNames::for_operation(GitOperation::Rebase),
+            true,
+        );
+        assert!(app.swap_sides);
+        assert_eq!(ours_role(&app), "upstream");
+
+        set_side_names(
+            &mut app,
+            SideNames::for_operation(GitOperation::Rebase),
+            false,
+        );
+        assert!(!app.swap_sides);
+    }
+
+    #[test]
+    fn names_without_operation_are_ignored() {
+        let mut app = app();
+        set_side_names(&mut app, SideNames::for_operation(GitOperation::None), true);
+        assert!(app.side_names.is_none());
+        assert_eq!(theirs_role(&app), "theirs");
+    }
+
+    #[test]
+    fn keys_keep_accepting_ours_and_theirs_when_swapped() {
+        let mut app = app();
+        set_side_names(
+            &mut app,
+            SideNames::for_operation(GitOperation::Rebase),
+            true,
+        );
+
+        app.resolve_left();
+        let session = app.session().unwrap();
+        assert_eq!(
+            session.resolutions().values().next().unwrap().content,
+            "upstream"
+        );
+        assert_eq!(
+            app.status_message().map(|(m, _)| m.as_str()),
+            Some("Accept upstream")
+        );
+
+        app.resolve_both();
+        let session = app.session().unwrap();
+        assert_eq!(
+            session.resolutions().values().next().unwrap().content,
+            "upstream\nmine"
+        );
+
+        app.toggle_swap();
+        assert!(!app.swap_sides);
+        assert_eq!(
+            app.status_message().map(|(m, _)| m.as_str()),
+            Some("Showing upstream on the left")
+        );
+    }
+}
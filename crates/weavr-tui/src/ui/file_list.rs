@@ -0,0 +1,117 @@
+//! File list sidebar for multi-file sessions.
+//!
+//! Shows each loaded file with its Git conflict type and how many of its
+//! hunks are resolved. The active file is highlighted.
+
+use crate::files::file_progress;
+use crate::App;
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+/// Renders the file list sidebar.
+pub fn render_file_list(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = app.theme();
+    let current = app.current_file_index();
+
+    let mut lines = Vec::new();
+    for (index, entry) in app.files().iter().enumerate() {
+        let (resolved, total) = file_progress(app, index);
+        let is_current = index == current;
+
+        let marker = if is_current { "▶ " } else { "  " };
+        let (icon, icon_style) = if resolved == total {
+            ("✓ ", theme.conflict.resolved)
+        } else {
+            ("● ", theme.conflict.unresolved)
+        };
+        let name_style = if is_current {
+            theme.ui.selection
+        } else {
+            Style::default().fg(theme.base.foreground)
+        };
+
+        lines.push(Line::from(vec![
+            Span::raw(marker),
+            Span::styled(icon, icon_style),
+            Span::styled(entry.path().display().to_string(), name_style),
+        ]));
+
+        let mut detail = format!("    {resolved}/{total} resolved");
+        if let Some(conflict_type) = entry.conflict_type() {
+            detail.push_str(" · ");
+            detail.push_str(conflict_type.label());
+        }
+        lines.push(Line::from(Span::styled(
+            detail,
+            Style::default().fg(theme.base.muted),
+        )));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.ui.border_unfocused))
+        .title(format!(" Files {}/{} ", current + 1, app.files().len()));
+
+    // Keep the active file in view (two lines per entry)
+    let visible = usize::from(area.height.saturating_sub(2));
+    let active_bottom = (current + 1) * 2;
+    let scroll = u16::try_from(active_bottom.saturating_sub(visible)).unwrap_or(u16::MAX);
+
+    let paragraph = Paragraph::new(lines).block(block).scroll((scroll, 0));
+    frame.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use ratatui::{backend::TestBackend, Terminal};
+    use weavr_core::MergeSession;
+
+    use weavr_git::ConflictType;
+
+    use super::*;
+    use crate::files::FileEntry;
+
+    fn entry(name: &str) -> FileEntry {
+        let content = "<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature";
+        FileEntry::new(MergeSession::from_conflicted(content, PathBuf::from(name)).unwrap())
+    }
+
+    #[test]
+    fn renders_files_with_progress_and_type() {
+        let mut app = App::new();
+        app.set_files(vec![
+            entry("a.rs").with_conflict_type(ConflictType::BothModified),
+            entry("b.rs").with_conflict_type(ConflictType::AddedByThemDeletedByUs),
+        ]);
+        app.resolve_left();
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 8)).unwrap();
+        terminal
+            .draw(|frame| render_file_list(frame, frame.area(), &app))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer.cell((x, y)).unwrap().symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert!(text.contains("Files 1/2"));
+        assert!(text.contains("▶ ✓ a.rs"));
+        assert!(text.contains("1/1 resolved · both modified"));
+        assert!(text.contains("● b.rs"));
+        assert!(text.contains("0/1 resolved · deleted by us"));
+    }
+}
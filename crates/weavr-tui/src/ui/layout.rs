@@ -2,6 +2,7 @@
 //!
 //! The layout consists of:
 //! - Title bar (1 line)
+//! - Optional file list sidebar (when several files are loaded)
 //! - Top row: Left and Right panes side by side
 //! - Bottom row: Result pane
 //! - Status bar (1 line)
@@ -10,11 +11,16 @@ use ratatui::layout::{Constraint, Layout, Rect};
 
 use crate::LayoutConfig;
 
+/// Width of the file list sidebar, in columns.
+const FILE_LIST_WIDTH: u16 = 32;
+
 /// Areas for each UI component.
 #[derive(Debug, Clone, Copy)]
 pub struct PaneAreas {
     /// Title bar area at the top.
     pub title_bar: Rect,
+    /// File list sidebar, if shown.
+    pub file_list: Option<Rect>,
     /// Left pane (ours).
     pub left_pane: Rect,
     /// Right pane (theirs).
@@ -42,6 +48,19 @@ pub struct PaneAreas {
 /// ```
 #[must_use]
 pub fn calculate_layout(area: Rect, config: &LayoutConfig) -> PaneAreas {
+    calculate_layout_with_file_list(area, config, false)
+}
+
+/// Calculates the layout areas, optionally reserving a file list sidebar
+/// to the left of the panes.
+///
+/// The sidebar is at most a quarter of the terminal width.
+#[must_use]
+pub fn calculate_layout_with_file_list(
+    area: Rect,
+    config: &LayoutConfig,
+    show_file_list: bool,
+) -> PaneAreas {
     // Vertical split: title, main, status
     let [title_bar, main_area, status_bar] = Layout::vertical([
         Constraint::Length(1),
@@ -50,6 +69,16 @@ pub fn calculate_layout(area: Rect, config: &LayoutConfig) -> PaneAreas {
     ])
     .areas(area);
 
+    // Optional sidebar on the left of the panes
+    let (file_list, main_area) = if show_file_list {
+        let width = FILE_LIST_WIDTH.min(main_area.width / 4);
+        let [sidebar, rest] =
+            Layout::horizontal([Constraint::Length(width), Constraint::Fill(1)]).areas(main_area);
+        (Some(sidebar), rest)
+    } else {
+        (None, main_area)
+    };
+
     // Split main area into top (left/right) and bottom (result) using config ratio
     let top_percent = config.top_ratio_percent;
     let bottom_percent = 100 - top_percent;
@@ -65,6 +94,7 @@ pub fn calculate_layout(area: Rect, config: &LayoutConfig) -> PaneAreas {
 
     PaneAreas {
         title_bar,
+        file_list,
         left_pane,
         right_pane,
         result_pane,
@@ -147,6 +177,26 @@ mod tests {
         let _ = areas;
     }
 
+    #[test]
+    fn file_list_sits_left_of_panes() {
+        let area = Rect::new(0, 0, 160, 24);
+        let areas = calculate_layout_with_file_list(area, &default_config(), true);
+
+        let sidebar = areas.file_list.expect("sidebar should be shown");
+        assert_eq!(sidebar.width, FILE_LIST_WIDTH);
+        assert_eq!(sidebar.x, 0);
+        assert_eq!(areas.left_pane.x, sidebar.width);
+        assert_eq!(areas.result_pane.width, area.width - sidebar.width);
+    }
+
+    #[test]
+    fn file_list_hidden_by_default() {
+        let area = Rect::new(0, 0, 80, 24);
+        assert!(calculate_layout(area, &default_config())
+            .file_list
+            .is_none());
+    }
+
     #[test]
     fn respects_custom_ratio() {
         let area = Rect::new(0, 0, 80, 24);
@@ -2,24 +2,32 @@
 //!
 //! This module handles all rendering logic using ratatui.
 
+mod file_list;
 mod layout;
 mod overlay;
 mod pane;
 
-pub use layout::{calculate_layout, PaneAreas};
+pub use layout::{calculate_layout, calculate_layout_with_file_list, PaneAreas};
 
 use ratatui::Frame;
 
+use crate::files::file_list_visible;
 use crate::input::Dialog;
 use crate::App;
 
 /// Renders the entire UI to the frame.
 pub fn draw(frame: &mut Frame, app: &App) {
-    let areas = calculate_layout(frame.area(), app.layout_config());
+    let areas =
+        calculate_layout_with_file_list(frame.area(), app.layout_config(), file_list_visible(app));
 
     // Title bar with hunk counter
     pane::render_title_bar(frame, areas.title_bar, app);
 
+    // File list sidebar (multi-file sessions only)
+    if let Some(area) = areas.file_list {
+        file_list::render_file_list(frame, area, app);
+    }
+
     // Three panes with full document content
     pane::render_left_pane(frame, areas.left_pane, app);
     pane::render_right_pane(frame, areas.right_pane, app);
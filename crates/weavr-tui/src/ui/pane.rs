@@ -13,10 +13,13 @@ use ratatui::{
     Frame,
 };
 use similar::ChangeTag;
-use weavr_core::{HunkState, Segment};
+use weavr_core::{HunkState, MarkerLabels, Resolution, Segment};
+use weavr_git::{FileConflict, FileResolution};
 
 use crate::ai::AiState;
+use crate::candidate::{self, candidate_label};
 use crate::diff::{compute_line_diffs, DiffConfig};
+use crate::files::active_file_conflict;
 use crate::input::InputMode;
 use crate::{App, FocusedPane};
 
@@ -30,11 +33,19 @@ pub enum PaneSide {
 }
 
 impl PaneSide {
-    /// Returns the title for this side.
-    fn title(self) -> &'static str {
+    /// Returns the pane's position name.
+    fn name(self) -> &'static str {
         match self {
-            Self::Left => "Left (Ours)",
-            Self::Right => "Right (Theirs)",
+            Self::Left => "Left",
+            Self::Right => "Right",
+        }
+    }
+
+    /// Returns the other side.
+    fn opposite(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
         }
     }
 
@@ -47,6 +58,46 @@ impl PaneSide {
     }
 }
 
+/// Returns the side whose content is shown in the pane at `position`.
+fn displayed_side(app: &App, position: PaneSide) -> PaneSide {
+    if app.swap_sides {
+        position.opposite()
+    } else {
+        position
+    }
+}
+
+/// Returns the title for the pane at `position`.
+///
+/// The side is named by its meaning in the Git operation when known
+/// (e.g. `Left (upstream: Add retries)`), then by the current hunk's
+/// marker label (e.g. `Left (HEAD)`), and otherwise `Ours`/`Theirs`.
+fn pane_title(app: &App, position: PaneSide) -> String {
+    let side = displayed_side(app, position);
+    let label = if let Some(names) = &app.side_names {
+        match side {
+            PaneSide::Left => names.ours.to_string(),
+            PaneSide::Right => names.theirs.to_string(),
+        }
+    } else {
+        let labels = current_labels(app);
+        let (marker, fallback) = match side {
+            PaneSide::Left => (labels.and_then(|l| l.left.as_deref()), "Ours"),
+            PaneSide::Right => (labels.and_then(|l| l.right.as_deref()), "Theirs"),
+        };
+        marker.unwrap_or(fallback).to_string()
+    };
+    format!("{} ({label})", position.name())
+}
+
+/// Returns the marker labels of the current hunk, if a file is loaded.
+fn current_labels(app: &App) -> Option<&MarkerLabels> {
+    app.session()?
+        .hunks()
+        .get(app.current_hunk_index())
+        .map(|hunk| &hunk.labels)
+}
+
 /// Renders the left pane showing the "ours" side of the document.
 pub fn render_left_pane(frame: &mut Frame, area: Rect, app: &App) {
     render_side_pane(frame, area, app, PaneSide::Left);
@@ -58,9 +109,12 @@ pub fn render_right_pane(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 /// Renders a side pane (left or right) with full document content.
-fn render_side_pane(frame: &mut Frame, area: Rect, app: &App, side: PaneSide) {
+///
+/// When the sides are swapped, the pane at `position` shows the other side.
+fn render_side_pane(frame: &mut Frame, area: Rect, app: &App, position: PaneSide) {
     let theme = app.theme();
-    let is_focused = app.focused_pane() == side.focused_pane();
+    let is_focused = app.focused_pane() == position.focused_pane();
+    let side = displayed_side(app, position);
 
     let border_style = if is_focused {
         Style::default().fg(theme.ui.border_focused)
@@ -68,26 +122,30 @@ fn render_side_pane(frame: &mut Frame, area: Rect, app: &App, side: PaneSide) {
         Style::default().fg(theme.ui.border_unfocused)
     };
 
-    let content = match app.session() {
-        Some(session) => build_side_document(
-            session.segments(),
-            session.hunks(),
-            side,
-            app.current_hunk_index(),
-            theme,
-            *app.diff_config(),
-        ),
-        None => vec![Line::from(Span::styled(
-            "No file loaded",
-            Style::default().fg(theme.base.muted),
-        ))],
+    let content = if let Some((conflict, _)) = active_file_conflict(app) {
+        build_file_conflict_side(conflict, side, theme)
+    } else {
+        match app.session() {
+            Some(session) => build_side_document(
+                session.segments(),
+                session.hunks(),
+                side,
+                app.current_hunk_index(),
+                theme,
+                *app.diff_config(),
+            ),
+            None => vec![Line::from(Span::styled(
+                "No file loaded",
+                Style::default().fg(theme.base.muted),
+            ))],
+        }
     };
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(border_style)
-        .title(format!(" {} ", side.title()));
+        .title(format!(" {} ", pane_title(app, position)));
 
     let paragraph = Paragraph::new(content)
         .block(block)
@@ -107,18 +165,23 @@ pub fn render_result_pane(frame: &mut Frame, area: Rect, app: &App) {
         Style::default().fg(theme.ui.border_unfocused)
     };
 
-    let content = match app.session() {
-        Some(session) => build_result_document(
-            session.segments(),
-            session.hunks(),
-            app.current_hunk_index(),
-            theme,
-            app.ai_state(),
-        ),
-        None => vec![Line::from(Span::styled(
-            "No file loaded",
-            Style::default().fg(theme.base.muted),
-        ))],
+    let content = if let Some((conflict, choice)) = active_file_conflict(app) {
+        build_file_conflict_result(conflict, choice, theme)
+    } else {
+        match app.session() {
+            Some(session) => build_result_document(
+                session.segments(),
+                session.hunks(),
+                app.current_hunk_index(),
+                theme,
+                app.ai_state(),
+                candidate::current_candidates(app),
+            ),
+            None => vec![Line::from(Span::styled(
+                "No file loaded",
+                Style::default().fg(theme.base.muted),
+            ))],
+        }
     };
 
     let block = Block::default()
@@ -138,7 +201,13 @@ pub fn render_result_pane(frame: &mut Frame, area: Rect, app: &App) {
 pub fn render_title_bar(frame: &mut Frame, area: Rect, app: &App) {
     let theme = app.theme();
 
-    let hunk_info = if app.total_hunks() > 0 {
+    let hunk_info = if let Some((conflict, choice)) = active_file_conflict(app) {
+        let state = choice.map_or_else(|| "unresolved".to_string(), |r| r.to_string());
+        format!(
+            "File conflict: {} ({state})",
+            conflict.conflict_type.label()
+        )
+    } else if app.total_hunks() > 0 {
         let resolved_count = app.session().map_or(0, |s| {
             s.hunks()
                 .iter()
@@ -156,11 +225,25 @@ pub fn render_title_bar(frame: &mut Frame, area: Rect, app: &App) {
         "No conflicts".to_string()
     };
 
-    let title = Line::from(vec![
-        Span::styled(" weavr ", theme.ui.title),
-        Span::raw("| "),
-        Span::styled(hunk_info, Style::default().fg(theme.base.accent)),
-    ]);
+    let mut spans = vec![Span::styled(" weavr ", theme.ui.title), Span::raw("| ")];
+    if let Some(entry) = app.files().get(app.current_file_index()) {
+        let file_count = app.files().len();
+        let file_info = if file_count > 1 {
+            format!(
+                "{} (file {}/{file_count}) | ",
+                entry.path().display(),
+                app.current_file_index() + 1
+            )
+        } else {
+            format!("{} | ", entry.path().display())
+        };
+        spans.push(Span::raw(file_info));
+    }
+    spans.push(Span::styled(
+        hunk_info,
+        Style::default().fg(theme.base.accent),
+    ));
+    let title = Line::from(spans);
 
     let paragraph = Paragraph::new(title).style(theme.ui.title.bg(theme.base.background));
     frame.render_widget(paragraph, area);
@@ -202,7 +285,7 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, app: &App) {
     let unresolved_count = app.session().map_or(0, |s| {
         s.hunks()
             .iter()
-            .filter(|h| matches!(h.state, HunkState::Unresolved))
+            .filter(|h| !matches!(h.state, HunkState::Resolved(_)))
             .count()
     });
 
@@ -214,7 +297,14 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, app: &App) {
     };
 
     // Format: "Hunk 2/5 | Left pane | 3 unresolved"
-    let status_text = if app.total_hunks() > 0 {
+    let status_text = if let Some((_, choice)) = active_file_conflict(app) {
+        let state = if choice.is_some() {
+            "resolved"
+        } else {
+            "unresolved"
+        };
+        format!(" File conflict | {pane_name} pane | {state}")
+    } else if app.total_hunks() > 0 {
         format!(
             " Hunk {}/{} | {} pane | {} unresolved",
             app.current_hunk_index() + 1,
@@ -229,7 +319,7 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, app: &App) {
     // Add AI indicator when AI is available
     let ai_indicator = if app.ai_available() {
         if app.ai_state().is_loading() {
-            format!(" | AI {}", app.ai_state().spinner_char())
+            format!(" | AI {}", app.ai_state().loading_indicator())
         } else if app
             .current_hunk()
             .is_some_and(|h| app.ai_state().has_suggestion_for(h.id))
@@ -355,6 +445,7 @@ fn build_result_document<'a>(
     current_hunk_idx: usize,
     theme: &'a crate::theme::Theme,
     ai_state: &AiState,
+    candidates: Option<(usize, &[Resolution])>,
 ) -> Vec<Line<'a>> {
     let mut lines = Vec::new();
     let mut line_number = 1;
@@ -412,6 +503,12 @@ fn build_result_document<'a>(
                         format!("──── AI Suggestion{conf_str} ────"),
                         header_style,
                     )));
+                    for warning in &suggestion.warnings {
+                        lines.push(Line::from(Span::styled(
+                            format!("  ! {warning}"),
+                            theme.conflict.unresolved,
+                        )));
+                    }
                     for line_text in suggestion.resolution.content.lines() {
                         // Render ghost lines without consuming line numbers
                         // so subsequent real content retains correct numbering.
@@ -447,6 +544,41 @@ fn build_result_document<'a>(
                         "────────────────────",
                         style.add_modifier(Modifier::BOLD),
                     )));
+                } else if let Some((index, candidates)) = candidates.filter(|_| is_current) {
+                    // Current candidate shown as ghost text
+                    let candidate = &candidates[index];
+                    let ghost_style = Style::default()
+                        .fg(theme.base.muted)
+                        .add_modifier(Modifier::ITALIC);
+                    let header_style = theme.conflict.unresolved.add_modifier(Modifier::BOLD);
+
+                    lines.push(Line::from(Span::styled(
+                        format!(
+                            "──── candidate {}/{} — {} ────",
+                            index + 1,
+                            candidates.len(),
+                            candidate_label(&candidate.kind)
+                        ),
+                        header_style,
+                    )));
+                    for line_text in candidate.content.lines() {
+                        // Ghost lines don't consume line numbers (see AI suggestions)
+                        lines.push(Line::from(vec![
+                            Span::styled(
+                                "   ~ ".to_string(),
+                                Style::default().add_modifier(Modifier::DIM),
+                            ),
+                            Span::styled(line_text.to_string(), ghost_style),
+                        ]));
+                    }
+                    lines.push(Line::from(Span::styled(
+                        "  [c/C] Cycle  [a] Accept  or [o]urs  [t]heirs  [b]oth",
+                        Style::default().fg(theme.base.muted),
+                    )));
+                    lines.push(Line::from(Span::styled(
+                        "────────────────────",
+                        header_style,
+                    )));
                 } else {
                     // Unresolved: show placeholder
                     let style = theme.conflict.unresolved;
@@ -485,6 +617,86 @@ fn build_result_document<'a>(
     lines
 }
 
+/// Builds a side pane for a file-level conflict: the whole file, or a
+/// note that this side deleted it.
+fn build_file_conflict_side<'a>(
+    conflict: &FileConflict,
+    side: PaneSide,
+    theme: &'a crate::theme::Theme,
+) -> Vec<Line<'a>> {
+    let content = match side {
+        PaneSide::Left => conflict.ours.as_deref(),
+        PaneSide::Right => conflict.theirs.as_deref(),
+    };
+    let Some(content) = content else {
+        return vec![Line::from(Span::styled(
+            "(file deleted on this side)",
+            Style::default()
+                .fg(theme.base.muted)
+                .add_modifier(Modifier::ITALIC),
+        ))];
+    };
+
+    let style = Style::default().fg(theme.base.foreground);
+    let lines: Vec<_> = content
+        .lines()
+        .enumerate()
+        .map(|(i, text)| build_line(i + 1, text, style, false))
+        .collect();
+    if lines.is_empty() {
+        return vec![Line::from(Span::styled(
+            "(empty file)",
+            Style::default().fg(theme.base.muted),
+        ))];
+    }
+    lines
+}
+
+/// Builds the result pane for a file-level conflict.
+fn build_file_conflict_result<'a>(
+    conflict: &FileConflict,
+    choice: Option<FileResolution>,
+    theme: &'a crate::theme::Theme,
+) -> Vec<Line<'a>> {
+    let Some(resolution) = choice else {
+        let style = theme.conflict.unresolved.add_modifier(Modifier::BOLD);
+        return vec![
+            Line::from(Span::styled(
+                format!(
+                    "──── FILE CONFLICT: {} ────",
+                    conflict.conflict_type.label()
+                ),
+                style,
+            )),
+            Line::from(Span::styled(
+                "  Select: [o] keep ours  [t] keep theirs  [D] delete",
+                Style::default().fg(theme.base.muted),
+            )),
+            Line::from(Span::styled("────────────────────", style)),
+        ];
+    };
+
+    let style = theme.conflict.resolved;
+    let Some(content) = conflict.content_for(resolution) else {
+        return vec![Line::from(Span::styled(
+            "──── File will be deleted ────",
+            style.add_modifier(Modifier::BOLD),
+        ))];
+    };
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!("──── {resolution} ────"),
+        style.add_modifier(Modifier::BOLD),
+    ))];
+    lines.extend(
+        content
+            .lines()
+            .enumerate()
+            .map(|(i, text)| build_line(i + 1, text, style, false)),
+    );
+    lines
+}
+
 /// Builds a single line with line number and content.
 fn build_line(line_number: usize, text: &str, style: Style, highlight: bool) -> Line<'static> {
     let line_num_style = if highlight {
@@ -548,6 +760,57 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn render_file_level_conflict() {
+        use crate::files::FileEntry;
+
+        let conflict = FileConflict {
+            path: std::path::PathBuf::from("gone.rs"),
+            conflict_type: weavr_git::ConflictType::AddedByUsDeletedByThem,
+            ours: Some("kept line".into()),
+            theirs: None,
+        };
+        let mut app = App::new();
+        app.set_files(vec![FileEntry::file_level(
+            std::path::PathBuf::from("gone.rs"),
+            conflict,
+        )]);
+
+        let mut terminal = create_test_terminal();
+        terminal
+            .draw(|frame| {
+                render_title_bar(frame, Rect::new(0, 0, 80, 1), &app);
+                render_left_pane(frame, Rect::new(0, 1, 40, 5), &app);
+                render_right_pane(frame, Rect::new(40, 1, 40, 5), &app);
+                render_result_pane(frame, Rect::new(0, 6, 80, 5), &app);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = (0..11)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer.cell((x, y)).unwrap().symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(text.contains("gone.rs | File conflict: deleted by them (unresolved)"));
+        assert!(text.contains("kept line"));
+        assert!(text.contains("(file deleted on this side)"));
+        assert!(text.contains("[D] delete"));
+
+        app.resolve_right();
+        terminal
+            .draw(|frame| render_result_pane(frame, Rect::new(0, 0, 80, 5), &app))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let line: String = (0..buffer.area.width)
+            .map(|x| buffer.cell((x, 1)).unwrap().symbol().to_string())
+            .collect();
+        assert!(line.contains("File will be deleted"));
+    }
+
     #[test]
     fn render_title_bar_shows_no_conflicts() {
         let mut terminal = create_test_terminal();
@@ -587,10 +850,77 @@ mod tests {
         assert!(status_line.contains("No conflicts"));
     }
 
+    #[test]
+    fn render_result_pane_shows_candidate_label() {
+        let content = "<<<<<<< HEAD\nleft\n=======\nright\n>>>>>>> feature";
+        let session =
+            weavr_core::MergeSession::from_conflicted(content, std::path::PathBuf::from("a.rs"))
+                .unwrap();
+        let mut app = App::new();
+        app.set_session(session);
+        app.next_candidate();
+
+        let mut terminal = create_test_terminal();
+        terminal
+            .draw(|frame| {
+                let area = Rect::new(0, 0, 80, 10);
+                render_result_pane(frame, area, &app);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let header: String = (0..buffer.area.width)
+            .map(|x| buffer.cell((x, 1)).unwrap().symbol().to_string())
+            .collect();
+        assert!(header.contains("candidate 2/3 — AcceptRight"));
+    }
+
     #[test]
     fn pane_side_titles() {
-        assert_eq!(PaneSide::Left.title(), "Left (Ours)");
-        assert_eq!(PaneSide::Right.title(), "Right (Theirs)");
+        let app = App::new();
+        assert_eq!(pane_title(&app, PaneSide::Left), "Left (Ours)");
+        assert_eq!(pane_title(&app, PaneSide::Right), "Right (Theirs)");
+    }
+
+    #[test]
+    fn pane_titles_use_marker_labels() {
+        let mut app = App::new();
+        let content = "<<<<<<< HEAD\na\n=======\nb\n>>>>>>> feature/payments\n<<<<<<<\nc\n=======\nd\n>>>>>>>";
+        app.set_session(
+            weavr_core::MergeSession::from_conflicted(content, "file.txt".into()).unwrap(),
+        );
+
+        assert_eq!(pane_title(&app, PaneSide::Left), "Left (HEAD)");
+        assert_eq!(
+            pane_title(&app, PaneSide::Right),
+            "Right (feature/payments)"
+        );
+
+        // Hunks without labels fall back to ours/theirs
+        app.next_hunk();
+        assert_eq!(pane_title(&app, PaneSide::Right), "Right (Theirs)");
+    }
+
+    #[test]
+    fn pane_titles_name_rebase_sides_and_follow_swap() {
+        use weavr_git::{GitOperation, SideNames};
+
+        let mut app = App::new();
+        let mut names = SideNames::for_operation(GitOperation::Rebase);
+        names.theirs.subject = Some("Fix typo".into());
+        app.set_side_names(names, false);
+        assert_eq!(pane_title(&app, PaneSide::Left), "Left (upstream)");
+        assert_eq!(
+            pane_title(&app, PaneSide::Right),
+            "Right (your commit being replayed: Fix typo)"
+        );
+
+        app.toggle_swap();
+        assert_eq!(displayed_side(&app, PaneSide::Left), PaneSide::Right);
+        assert_eq!(
+            pane_title(&app, PaneSide::Left),
+            "Left (your commit being replayed: Fix typo)"
+        );
     }
 
     #[test]